@@ -56,6 +56,15 @@ pub trait FileLoader {
 /// model. Everything else in rust-analyzer is derived from these queries.
 #[salsa::query_group(SourceDatabaseStorage)]
 pub trait SourceDatabase: FileLoader + std::fmt::Debug {
+    /// Contents of the file, stored lz4-compressed to keep the resident size
+    /// of big workspaces down.
+    ///
+    /// Note that, being an input, this must be set for every file before any
+    /// query runs. Reading files from disk lazily on first [`file_text`]
+    /// access instead is not an option: queries execute on immutable database
+    /// snapshots and cannot perform IO or set inputs.
+    ///
+    /// [`file_text`]: SourceDatabase::file_text
     #[salsa::input]
     fn compressed_file_text(&self, file_id: FileId) -> Arc<[u8]>;
 
@@ -49,6 +49,10 @@ impl CfgOptions {
         cfg.fold(&|atom| self.enabled.contains(atom))
     }
 
+    pub fn check_atom(&self, cfg: &CfgAtom) -> bool {
+        self.enabled.contains(cfg)
+    }
+
     pub fn insert_atom(&mut self, key: Symbol) {
         self.enabled.insert(CfgAtom::Flag(key));
     }
@@ -136,6 +140,17 @@ impl CfgDiff {
         Some(CfgDiff { enable, disable })
     }
 
+    /// Enables `atoms` on top of the diff. Atoms the diff already disables are
+    /// flipped to enabled, already enabled ones are left alone.
+    pub fn extend_enabled(&mut self, atoms: impl IntoIterator<Item = CfgAtom>) {
+        for atom in atoms {
+            self.disable.retain(|it| *it != atom);
+            if !self.enable.contains(&atom) {
+                self.enable.push(atom);
+            }
+        }
+    }
+
     /// Returns the total number of atoms changed by this diff.
     pub fn len(&self) -> usize {
         self.enable.len() + self.disable.len()
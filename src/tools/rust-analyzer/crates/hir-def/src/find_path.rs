@@ -170,6 +170,7 @@ fn find_path_for_module(
                 path_text_len: 5,
                 stability: Stable,
                 prefer_due_to_prelude: false,
+                prefer_due_to_reexport: false,
             });
         }
         // - otherwise if the item is the crate root of a dependency crate, return the name from the extern prelude
@@ -232,6 +233,7 @@ fn find_path_for_module(
                 path_text_len: path_kind_len(kind),
                 stability: Stable,
                 prefer_due_to_prelude: false,
+                prefer_due_to_reexport: false,
             });
         }
     }
@@ -425,6 +427,7 @@ fn find_in_dep(
     let Some(import_info_for) = import_map.import_info_for(item) else {
         return;
     };
+    let defining_module = item.module(ctx.db);
     for info in import_info_for {
         if info.is_doc_hidden {
             // the item or import is `#[doc(hidden)]`, so skip it as it is in an external crate
@@ -447,6 +450,8 @@ fn find_in_dep(
         if info.is_unstable {
             choice.stability = Unstable;
         }
+        choice.prefer_due_to_reexport =
+            ctx.cfg.prefer_reexports && defining_module != Some(info.container);
 
         Choice::try_select(best_choice, choice, ctx.cfg.prefer_prelude, info.name.clone());
     }
@@ -491,6 +496,8 @@ struct Choice {
     stability: Stability,
     /// Whether this path contains a prelude segment and preference for it has been signaled
     prefer_due_to_prelude: bool,
+    /// Whether this path goes through a re-export and preference for it has been signaled
+    prefer_due_to_reexport: bool,
 }
 
 impl Choice {
@@ -499,6 +506,7 @@ impl Choice {
             path_text_len: path_kind_len(kind) + name.as_str().len(),
             stability,
             prefer_due_to_prelude: prefer_prelude && name == sym::prelude,
+            prefer_due_to_reexport: false,
             path: ModPath::from_segments(kind, iter::once(name)),
         }
     }
@@ -524,6 +532,7 @@ impl Choice {
             .stability
             .cmp(&current.stability)
             .then_with(|| other.prefer_due_to_prelude.cmp(&current.prefer_due_to_prelude))
+            .then_with(|| other.prefer_due_to_reexport.cmp(&current.prefer_due_to_reexport))
             .then_with(|| (current.path.len()).cmp(&(other.path.len() + 1)))
         {
             Ordering::Less => return,
@@ -669,6 +678,7 @@ mod tests {
         prefer_prelude: bool,
         prefer_absolute: bool,
         prefer_no_std: bool,
+        prefer_reexports: bool,
         expect: Expect,
     ) {
         let (db, pos) = TestDB::with_position(ra_fixture);
@@ -710,7 +720,12 @@ mod tests {
                 module,
                 prefix,
                 ignore_local_imports,
-                ImportPathConfig { prefer_no_std, prefer_prelude, prefer_absolute },
+                ImportPathConfig {
+                    prefer_no_std,
+                    prefer_prelude,
+                    prefer_absolute,
+                    prefer_reexports,
+                },
             );
             format_to!(
                 res,
@@ -725,19 +740,63 @@ mod tests {
     }
 
     fn check_found_path(ra_fixture: &str, path: &str, expect: Expect) {
-        check_found_path_(ra_fixture, path, false, false, false, expect);
+        check_found_path_(ra_fixture, path, false, false, false, false, expect);
     }
 
     fn check_found_path_prelude(ra_fixture: &str, path: &str, expect: Expect) {
-        check_found_path_(ra_fixture, path, true, false, false, expect);
+        check_found_path_(ra_fixture, path, true, false, false, false, expect);
     }
 
     fn check_found_path_absolute(ra_fixture: &str, path: &str, expect: Expect) {
-        check_found_path_(ra_fixture, path, false, true, false, expect);
+        check_found_path_(ra_fixture, path, false, true, false, false, expect);
     }
 
     fn check_found_path_prefer_no_std(ra_fixture: &str, path: &str, expect: Expect) {
-        check_found_path_(ra_fixture, path, false, false, true, expect);
+        check_found_path_(ra_fixture, path, false, false, true, false, expect);
+    }
+
+    fn check_found_path_prefer_reexports(ra_fixture: &str, path: &str, expect: Expect) {
+        check_found_path_(ra_fixture, path, false, false, false, true, expect);
+    }
+
+    #[test]
+    fn prefer_reexports_picks_prelude_style_reexport() {
+        let fixture = r#"
+//- /main.rs crate:main deps:dep
+$0
+//- /dep.rs crate:dep
+pub mod prelude {
+    pub use crate::sync::*;
+}
+pub mod sync {
+    pub struct Mutex;
+}
+"#;
+        // By default the path through the defining module wins the tie on length.
+        check_found_path(
+            fixture,
+            "dep::sync::Mutex",
+            expect![[r#"
+                Plain  (imports ✔): dep::sync::Mutex
+                Plain  (imports ✖): dep::sync::Mutex
+                ByCrate(imports ✔): dep::sync::Mutex
+                ByCrate(imports ✖): dep::sync::Mutex
+                BySelf (imports ✔): dep::sync::Mutex
+                BySelf (imports ✖): dep::sync::Mutex
+            "#]],
+        );
+        check_found_path_prefer_reexports(
+            fixture,
+            "dep::sync::Mutex",
+            expect![[r#"
+                Plain  (imports ✔): dep::prelude::Mutex
+                Plain  (imports ✖): dep::prelude::Mutex
+                ByCrate(imports ✔): dep::prelude::Mutex
+                ByCrate(imports ✖): dep::prelude::Mutex
+                BySelf (imports ✔): dep::prelude::Mutex
+                BySelf (imports ✖): dep::prelude::Mutex
+            "#]],
+        );
     }
 
     #[test]
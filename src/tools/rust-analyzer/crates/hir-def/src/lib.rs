@@ -115,6 +115,9 @@ pub struct ImportPathConfig {
     pub prefer_prelude: bool,
     /// If true, prefer abs path (starting with `::`) where it is available.
     pub prefer_absolute: bool,
+    /// If true, prefer paths going through a re-export (glob or explicit) over the path
+    /// through the item's defining module.
+    pub prefer_reexports: bool,
 }
 
 #[derive(Debug)]
@@ -1449,6 +1449,10 @@ impl DefCollector<'_> {
         if recursion_limit.check(depth).is_err() {
             cov_mark::hit!(macro_expansion_overflow);
             tracing::warn!("macro expansion is too deep");
+            self.def_map.diagnostics.push(DefDiagnostic::macro_expansion_overflow(
+                module_id,
+                self.db.lookup_intern_macro_call(macro_call_id).kind,
+            ));
             return;
         }
         let file_id = macro_call_id.as_file();
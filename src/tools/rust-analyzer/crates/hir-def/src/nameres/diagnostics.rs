@@ -26,6 +26,7 @@ pub enum DefDiagnosticKind {
     MalformedDerive { ast: AstId<ast::Adt>, id: usize },
     MacroDefError { ast: AstId<ast::Macro>, message: String },
     MacroError { ast: AstId<ast::Item>, path: ModPath, err: ExpandErrorKind },
+    MacroExpansionOverflow { ast: MacroCallKind },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -91,6 +92,10 @@ impl DefDiagnostic {
         Self { in_module: container, kind: DefDiagnosticKind::MacroError { ast, path, err } }
     }
 
+    pub(super) fn macro_expansion_overflow(container: LocalModuleId, ast: MacroCallKind) -> Self {
+        Self { in_module: container, kind: DefDiagnosticKind::MacroExpansionOverflow { ast } }
+    }
+
     pub fn unconfigured_code(
         container: LocalModuleId,
         tree: TreeId,
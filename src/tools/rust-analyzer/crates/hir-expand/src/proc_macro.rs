@@ -64,6 +64,14 @@ impl ProcMacrosBuilder {
 #[derive(Default, Debug)]
 pub struct ProcMacros(FxHashMap<CrateId, StoredProcMacroLoadResult>);
 
+impl ProcMacros {
+    /// Rebuilds the map with the crate ids mapped through `map`, for use when the crate graph
+    /// is reconstructed with fresh ids.
+    pub fn remap_crate_ids(&self, map: &FxHashMap<CrateId, CrateId>) -> ProcMacros {
+        ProcMacros(self.0.iter().map(|(&krate, res)| (map[&krate], res.clone())).collect())
+    }
+}
+
 impl FromIterator<(CrateId, ProcMacroLoadResult)> for ProcMacros {
     fn from_iter<T: IntoIterator<Item = (CrateId, ProcMacroLoadResult)>>(iter: T) -> Self {
         let mut builder = ProcMacrosBuilder::default();
@@ -1074,6 +1074,7 @@ impl HirDisplay for Ty {
                                 prefer_no_std: false,
                                 prefer_prelude: true,
                                 prefer_absolute: false,
+                                prefer_reexports: false,
                             },
                         ) {
                             write!(f, "{}", path.display(f.db.upcast()))?;
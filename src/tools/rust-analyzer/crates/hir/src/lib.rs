@@ -58,7 +58,8 @@ use hir_def::{
     TypeOrConstParamId, TypeParamId, UnionId,
 };
 use hir_expand::{
-    attrs::collect_attrs, proc_macro::ProcMacroKind, AstId, MacroCallKind, ValueResult,
+    attrs::collect_attrs, proc_macro::ProcMacroKind, AstId, ExpandErrorKind, MacroCallKind,
+    ValueResult,
 };
 use hir_ty::{
     all_super_traits, autoderef, check_orphan_rules,
@@ -916,6 +917,11 @@ fn emit_def_diagnostic_(
                 .into(),
             )
         }
+        DefDiagnosticKind::MacroExpansionOverflow { ast } => {
+            let (node, precise_location) = precise_macro_call_location(ast, db);
+            let (message, error) = ExpandErrorKind::RecursionOverflow.render_to_string(db.upcast());
+            acc.push(MacroError { node, precise_location, message, error }.into())
+        }
         DefDiagnosticKind::UnresolvedImport { id, index } => {
             let file_id = id.file_id();
             let item_tree = id.item_tree(db.upcast());
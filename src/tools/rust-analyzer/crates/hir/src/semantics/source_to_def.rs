@@ -128,8 +128,21 @@ impl SourceToDefCtx<'_, '_> {
     pub(super) fn file_to_def(&mut self, file: FileId) -> &SmallVec<[ModuleId; 1]> {
         let _p = tracing::info_span!("SourceToDefCtx::file_to_def").entered();
         self.cache.file_to_def_cache.entry(file).or_insert_with(|| {
+            let relevant_crates = self.db.relevant_crates(file);
+            let crate_graph = self.db.crate_graph();
+            let mut crates: SmallVec<[_; 1]> = relevant_crates.iter().copied().collect();
+            // A file shared between several targets of a package belongs to one crate per
+            // target. Visit the library target first -- it is the candidate the others
+            // depend on -- so that features which can only honor a single module pick its
+            // context deterministically.
+            crates.sort_by_cached_key(|&krate| {
+                !relevant_crates.iter().any(|&other| {
+                    other != krate
+                        && crate_graph[other].dependencies.iter().any(|dep| dep.crate_id == krate)
+                })
+            });
             let mut mods = SmallVec::new();
-            for &crate_id in self.db.relevant_crates(file).iter() {
+            for crate_id in crates {
                 // Note: `mod` declarations in block modules cannot be supported here
                 let crate_def_map = self.db.crate_def_map(crate_id);
                 let n_mods = mods.len();
@@ -133,6 +133,7 @@ pub(crate) struct Assists {
     resolve: AssistResolveStrategy,
     buf: Vec<Assist>,
     allowed: Option<Vec<AssistKind>>,
+    disabled: Vec<String>,
 }
 
 impl Assists {
@@ -142,6 +143,7 @@ impl Assists {
             file: ctx.frange.file_id.file_id(),
             buf: Vec::new(),
             allowed: ctx.config.allowed.clone(),
+            disabled: ctx.config.disabled.clone(),
         }
     }
 
@@ -202,6 +204,9 @@ impl Assists {
     }
 
     fn is_allowed(&self, id: &AssistId) -> bool {
+        if self.disabled.iter().any(|it| it == id.0) {
+            return false;
+        }
         match &self.allowed {
             Some(allowed) => allowed.iter().any(|kind| kind.contains(id.1)),
             None => true,
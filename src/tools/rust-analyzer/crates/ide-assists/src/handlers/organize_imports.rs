@@ -0,0 +1,312 @@
+use std::cmp::Ordering;
+
+use ide_db::imports::{
+    insert_use::ImportGranularity,
+    merge_imports::{try_merge_imports, try_normalize_import, use_tree_cmp, MergeBehavior},
+};
+use syntax::{
+    ast::{self, edit_in_place::Removable, HasAttrs, HasVisibility},
+    ted, AstNode, SyntaxNode,
+};
+
+use crate::{
+    assist_context::{AssistContext, Assists},
+    handlers::remove_unused_imports::unused_use_trees,
+    AssistId, AssistKind,
+};
+
+// Assist: organize_imports
+//
+// Sorts and merges the `use` items surrounding the cursor, removing imports that are unused in
+// the current file.
+//
+// ```
+// mod foo { pub struct Bar; pub struct Baz; }
+// use foo::Baz;
+// use foo$0::Bar;
+//
+// fn main() {
+//     let _ = (Bar, Baz);
+// }
+// ```
+// ->
+// ```
+// mod foo { pub struct Bar; pub struct Baz; }
+// use foo::{Bar, Baz};
+//
+// fn main() {
+//     let _ = (Bar, Baz);
+// }
+// ```
+pub(crate) fn organize_imports(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let use_item: ast::Use = if ctx.has_empty_selection() {
+        ctx.find_node_at_offset()?
+    } else {
+        ctx.covering_element().ancestors().find_map(ast::Use::cast)?
+    };
+
+    // All the `use` items that are direct siblings of the one under the cursor.
+    let uses: Vec<ast::Use> =
+        use_item.syntax().parent()?.children().filter_map(ast::Use::cast).collect();
+
+    // Whether a `#[cfg]`'d import is used cannot be answered for inactive configurations, and a
+    // re-export is part of the module's interface even when it goes unused here, so neither is a
+    // candidate for removal.
+    let removal_candidates: Vec<ast::Use> = uses
+        .iter()
+        .filter(|it| it.attrs().next().is_none() && it.visibility().is_none())
+        .cloned()
+        .collect();
+    let unused: Vec<ast::UseTree> = unused_use_trees(ctx, removal_candidates).collect();
+
+    let merge_behavior = match ctx.config.insert_use.granularity {
+        ImportGranularity::Crate => Some(MergeBehavior::Crate),
+        ImportGranularity::Module => Some(MergeBehavior::Module),
+        ImportGranularity::One => Some(MergeBehavior::One),
+        ImportGranularity::Item | ImportGranularity::Preserve => None,
+    };
+
+    if unused.is_empty() && !any_merges(&uses, merge_behavior) && is_normalized(ctx, &uses) {
+        return None;
+    }
+
+    let target = use_item.syntax().text_range();
+    acc.add(
+        AssistId("organize_imports", AssistKind::RefactorRewrite),
+        "Organize imports",
+        target,
+        |builder| {
+            let unused: Vec<_> = unused.into_iter().map(|it| builder.make_mut(it)).collect();
+            let mut uses: Vec<_> = uses.into_iter().map(|it| builder.make_mut(it)).collect();
+            for node in unused {
+                node.remove_recursive();
+            }
+            // Removing the last use tree takes the whole `use` item with it.
+            uses.retain(|it| it.syntax().parent().is_some());
+
+            if let Some(merge_behavior) = merge_behavior {
+                let mut idx = 0;
+                while idx < uses.len() {
+                    let mut other = idx + 1;
+                    while other < uses.len() {
+                        match try_merge_imports(&uses[idx], &uses[other], merge_behavior) {
+                            Some(merged) => {
+                                ted::replace(uses[idx].syntax(), merged.syntax());
+                                uses[other].remove();
+                                uses[idx] = merged;
+                                uses.remove(other);
+                            }
+                            None => other += 1,
+                        }
+                    }
+                    idx += 1;
+                }
+            }
+
+            for use_item in uses.iter_mut() {
+                if let Some(normalized) =
+                    try_normalize_import(use_item, ctx.config.insert_use.granularity.into())
+                {
+                    ted::replace(use_item.syntax(), normalized.syntax());
+                    *use_item = normalized;
+                }
+            }
+
+            let mut sorted = uses.clone();
+            sorted.sort_by(|a, b| use_cmp(a, b));
+            if sorted != uses {
+                // Replace each `use` in place with a copy of the one that sorts there, so that
+                // only the items move while comments and whitespace between them stay put.
+                let in_order: Vec<SyntaxNode> = sorted
+                    .iter()
+                    .map(|it| it.syntax().clone_subtree().clone_for_update())
+                    .collect();
+                for (slot, replacement) in uses.iter().zip(in_order) {
+                    if slot.syntax().text() != replacement.text() {
+                        ted::replace(slot.syntax(), replacement);
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Whether the configured merge behavior would collapse any two of the `use` items into one.
+fn any_merges(uses: &[ast::Use], merge_behavior: Option<MergeBehavior>) -> bool {
+    let Some(merge_behavior) = merge_behavior else { return false };
+    uses.iter()
+        .enumerate()
+        .flat_map(|(idx, lhs)| uses[idx + 1..].iter().map(move |rhs| (lhs, rhs)))
+        .any(|(lhs, rhs)| try_merge_imports(lhs, rhs, merge_behavior).is_some())
+}
+
+/// Whether every `use` item is already normalized and the group is sorted.
+fn is_normalized(ctx: &AssistContext<'_>, uses: &[ast::Use]) -> bool {
+    uses.iter()
+        .all(|it| try_normalize_import(it, ctx.config.insert_use.granularity.into()).is_none())
+        && uses.windows(2).all(|pair| use_cmp(&pair[0], &pair[1]) != Ordering::Greater)
+}
+
+fn use_cmp(a: &ast::Use, b: &ast::Use) -> Ordering {
+    match (a.use_tree(), b.use_tree()) {
+        (Some(a), Some(b)) => use_tree_cmp(&a, &b),
+        _ => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_import_one, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn sorts_and_merges_plain_imports() {
+        check_assist(
+            organize_imports,
+            r#"
+mod foo { pub struct B; pub struct C; }
+mod bar { pub struct A; }
+use foo::C;
+use bar::A;
+use foo$0::B;
+
+fn f() {
+    let _ = (A, B, C);
+}
+"#,
+            r#"
+mod foo { pub struct B; pub struct C; }
+mod bar { pub struct A; }
+use bar::A;
+use foo::{B, C};
+
+fn f() {
+    let _ = (A, B, C);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn removes_unused_imports() {
+        check_assist(
+            organize_imports,
+            r#"
+mod foo { pub struct A; pub struct B; }
+use foo::{A$0, B};
+
+fn f() {
+    let _ = A;
+}
+"#,
+            r#"
+mod foo { pub struct A; pub struct B; }
+use foo::A;
+
+fn f() {
+    let _ = A;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keeps_cfgd_imports_and_reexports() {
+        check_assist(
+            organize_imports,
+            r#"
+mod foo { pub struct A; pub struct B; pub struct C; }
+#[cfg(test)]
+use foo::A;
+pub use foo::B;
+use foo$0::C;
+"#,
+            r#"
+mod foo { pub struct A; pub struct B; pub struct C; }
+#[cfg(test)]
+use foo::A;
+pub use foo::B;
+"#,
+        );
+    }
+
+    #[test]
+    fn normalizes_nested_groups() {
+        check_assist(
+            organize_imports,
+            r#"
+mod foo { pub struct A; pub struct B; }
+use foo::{B, {A}}$0;
+
+fn f() {
+    let _ = (A, B);
+}
+"#,
+            r#"
+mod foo { pub struct A; pub struct B; }
+use foo::{A, B};
+
+fn f() {
+    let _ = (A, B);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn import_one_granularity_merges_into_single_use() {
+        check_assist_import_one(
+            organize_imports,
+            r#"
+mod foo { pub struct A; }
+mod bar { pub struct B; }
+use foo::A;
+use bar$0::B;
+
+fn f() {
+    let _ = (A, B);
+}
+"#,
+            r#"
+mod foo { pub struct A; }
+mod bar { pub struct B; }
+use {bar::B, foo::A};
+
+fn f() {
+    let _ = (A, B);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_already_organized() {
+        check_assist_not_applicable(
+            organize_imports,
+            r#"
+mod foo { pub struct A; pub struct B; }
+use foo::{A, B}$0;
+
+fn f() {
+    let _ = (A, B);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_outside_of_use() {
+        check_assist_not_applicable(
+            organize_imports,
+            r#"
+mod foo { pub struct A; }
+use foo::A;
+
+fn f$0() {
+    let _ = A;
+}
+"#,
+        );
+    }
+}
@@ -42,15 +42,38 @@ pub(crate) fn remove_unused_imports(acc: &mut Assists, ctx: &AssistContext<'_>)
         .filter_map(ast::Use::cast);
     let uses = uses_up.chain(uses_down).collect::<Vec<_>>();
 
+    // Peek so we terminate early if an unused use is found. Only do the rest of the work if the user selects the assist.
+    let mut unused = unused_use_trees(ctx, uses).peekable();
+    if unused.peek().is_some() {
+        acc.add(
+            AssistId("remove_unused_imports", AssistKind::QuickFix),
+            "Remove all the unused imports",
+            selected_el.text_range(),
+            |builder| {
+                let unused: Vec<ast::UseTree> = unused.map(|x| builder.make_mut(x)).collect();
+                for node in unused {
+                    node.remove_recursive();
+                }
+            },
+        )
+    } else {
+        None
+    }
+}
+
+/// Iterator over the leaf use trees of `uses` whose imports are not used anywhere in their
+/// containing module, excluding submodules.
+pub(crate) fn unused_use_trees<'ctx>(
+    ctx: &'ctx AssistContext<'_>,
+    uses: Vec<ast::Use>,
+) -> impl Iterator<Item = ast::UseTree> + 'ctx {
     // Maps use nodes to the scope that we should search through to find
     let mut search_scopes = FxHashMap::<Module, Vec<SearchScope>>::default();
 
-    // iterator over all unused use trees
-    let mut unused = uses
-        .into_iter()
+    uses.into_iter()
         .flat_map(|u| u.syntax().descendants().filter_map(ast::UseTree::cast))
         .filter(|u| u.use_tree_list().is_none())
-        .filter_map(|u| {
+        .filter_map(move |u| {
             // Find any uses trees that are unused
 
             let use_module = ctx.sema.scope(u.syntax()).map(|s| s.module())?;
@@ -118,24 +141,6 @@ pub(crate) fn remove_unused_imports(acc: &mut Assists, ctx: &AssistContext<'_>)
 
             None
         })
-        .peekable();
-
-    // Peek so we terminate early if an unused use is found. Only do the rest of the work if the user selects the assist.
-    if unused.peek().is_some() {
-        acc.add(
-            AssistId("remove_unused_imports", AssistKind::QuickFix),
-            "Remove all the unused imports",
-            selected_el.text_range(),
-            |builder| {
-                let unused: Vec<ast::UseTree> = unused.map(|x| builder.make_mut(x)).collect();
-                for node in unused {
-                    node.remove_recursive();
-                }
-            },
-        )
-    } else {
-        None
-    }
 }
 
 fn used_once_in_scope(ctx: &AssistContext<'_>, def: Definition, scopes: &Vec<SearchScope>) -> bool {
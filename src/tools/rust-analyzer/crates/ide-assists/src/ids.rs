@@ -0,0 +1,145 @@
+//! Generated by `cargo codegen assists-doc-tests`, do not edit by hand.
+
+pub const ASSIST_IDS: &[&str] = &[
+    "add_braces",
+    "add_explicit_type",
+    "add_hash",
+    "add_impl_default_members",
+    "add_impl_missing_members",
+    "add_label_to_loop",
+    "add_lifetime_to_type",
+    "add_missing_match_arms",
+    "add_return_type",
+    "add_turbo_fish",
+    "apply_demorgan",
+    "apply_demorgan_iterator",
+    "auto_import",
+    "bind_unused_param",
+    "bool_to_enum",
+    "change_visibility",
+    "comment_to_doc",
+    "convert_bool_then_to_if",
+    "convert_for_loop_with_for_each",
+    "convert_from_to_tryfrom",
+    "convert_if_to_bool_then",
+    "convert_integer_literal",
+    "convert_into_to_from",
+    "convert_iter_for_each_to_for",
+    "convert_let_else_to_match",
+    "convert_match_to_let_else",
+    "convert_named_struct_to_tuple_struct",
+    "convert_nested_function_to_closure",
+    "convert_to_guarded_return",
+    "convert_tuple_return_type_to_struct",
+    "convert_tuple_struct_to_named_struct",
+    "convert_two_arm_bool_match_to_matches_macro",
+    "convert_while_to_loop",
+    "destructure_struct_binding",
+    "destructure_tuple_binding",
+    "desugar_async_into_impl_future",
+    "desugar_doc_comment",
+    "expand_glob_import",
+    "extract_expressions_from_format_string",
+    "extract_function",
+    "extract_module",
+    "extract_struct_from_enum_variant",
+    "extract_type_alias",
+    "extract_variable",
+    "fill_record_pattern_fields",
+    "fix_visibility",
+    "flip_binexpr",
+    "flip_comma",
+    "flip_trait_bound",
+    "generate_constant",
+    "generate_default_from_enum_variant",
+    "generate_default_from_new",
+    "generate_delegate_methods",
+    "generate_delegate_trait",
+    "generate_deref",
+    "generate_derive",
+    "generate_doc_example",
+    "generate_documentation_template",
+    "generate_enum_as_method",
+    "generate_enum_is_method",
+    "generate_enum_try_into_method",
+    "generate_enum_variant",
+    "generate_from_impl_for_enum",
+    "generate_function",
+    "generate_getter",
+    "generate_getter_mut",
+    "generate_impl",
+    "generate_is_empty_from_len",
+    "generate_mut_trait_impl",
+    "generate_new",
+    "generate_setter",
+    "generate_trait_from_impl",
+    "generate_trait_impl",
+    "inline_call",
+    "inline_const_as_literal",
+    "inline_into_callers",
+    "inline_local_variable",
+    "inline_macro",
+    "inline_type_alias",
+    "inline_type_alias_uses",
+    "into_to_qualified_from",
+    "introduce_named_generic",
+    "introduce_named_lifetime",
+    "invert_if",
+    "line_to_block",
+    "make_raw_string",
+    "make_usual_string",
+    "merge_imports",
+    "merge_match_arms",
+    "merge_nested_if",
+    "move_arm_cond_to_match_guard",
+    "move_bounds_to_where_clause",
+    "move_const_to_impl",
+    "move_from_mod_rs",
+    "move_guard_to_arm_body",
+    "move_module_to_file",
+    "move_to_mod_rs",
+    "normalize_import",
+    "organize_imports",
+    "promote_local_to_const",
+    "pull_assignment_up",
+    "qualify_method_call",
+    "qualify_path",
+    "reformat_number_literal",
+    "remove_dbg",
+    "remove_hash",
+    "remove_mut",
+    "remove_parentheses",
+    "remove_unused_imports",
+    "remove_unused_param",
+    "reorder_fields",
+    "reorder_impl_items",
+    "replace_arith_with_checked",
+    "replace_arith_with_saturating",
+    "replace_arith_with_wrapping",
+    "replace_char_with_string",
+    "replace_derive_with_manual_impl",
+    "replace_if_let_with_match",
+    "replace_is_some_with_if_let_some",
+    "replace_let_with_if_let",
+    "replace_match_with_if_let",
+    "replace_named_generic_with_impl",
+    "replace_qualified_name_with_use",
+    "replace_string_with_char",
+    "replace_try_expr_with_match",
+    "replace_turbofish_with_explicit_type",
+    "replace_with_eager_method",
+    "replace_with_lazy_method",
+    "sort_items",
+    "split_import",
+    "sugar_impl_future_into_async",
+    "toggle_ignore",
+    "unmerge_match_arm",
+    "unmerge_use",
+    "unnecessary_async",
+    "unqualify_method_call",
+    "unwrap_block",
+    "unwrap_result_return_type",
+    "unwrap_tuple",
+    "wrap_return_type_in_result",
+    "wrap_unwrap_cfg_attr",
+];
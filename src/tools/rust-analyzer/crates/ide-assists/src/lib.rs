@@ -60,6 +60,7 @@
 
 mod assist_config;
 mod assist_context;
+mod ids;
 #[cfg(test)]
 mod tests;
 pub mod utils;
@@ -74,6 +75,7 @@ pub use assist_config::AssistConfig;
 pub use ide_db::assists::{
     Assist, AssistId, AssistKind, AssistResolveStrategy, GroupLabel, SingleResolve,
 };
+pub use ids::ASSIST_IDS;
 
 /// Return all the assists applicable at the given position.
 ///
@@ -185,6 +187,7 @@ mod handlers {
     mod move_to_mod_rs;
     mod normalize_import;
     mod number_representation;
+    mod organize_imports;
     mod promote_local_to_const;
     mod pull_assignment_up;
     mod qualify_method_call;
@@ -312,6 +315,7 @@ mod handlers {
             move_from_mod_rs::move_from_mod_rs,
             normalize_import::normalize_import,
             number_representation::reformat_number_literal,
+            organize_imports::organize_imports,
             pull_assignment_up::pull_assignment_up,
             promote_local_to_const::promote_local_to_const,
             qualify_path::qualify_path,
@@ -21,6 +21,7 @@ use crate::{
 pub(crate) const TEST_CONFIG: AssistConfig = AssistConfig {
     snippet_cap: SnippetCap::new(true),
     allowed: None,
+    disabled: Vec::new(),
     insert_use: InsertUseConfig {
         granularity: ImportGranularity::Crate,
         prefix_kind: hir::PrefixKind::Plain,
@@ -31,6 +32,7 @@ pub(crate) const TEST_CONFIG: AssistConfig = AssistConfig {
     prefer_no_std: false,
     prefer_prelude: true,
     prefer_absolute: false,
+    prefer_reexports: false,
     assist_emit_must_use: false,
     term_search_fuel: 400,
     term_search_borrowck: true,
@@ -39,6 +41,7 @@ pub(crate) const TEST_CONFIG: AssistConfig = AssistConfig {
 pub(crate) const TEST_CONFIG_NO_SNIPPET_CAP: AssistConfig = AssistConfig {
     snippet_cap: None,
     allowed: None,
+    disabled: Vec::new(),
     insert_use: InsertUseConfig {
         granularity: ImportGranularity::Crate,
         prefix_kind: hir::PrefixKind::Plain,
@@ -49,6 +52,7 @@ pub(crate) const TEST_CONFIG_NO_SNIPPET_CAP: AssistConfig = AssistConfig {
     prefer_no_std: false,
     prefer_prelude: true,
     prefer_absolute: false,
+    prefer_reexports: false,
     assist_emit_must_use: false,
     term_search_fuel: 400,
     term_search_borrowck: true,
@@ -57,6 +61,7 @@ pub(crate) const TEST_CONFIG_NO_SNIPPET_CAP: AssistConfig = AssistConfig {
 pub(crate) const TEST_CONFIG_IMPORT_ONE: AssistConfig = AssistConfig {
     snippet_cap: SnippetCap::new(true),
     allowed: None,
+    disabled: Vec::new(),
     insert_use: InsertUseConfig {
         granularity: ImportGranularity::One,
         prefix_kind: hir::PrefixKind::Plain,
@@ -67,6 +72,7 @@ pub(crate) const TEST_CONFIG_IMPORT_ONE: AssistConfig = AssistConfig {
     prefer_no_std: false,
     prefer_prelude: true,
     prefer_absolute: false,
+    prefer_reexports: false,
     assist_emit_must_use: false,
     term_search_fuel: 400,
     term_search_borrowck: true,
@@ -421,6 +427,33 @@ pub fn test_some_range(a: int) -> bool {
     }
 }
 
+#[test]
+fn disabled_assists_are_not_offered() {
+    let (db, frange) = RootDatabase::with_range(
+        r#"
+pub fn test_some_range(a: int) -> bool {
+    if let 2..6 = $05$0 {
+        true
+    } else {
+        false
+    }
+}
+"#,
+    );
+
+    let mut cfg = TEST_CONFIG;
+    cfg.disabled = vec!["extract_variable".to_owned(), "convert_integer_literal".to_owned()];
+
+    let assists = assists(&db, &cfg, AssistResolveStrategy::None, frange.into());
+    let expected = labels(&assists);
+
+    expect![[r#"
+        Extract into function
+        Replace if let with match
+    "#]]
+    .assert_eq(&expected);
+}
+
 #[test]
 fn various_resolve_strategies() {
     let (db, frange) = RootDatabase::with_range(
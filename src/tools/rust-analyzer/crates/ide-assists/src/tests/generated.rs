@@ -2355,6 +2355,30 @@ use std::{fmt::Formatter, io};
     )
 }
 
+#[test]
+fn doctest_organize_imports() {
+    check_doc_test(
+        "organize_imports",
+        r#####"
+mod foo { pub struct Bar; pub struct Baz; }
+use foo::Baz;
+use foo$0::Bar;
+
+fn main() {
+    let _ = (Bar, Baz);
+}
+"#####,
+        r#####"
+mod foo { pub struct Bar; pub struct Baz; }
+use foo::{Bar, Baz};
+
+fn main() {
+    let _ = (Bar, Baz);
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_promote_local_to_const() {
     check_doc_test(
@@ -13,7 +13,7 @@ use crate::{
         TypeLocation,
     },
     render::{render_resolution_with_import, render_resolution_with_import_pat, RenderContext},
-    Completions,
+    CompletionScope, Completions,
 };
 
 // Feature: Completion With Autoimport
@@ -261,6 +261,8 @@ fn import_on_the_fly(
     import_assets
         .search_for_imports(&ctx.sema, import_cfg, ctx.config.insert_use.prefix_kind)
         .filter(ns_filter)
+        .filter(|import| scope_filter(ctx, import.original_item))
+        .filter(|import| exclusion_filter(ctx, import))
         .filter(|import| {
             let original_item = &import.original_item;
             !ctx.is_item_hidden(&import.item_to_import)
@@ -307,6 +309,8 @@ fn import_on_the_fly_pat_(
     import_assets
         .search_for_imports(&ctx.sema, cfg, ctx.config.insert_use.prefix_kind)
         .filter(ns_filter)
+        .filter(|import| scope_filter(ctx, import.original_item))
+        .filter(|import| exclusion_filter(ctx, import))
         .filter(|import| {
             let original_item = &import.original_item;
             !ctx.is_item_hidden(&import.item_to_import)
@@ -348,6 +352,8 @@ fn import_on_the_fly_method(
 
     import_assets
         .search_for_imports(&ctx.sema, cfg, ctx.config.insert_use.prefix_kind)
+        .filter(|import| scope_filter(ctx, import.original_item))
+        .filter(|import| exclusion_filter(ctx, import))
         .filter(|import| {
             !ctx.is_item_hidden(&import.item_to_import)
                 && !ctx.is_item_hidden(&import.original_item)
@@ -369,6 +375,32 @@ fn import_on_the_fly_method(
     Some(())
 }
 
+/// Whether the import survives `completion.autoimport.exclude`: imports whose
+/// inserted path starts with one of the configured module path prefixes are
+/// dropped.
+fn exclusion_filter(ctx: &CompletionContext<'_>, import: &LocatedImport) -> bool {
+    if ctx.config.autoimport_exclude.is_empty() {
+        return true;
+    }
+    let path = import.import_path.display(ctx.db).to_string();
+    !ctx.config.autoimport_exclude.iter().any(|prefix| {
+        path.strip_prefix(prefix.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+    })
+}
+
+/// Whether the completion scope allows offering an import of the item: with
+/// [`CompletionScope::Crate`] only items from the current crate and the
+/// sysroot crates are proposed.
+fn scope_filter(ctx: &CompletionContext<'_>, item: ItemInNs) -> bool {
+    match ctx.config.scope {
+        CompletionScope::Workspace => true,
+        CompletionScope::Crate => item
+            .krate(ctx.db)
+            .is_none_or(|krate| krate == ctx.krate || krate.is_builtin(ctx.db)),
+    }
+}
+
 fn import_name(ctx: &CompletionContext<'_>) -> String {
     let token_kind = ctx.token.kind();
     if matches!(token_kind, T![.] | T![::]) {
@@ -13,6 +13,7 @@ use crate::snippet::Snippet;
 pub struct CompletionConfig {
     pub enable_postfix_completions: bool,
     pub enable_imports_on_the_fly: bool,
+    pub autoimport_exclude: Vec<String>,
     pub enable_self_on_the_fly: bool,
     pub enable_private_editable: bool,
     pub enable_term_search: bool,
@@ -24,8 +25,44 @@ pub struct CompletionConfig {
     pub prefer_no_std: bool,
     pub prefer_prelude: bool,
     pub prefer_absolute: bool,
+    pub prefer_reexports: bool,
     pub snippets: Vec<Snippet>,
     pub limit: Option<usize>,
+    pub scope: CompletionScope,
+    pub scoring: CompletionScoringWeights,
+}
+
+/// User-adjustable weights layered on top of [`CompletionRelevance::score`]
+/// when ranking completions, for users who e.g. want locals ranked above
+/// methods or the other way around.
+///
+/// [`CompletionRelevance::score`]: crate::CompletionRelevance::score
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CompletionScoringWeights {
+    /// Additional weight for locals.
+    pub local: u32,
+    /// Additional weight for struct and union fields.
+    pub field: u32,
+    /// Additional weight for methods.
+    pub method: u32,
+    /// Additional weight for completions that add an import.
+    pub import: u32,
+    /// Additional weight for completions the user recently accepted.
+    pub recency: u32,
+    /// Additional weight for completions whose name matches what was typed exactly.
+    pub prefix_match: u32,
+}
+
+impl CompletionScoringWeights {
+    /// Leaves the built-in relevance ranking untouched.
+    pub const UNWEIGHTED: Self = CompletionScoringWeights {
+        local: 0,
+        field: 0,
+        method: 0,
+        import: 0,
+        recency: 0,
+        prefix_match: 0,
+    };
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -34,6 +71,15 @@ pub enum CallableSnippets {
     AddParentheses,
 }
 
+/// Which crates to search for completion candidates that are not yet in scope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompletionScope {
+    /// Only the current crate and the sysroot crates.
+    Crate,
+    /// The current crate and all of its dependencies.
+    Workspace,
+}
+
 impl CompletionConfig {
     pub fn postfix_snippets(&self) -> impl Iterator<Item = (&str, &Snippet)> {
         self.snippets
@@ -52,6 +98,7 @@ impl CompletionConfig {
             prefer_no_std: self.prefer_no_std,
             prefer_prelude: self.prefer_prelude,
             prefer_absolute: self.prefer_absolute,
+            prefer_reexports: self.prefer_reexports,
         }
     }
 }
@@ -14,6 +14,7 @@ use syntax::{format_smolstr, SmolStr, TextRange, TextSize};
 use text_edit::TextEdit;
 
 use crate::{
+    config::CompletionScoringWeights,
     context::{CompletionContext, PathCompletionCtx},
     render::{render_path_resolution, RenderContext},
 };
@@ -427,6 +428,32 @@ impl CompletionItem {
         self.lookup.as_str()
     }
 
+    /// The additive bump the user's scoring weights award this item, to be
+    /// added on top of [`CompletionRelevance::score`]. `recently_used` is
+    /// supplied by the caller, which is the one in a position to track which
+    /// completions the user accepted before.
+    pub fn score_bump(&self, weights: &CompletionScoringWeights, recently_used: bool) -> u32 {
+        let CompletionScoringWeights { local, field, method, import, recency, prefix_match } =
+            *weights;
+
+        let mut bump = match self.kind {
+            CompletionItemKind::SymbolKind(SymbolKind::Local) => local,
+            CompletionItemKind::SymbolKind(SymbolKind::Field) => field,
+            CompletionItemKind::SymbolKind(SymbolKind::Method) => method,
+            _ => 0,
+        };
+        if self.relevance.requires_import {
+            bump += import;
+        }
+        if self.relevance.exact_name_match {
+            bump += prefix_match;
+        }
+        if recently_used {
+            bump += recency;
+        }
+        bump
+    }
+
     pub fn ref_match(&self) -> Option<(String, text_edit::Indel, CompletionRelevance)> {
         // Relevance of the ref match should be the same as the original
         // match, but with exact type match set because self.ref_match
@@ -30,7 +30,7 @@ use crate::{
 };
 
 pub use crate::{
-    config::{CallableSnippets, CompletionConfig},
+    config::{CallableSnippets, CompletionConfig, CompletionScope, CompletionScoringWeights},
     item::{
         CompletionItem, CompletionItemKind, CompletionRelevance, CompletionRelevancePostfixMatch,
     },
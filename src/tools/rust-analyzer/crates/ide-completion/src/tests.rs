@@ -37,7 +37,7 @@ use test_utils::assert_eq_text;
 
 use crate::{
     resolve_completion_edits, CallableSnippets, CompletionConfig, CompletionItem,
-    CompletionItemKind,
+    CompletionItemKind, CompletionScope, CompletionScoringWeights,
 };
 
 /// Lots of basic item definitions
@@ -63,6 +63,7 @@ union Union { field: i32 }
 pub(crate) const TEST_CONFIG: CompletionConfig = CompletionConfig {
     enable_postfix_completions: true,
     enable_imports_on_the_fly: true,
+    autoimport_exclude: Vec::new(),
     enable_self_on_the_fly: true,
     enable_private_editable: false,
     enable_term_search: true,
@@ -80,8 +81,11 @@ pub(crate) const TEST_CONFIG: CompletionConfig = CompletionConfig {
     prefer_no_std: false,
     prefer_prelude: true,
     prefer_absolute: false,
+    prefer_reexports: false,
     snippets: Vec::new(),
     limit: None,
+    scope: CompletionScope::Workspace,
+    scoring: CompletionScoringWeights::UNWEIGHTED,
 };
 
 pub(crate) fn completion_list(ra_fixture: &str) -> String {
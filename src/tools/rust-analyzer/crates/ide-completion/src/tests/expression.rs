@@ -1320,3 +1320,106 @@ fn main() {
         "#]],
     );
 }
+
+#[test]
+fn cfg_test_gated_helper_inside_test_module() {
+    // Project loading enables `cfg(test)` for local crates, so test-only helpers are
+    // visible for analysis inside `#[cfg(test)] mod tests`.
+    check_empty(
+        r#"
+//- /main.rs cfg:test
+#[cfg(test)]
+fn test_helper() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t() { test_h$0 }
+}
+"#,
+        expect![[r#"
+            fn t()           fn()
+            fn test_helper() fn()
+            md tests
+            bt u32           u32
+            kw async
+            kw const
+            kw crate::
+            kw enum
+            kw extern
+            kw false
+            kw fn
+            kw for
+            kw if
+            kw if let
+            kw impl
+            kw let
+            kw loop
+            kw match
+            kw mod
+            kw return
+            kw self::
+            kw static
+            kw struct
+            kw super::
+            kw trait
+            kw true
+            kw type
+            kw union
+            kw unsafe
+            kw use
+            kw while
+            kw while let
+            sn macro_rules
+            sn pd
+            sn ppd
+        "#]],
+    );
+    // Without the test cfg the helper (and the whole test module) is inactive.
+    check_empty(
+        r#"
+#[cfg(test)]
+fn test_helper() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t() { test_h$0 }
+}
+"#,
+        expect![[r#"
+            bt u32         u32
+            kw async
+            kw const
+            kw crate::
+            kw enum
+            kw extern
+            kw false
+            kw fn
+            kw for
+            kw if
+            kw if let
+            kw impl
+            kw let
+            kw loop
+            kw match
+            kw mod
+            kw self::
+            kw static
+            kw struct
+            kw trait
+            kw true
+            kw type
+            kw union
+            kw unsafe
+            kw use
+            kw while
+            kw while let
+            sn macro_rules
+            sn pd
+            sn ppd
+        "#]],
+    );
+}
@@ -3,10 +3,14 @@ use expect_test::{expect, Expect};
 use crate::{
     context::{CompletionAnalysis, NameContext, NameKind, NameRefKind},
     tests::{check_edit, check_edit_with_config, TEST_CONFIG},
+    CompletionConfig, CompletionScope,
 };
 
 fn check(ra_fixture: &str, expect: Expect) {
-    let config = TEST_CONFIG;
+    check_with_config(TEST_CONFIG, ra_fixture, expect);
+}
+
+fn check_with_config(config: CompletionConfig, ra_fixture: &str, expect: Expect) {
     let (db, position) = crate::tests::position(ra_fixture);
     let (ctx, analysis) = crate::context::CompletionContext::new(&db, position, &config).unwrap();
 
@@ -1633,3 +1637,67 @@ fn main() {
         "#]],
     );
 }
+
+#[test]
+fn current_crate_scope_skips_dependency_items() {
+    let fixture = r#"
+//- /main.rs crate:main deps:dep
+mod foo {
+    pub struct FirstLocal;
+}
+fn main() {
+    Firs$0
+}
+//- /lib.rs crate:dep
+pub struct FirstStruct;
+"#;
+    check(
+        fixture,
+        expect![[r#"
+            st FirstStruct (use dep::FirstStruct) FirstStruct
+            st FirstLocal (use foo::FirstLocal) FirstLocal
+        "#]],
+    );
+    check_with_config(
+        CompletionConfig { scope: CompletionScope::Crate, ..TEST_CONFIG },
+        fixture,
+        expect![[r#"
+            st FirstLocal (use foo::FirstLocal) FirstLocal
+        "#]],
+    );
+}
+
+#[test]
+fn excluded_paths_are_skipped() {
+    let fixture = r#"
+//- /main.rs crate:main deps:dep
+fn main() {
+    Firs$0
+}
+//- /lib.rs crate:dep
+pub struct FirstStruct;
+pub mod private {
+    pub struct FirstPrivateStruct;
+}
+pub mod privateer {
+    pub struct FirstShipStruct;
+}
+"#;
+    check(
+        fixture,
+        expect![[r#"
+            st FirstStruct (use dep::FirstStruct) FirstStruct
+            st FirstPrivateStruct (use dep::private::FirstPrivateStruct) FirstPrivateStruct
+            st FirstShipStruct (use dep::privateer::FirstShipStruct) FirstShipStruct
+        "#]],
+    );
+    // The prefix match is per path segment, so `dep::privateer` is unaffected.
+    check_with_config(
+        CompletionConfig { autoimport_exclude: vec!["dep::private".to_owned()], ..TEST_CONFIG },
+        fixture,
+        expect![[r#"
+            st FirstStruct (use dep::FirstStruct) FirstStruct
+            st FirstShipStruct (use dep::privateer::FirstShipStruct) FirstShipStruct
+        "#]],
+    );
+}
@@ -168,6 +168,13 @@ impl RootDatabase {
         hir::db::BodyWithSourceMapQuery.in_db_mut(self).set_lru_capacity(2048);
     }
 
+    /// Sets the size of the macro expansion cache separately from the base
+    /// queries, so that macro-heavy workspaces can keep expansions alive
+    /// without also growing the syntax tree cache.
+    pub fn update_macro_expansion_lru_capacity(&mut self, capacity: u16) {
+        hir::db::ParseMacroExpansionQuery.in_db_mut(self).set_lru_capacity(capacity);
+    }
+
     pub fn update_lru_capacities(&mut self, lru_capacities: &FxHashMap<Box<str>, u16>) {
         use hir::db as hir_db;
 
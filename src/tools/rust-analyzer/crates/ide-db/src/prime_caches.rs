@@ -142,3 +142,11 @@ pub fn parallel_prime_caches(
         cb(progress);
     }
 }
+
+/// Primes the caches for a single crate, making its items available to e.g.
+/// import-inserting completions without indexing the whole workspace.
+pub fn prime_crate(db: &RootDatabase, crate_id: CrateId) {
+    let _p = tracing::info_span!("prime_crate").entered();
+    // This also computes the `DefMap`.
+    db.import_map(crate_id);
+}
@@ -349,6 +349,18 @@ fn rename_reference(
         cov_mark::hit!(rename_underscore_multiple);
         bail!("Cannot rename reference to `_` as it is being referenced multiple times");
     }
+    // Usages whose identifier doesn't line up with the reference range come out
+    // of a macro expansion and cannot be edited in place. Silently skipping
+    // them would leave the rename incomplete and the code broken, so refuse.
+    if usages
+        .iter()
+        .flat_map(|(_, references)| references)
+        .any(|reference| reference.name.text_range().len() != reference.range.len())
+    {
+        bail!(
+            "Cannot rename: a usage inside a macro expansion cannot be mapped back onto the name"
+        );
+    }
     let mut source_change = SourceChange::default();
     source_change.extend(usages.iter().map(|(file_id, references)| {
         (EditionedFileId::file_id(file_id), source_edit_from_references(references, def, new_name))
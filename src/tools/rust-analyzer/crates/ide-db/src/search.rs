@@ -927,6 +927,32 @@ impl ReferenceCategory {
                         }
                         Some(ReferenceCategory::READ)
                     },
+                    ast::RefExpr(expr) => {
+                        if expr.mut_token().is_some() {
+                            return Some(ReferenceCategory::WRITE);
+                        }
+                        // A shared borrow is just a read, but keep looking as the
+                        // reference might end up on the LHS of an assignment.
+                        None
+                    },
+                    ast::MethodCallExpr(expr) => {
+                        // Calling a method through a `&mut self` receiver writes to it.
+                        // The method name itself resolves to a function and was
+                        // filtered out above, but `r` might be an argument rather
+                        // than part of the receiver.
+                        let receiver = expr.receiver()?;
+                        if !receiver.syntax().text_range().contains_range(r.syntax().text_range()) {
+                            return None;
+                        }
+                        let func = sema.resolve_method_call(&expr)?;
+                        let self_param = func.self_param(sema.db)?;
+                        match self_param.access(sema.db) {
+                            hir::Access::Exclusive => Some(ReferenceCategory::WRITE),
+                            hir::Access::Shared | hir::Access::Owned => {
+                                Some(ReferenceCategory::READ)
+                            }
+                        }
+                    },
                     _ => None,
                 }
             }
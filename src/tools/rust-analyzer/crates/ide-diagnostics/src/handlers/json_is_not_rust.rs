@@ -146,6 +146,7 @@ pub(crate) fn json_in_items(
                             prefer_no_std: config.prefer_no_std,
                             prefer_prelude: config.prefer_prelude,
                             prefer_absolute: config.prefer_absolute,
+                            prefer_reexports: config.prefer_reexports,
                         };
 
                         if !scope_has("Serialize") {
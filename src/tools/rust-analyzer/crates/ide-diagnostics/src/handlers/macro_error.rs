@@ -6,14 +6,11 @@ use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
 pub(crate) fn macro_error(ctx: &DiagnosticsContext<'_>, d: &hir::MacroError) -> Diagnostic {
     // Use more accurate position if available.
     let display_range = ctx.resolve_precise_location(&d.node, d.precise_location);
-    Diagnostic::new(
-        DiagnosticCode::Ra(
-            "macro-error",
-            if d.error { Severity::Error } else { Severity::WeakWarning },
-        ),
-        d.message.clone(),
-        display_range,
-    )
+    // Errors that are not hard errors stem from proc-macros that could not be
+    // expanded, whose severity is configurable.
+    let severity =
+        if d.error { Severity::Error } else { ctx.config.proc_macro_diagnostic_severity };
+    Diagnostic::new(DiagnosticCode::Ra("macro-error", severity), d.message.clone(), display_range)
 }
 
 // Diagnostic: macro-def-error
@@ -133,6 +130,25 @@ macro_rules! concat { () => {} }
         );
     }
 
+    #[test]
+    fn recursion_limit_from_crate_root_is_applied() {
+        check_diagnostics(
+            r#"
+#![recursion_limit = "8"]
+macro_rules! rec {
+    () => {};
+    ($t:tt $($rest:tt)*) => { rec!($($rest)*) };
+}
+
+fn f() {
+    rec!(1 2 3);
+    rec!(1 2 3 4 5 6 7 8 9);
+    //                   ^ error: overflow expanding the original macro
+}
+"#,
+        );
+    }
+
     #[test]
     fn register_attr_and_tool() {
         cov_mark::check!(register_attr);
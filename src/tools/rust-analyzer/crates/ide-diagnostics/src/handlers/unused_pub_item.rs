@@ -0,0 +1,195 @@
+use hir::{HasVisibility as _, InFile, ModuleDef, Visibility};
+use ide_db::{defs::Definition, EditionedFileId, FileRange};
+use syntax::{
+    ast::{self, HasAttrs, HasName, HasVisibility as _, VisibilityKind},
+    AstNode, SyntaxNode, SyntaxNodePtr,
+};
+
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+// Diagnostic: unused-pub-item
+//
+// This diagnostic is triggered when a `pub` item is not used from anywhere in the
+// workspace. For library crates, items that are reachable through the crate's
+// public API are not reported since they exist for downstream consumers.
+//
+// As checking every `pub` item means searching all of its reverse references,
+// this diagnostic is expensive and therefore off by default; it can be enabled
+// with the `rust-analyzer.diagnostics.unusedPubItems.enable` setting.
+pub(crate) fn unused_pub_item(
+    ctx: &DiagnosticsContext<'_>,
+    acc: &mut Vec<Diagnostic>,
+    module: hir::Module,
+    file_id: EditionedFileId,
+    file: &SyntaxNode,
+) {
+    let db = ctx.sema.db;
+    // A crate without a `main` function is built as a library, so its public API
+    // is exempt: those items are meant for consumers outside the workspace.
+    let is_library = !module
+        .krate()
+        .root_module()
+        .declarations(db)
+        .into_iter()
+        .any(|def| matches!(def, ModuleDef::Function(it) if it.is_main(db)));
+
+    for node in file.descendants() {
+        let Some(item) = ast::Item::cast(node) else { continue };
+        let Some((name, def)) = item_def(ctx, &item) else { continue };
+        if item.attrs().any(|attr| {
+            matches!(
+                attr.simple_name().as_deref(),
+                Some(
+                    "no_mangle"
+                        | "export_name"
+                        | "used"
+                        | "test"
+                        | "proc_macro"
+                        | "proc_macro_attribute"
+                        | "proc_macro_derive"
+                )
+            )
+        }) {
+            // These items are referenced by the linker, the test harness, or
+            // macro expansion rather than by name resolution.
+            continue;
+        }
+        if is_library && is_public_api(ctx, def) {
+            continue;
+        }
+        if def.usages(&ctx.sema).at_least_one() {
+            continue;
+        }
+        acc.push(
+            Diagnostic::new(
+                DiagnosticCode::Ra("unused-pub-item", Severity::Warning),
+                format!("`pub` item `{name}` is never used in the workspace"),
+                FileRange { file_id: file_id.into(), range: name.syntax().text_range() },
+            )
+            .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(item.syntax())))
+            .with_unused(true),
+        );
+    }
+}
+
+fn item_def(ctx: &DiagnosticsContext<'_>, item: &ast::Item) -> Option<(ast::Name, Definition)> {
+    let sema = &ctx.sema;
+    let (vis, name, def) = match item {
+        ast::Item::Const(it) => (it.visibility(), it.name()?, sema.to_def(it)?.into()),
+        ast::Item::Enum(it) => {
+            (it.visibility(), it.name()?, hir::Adt::from(sema.to_def(it)?).into())
+        }
+        ast::Item::Fn(it) => (it.visibility(), it.name()?, sema.to_def(it)?.into()),
+        ast::Item::Module(it) => (it.visibility(), it.name()?, sema.to_def(it)?.into()),
+        ast::Item::Static(it) => (it.visibility(), it.name()?, sema.to_def(it)?.into()),
+        ast::Item::Struct(it) => {
+            (it.visibility(), it.name()?, hir::Adt::from(sema.to_def(it)?).into())
+        }
+        ast::Item::Trait(it) => (it.visibility(), it.name()?, sema.to_def(it)?.into()),
+        ast::Item::TraitAlias(it) => (it.visibility(), it.name()?, sema.to_def(it)?.into()),
+        ast::Item::TypeAlias(it) => (it.visibility(), it.name()?, sema.to_def(it)?.into()),
+        ast::Item::Union(it) => {
+            (it.visibility(), it.name()?, hir::Adt::from(sema.to_def(it)?).into())
+        }
+        _ => return None,
+    };
+    // Restricted visibilities like `pub(crate)` are already dead-code checked
+    // by rustc, so only plain `pub` items are considered here.
+    match vis?.kind() {
+        VisibilityKind::Pub => Some((name, def)),
+        _ => None,
+    }
+}
+
+/// Whether `def` is reachable from outside the crate through `pub` modules only.
+fn is_public_api(ctx: &DiagnosticsContext<'_>, def: Definition) -> bool {
+    let db = ctx.sema.db;
+    let Some(module) = def.module(db) else { return false };
+    matches!(def.visibility(db), Some(Visibility::Public))
+        && module
+            .path_to_root(db)
+            .into_iter()
+            .all(|m| m.parent(db).is_none() || matches!(m.visibility(db), Visibility::Public))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests::check_diagnostics_with_config, DiagnosticsConfig};
+
+    #[track_caller]
+    fn check(ra_fixture: &str) {
+        let mut config = DiagnosticsConfig::test_sample();
+        config.unused_pub_items = true;
+        check_diagnostics_with_config(config, ra_fixture);
+    }
+
+    #[test]
+    fn unused_pub_item_in_binary() {
+        check(
+            r#"
+//- /main.rs
+pub fn unused() {}
+     //^^^^^^ warn: `pub` item `unused` is never used in the workspace
+pub fn used() {}
+
+fn main() {
+    used();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn library_public_api_is_exempt() {
+        check(
+            r#"
+//- /lib.rs crate:foo
+pub fn api() {}
+mod private {
+    pub fn helper() {}
+         //^^^^^^ warn: `pub` item `helper` is never used in the workspace
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn usage_from_another_crate_counts() {
+        check(
+            r#"
+//- /main.rs crate:main deps:foo
+fn main() {
+    foo::private::helper();
+}
+//- /lib.rs crate:foo
+pub mod private;
+//- /private.rs
+pub fn helper() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn restricted_visibility_is_ignored() {
+        check(
+            r#"
+//- /main.rs
+pub(crate) fn unused() {}
+
+fn main() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        crate::tests::check_diagnostics(
+            r#"
+//- /main.rs
+pub fn unused() {}
+
+fn main() {}
+"#,
+        );
+    }
+}
@@ -69,6 +69,7 @@ mod handlers {
     pub(crate) mod field_shorthand;
     pub(crate) mod json_is_not_rust;
     pub(crate) mod unlinked_file;
+    pub(crate) mod unused_pub_item;
     pub(crate) mod useless_braces;
 }
 
@@ -231,16 +232,24 @@ pub struct DiagnosticsConfig {
     pub enabled: bool,
     pub proc_macros_enabled: bool,
     pub proc_attr_macros_enabled: bool,
+    /// The severity for diagnostics about proc-macros that could not be
+    /// expanded, `Severity::Allow` suppresses them entirely.
+    pub proc_macro_diagnostic_severity: Severity,
     pub disable_experimental: bool,
     pub disabled: FxHashSet<String>,
     pub expr_fill_default: ExprFillDefaultMode,
     pub style_lints: bool,
+    /// Whether to flag `pub` items without any uses in the workspace. This
+    /// searches the reverse references of every `pub` item, which is expensive,
+    /// so it is opt-in.
+    pub unused_pub_items: bool,
     // FIXME: We may want to include a whole `AssistConfig` here
     pub snippet_cap: Option<SnippetCap>,
     pub insert_use: InsertUseConfig,
     pub prefer_no_std: bool,
     pub prefer_prelude: bool,
     pub prefer_absolute: bool,
+    pub prefer_reexports: bool,
     pub term_search_fuel: u64,
     pub term_search_borrowck: bool,
 }
@@ -254,10 +263,12 @@ impl DiagnosticsConfig {
             enabled: true,
             proc_macros_enabled: Default::default(),
             proc_attr_macros_enabled: Default::default(),
+            proc_macro_diagnostic_severity: Severity::WeakWarning,
             disable_experimental: Default::default(),
             disabled: Default::default(),
             expr_fill_default: Default::default(),
             style_lints: true,
+            unused_pub_items: false,
             snippet_cap: SnippetCap::new(true),
             insert_use: InsertUseConfig {
                 granularity: ImportGranularity::Preserve,
@@ -269,6 +280,7 @@ impl DiagnosticsConfig {
             prefer_no_std: false,
             prefer_prelude: true,
             prefer_absolute: false,
+            prefer_reexports: false,
             term_search_fuel: 400,
             term_search_borrowck: true,
         }
@@ -366,6 +378,18 @@ pub fn semantic_diagnostics(
 
     let ctx = DiagnosticsContext { config, sema, resolve };
 
+    if config.unused_pub_items {
+        if let Some(module) = module {
+            handlers::unused_pub_item::unused_pub_item(
+                &ctx,
+                &mut res,
+                module,
+                file_id,
+                parse.syntax(),
+            );
+        }
+    }
+
     let mut diags = Vec::new();
     match module {
         // A bunch of parse errors in a file indicate some bigger structural parse changes in the
@@ -0,0 +1,241 @@
+//! Discovers color literals in a file, for `textDocument/documentColor`.
+//!
+//! The discovery is purely syntactic. Calls producing colors, e.g. `Color::rgb(255, 0, 0)`,
+//! are matched against user configured path patterns since color APIs vary between crates.
+//! Hex color strings like `"#ff0000"` are always recognized.
+
+use syntax::{
+    ast::{self, AstToken, HasArgList},
+    AstNode, NodeOrToken, SourceFile, SyntaxKind, TextRange, TextSize,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorInfo {
+    pub range: TextRange,
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+    pub alpha: f32,
+}
+
+pub(crate) fn document_colors(file: &SourceFile, patterns: &[String]) -> Vec<ColorInfo> {
+    let mut res = Vec::new();
+    for element in file.syntax().descendants_with_tokens() {
+        match element {
+            NodeOrToken::Node(node) => {
+                if let Some(call) = ast::CallExpr::cast(node.clone()) {
+                    res.extend(color_from_call(&call, patterns));
+                } else if let Some(mac) = ast::MacroCall::cast(node) {
+                    res.extend(color_from_macro(&mac, patterns));
+                }
+            }
+            NodeOrToken::Token(token) => {
+                if let Some(string) = ast::String::cast(token) {
+                    colors_from_string(&string, &mut res);
+                }
+            }
+        }
+    }
+    res.sort_by_key(|it| it.range.start());
+    res
+}
+
+/// A pattern matches a call path that equals it or ends with `::` followed by it, so
+/// `Color::rgb` matches both `Color::rgb(..)` and `gfx::Color::rgb(..)`.
+fn path_matches(path: &str, pattern: &str) -> bool {
+    path == pattern || path.strip_suffix(pattern).is_some_and(|rest| rest.ends_with("::"))
+}
+
+fn color_from_call(call: &ast::CallExpr, patterns: &[String]) -> Option<ColorInfo> {
+    let path = match call.expr()? {
+        ast::Expr::PathExpr(it) => it.path()?.syntax().text().to_string(),
+        _ => return None,
+    };
+    patterns
+        .iter()
+        .filter(|pattern| !pattern.ends_with('!'))
+        .any(|pattern| path_matches(&path, pattern))
+        .then_some(())?;
+
+    let components = call
+        .arg_list()?
+        .args()
+        .map(|arg| match arg {
+            ast::Expr::Literal(lit) => literal_component(&lit.kind()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    color_from_components(call.syntax().text_range(), &components)
+}
+
+fn color_from_macro(mac: &ast::MacroCall, patterns: &[String]) -> Option<ColorInfo> {
+    let path = mac.path()?.syntax().text().to_string();
+    patterns
+        .iter()
+        .filter_map(|pattern| pattern.strip_suffix('!'))
+        .any(|pattern| path_matches(&path, pattern))
+        .then_some(())?;
+
+    let components: Vec<_> = mac
+        .token_tree()?
+        .token_trees_and_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter_map(|token| match token.kind() {
+            SyntaxKind::INT_NUMBER => {
+                literal_component(&ast::LiteralKind::IntNumber(ast::IntNumber::cast(token)?))
+            }
+            SyntaxKind::FLOAT_NUMBER => {
+                literal_component(&ast::LiteralKind::FloatNumber(ast::FloatNumber::cast(token)?))
+            }
+            _ => None,
+        })
+        .collect();
+    color_from_components(mac.syntax().text_range(), &components)
+}
+
+/// Interprets a literal as a color component: integers as `0..=255`, floats as `0.0..=1.0`.
+fn literal_component(literal: &ast::LiteralKind) -> Option<f32> {
+    match literal {
+        ast::LiteralKind::IntNumber(int) => Some(int.value().ok()? as f32 / 255.0),
+        ast::LiteralKind::FloatNumber(float) => float.value_string().parse().ok(),
+        _ => None,
+    }
+}
+
+fn color_from_components(range: TextRange, components: &[f32]) -> Option<ColorInfo> {
+    let ([red, green, blue], alpha) = match *components {
+        [red, green, blue] => ([red, green, blue], 1.0),
+        [red, green, blue, alpha] => ([red, green, blue], alpha),
+        _ => return None,
+    };
+    Some(ColorInfo {
+        range,
+        red: red.clamp(0.0, 1.0),
+        green: green.clamp(0.0, 1.0),
+        blue: blue.clamp(0.0, 1.0),
+        alpha: alpha.clamp(0.0, 1.0),
+    })
+}
+
+fn colors_from_string(string: &ast::String, acc: &mut Vec<ColorInfo>) {
+    let text = string.text();
+    let token_start = string.syntax().text_range().start();
+    let mut idx = 0;
+    while let Some(pos) = text[idx..].find('#') {
+        let hash = idx + pos;
+        idx = hash + 1;
+        let digits = text[hash + 1..].bytes().take_while(u8::is_ascii_hexdigit).count();
+        let len = match digits {
+            3 | 4 | 6 | 8 => digits,
+            _ => continue,
+        };
+        let range = TextRange::at(
+            token_start + TextSize::new(hash as u32),
+            TextSize::new((1 + len) as u32),
+        );
+        acc.extend(color_from_hex(&text[hash + 1..hash + 1 + len], range));
+        idx = hash + 1 + len;
+    }
+}
+
+fn color_from_hex(hex: &str, range: TextRange) -> Option<ColorInfo> {
+    let digit = |idx: usize| {
+        u8::from_str_radix(&hex[idx..idx + 1], 16).ok().map(|it| (it * 17) as f32 / 255.0)
+    };
+    let byte =
+        |idx: usize| u8::from_str_radix(&hex[idx..idx + 2], 16).ok().map(|it| it as f32 / 255.0);
+    let (red, green, blue, alpha) = match hex.len() {
+        3 => (digit(0)?, digit(1)?, digit(2)?, 1.0),
+        4 => (digit(0)?, digit(1)?, digit(2)?, digit(3)?),
+        6 => (byte(0)?, byte(2)?, byte(4)?, 1.0),
+        8 => (byte(0)?, byte(2)?, byte(4)?, byte(6)?),
+        _ => return None,
+    };
+    Some(ColorInfo { range, red, green, blue, alpha })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::fixture;
+
+    fn check(ra_fixture: &str, patterns: &[&str], expect: Expect) {
+        let (analysis, file_id) = fixture::file(ra_fixture);
+        let patterns: Vec<String> = patterns.iter().map(ToString::to_string).collect();
+        let colors = analysis.document_colors(&patterns, file_id).unwrap();
+        expect.assert_debug_eq(&colors);
+    }
+
+    #[test]
+    fn hex_color_strings() {
+        check(
+            r##"
+fn f() {
+    let _ = "#ff0000";
+    let _ = "#0f08";
+    let _ = "not a #color";
+}
+"##,
+            &[],
+            expect![[r#"
+                [
+                    ColorInfo {
+                        range: 22..29,
+                        red: 1.0,
+                        green: 0.0,
+                        blue: 0.0,
+                        alpha: 1.0,
+                    },
+                    ColorInfo {
+                        range: 45..50,
+                        red: 0.0,
+                        green: 1.0,
+                        blue: 0.0,
+                        alpha: 0.53333336,
+                    },
+                ]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn configured_call_and_macro_patterns() {
+        check(
+            r#"
+fn f() {
+    let _ = gfx::Color::rgb(255, 0, 0);
+    let _ = Color::rgba(0.0, 1.0, 0.0, 0.5);
+    let _ = rgb!(0, 0, 255);
+    let _ = Color::hsl(0.5, 0.5, 0.5);
+}
+"#,
+            &["Color::rgb", "Color::rgba", "rgb!"],
+            expect![[r#"
+                [
+                    ColorInfo {
+                        range: 21..47,
+                        red: 1.0,
+                        green: 0.0,
+                        blue: 0.0,
+                        alpha: 1.0,
+                    },
+                    ColorInfo {
+                        range: 61..92,
+                        red: 0.0,
+                        green: 1.0,
+                        blue: 0.0,
+                        alpha: 0.5,
+                    },
+                    ColorInfo {
+                        range: 106..121,
+                        red: 0.0,
+                        green: 0.0,
+                        blue: 1.0,
+                        alpha: 1.0,
+                    },
+                ]
+            "#]],
+        );
+    }
+}
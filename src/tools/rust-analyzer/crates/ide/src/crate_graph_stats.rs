@@ -0,0 +1,55 @@
+//! Computes summary statistics about the crate graph, to help quantify project scale in
+//! performance reports.
+
+use ide_db::{
+    base_db::{SourceDatabase, SourceRootDatabase},
+    FxHashMap, RootDatabase,
+};
+
+#[derive(Debug)]
+pub struct CrateGraphStats {
+    pub crates: usize,
+    /// The number of dependency edges between crates.
+    pub edges: usize,
+    /// The number of source roots containing at least one crate root.
+    pub source_roots: usize,
+    /// The number of files in those source roots.
+    pub files: usize,
+    /// Crate names with the file count of their source root, largest first.
+    pub largest_crates: Vec<(String, usize)>,
+}
+
+const LARGEST_CRATES_CAP: usize = 10;
+
+pub(crate) fn crate_graph_stats(db: &RootDatabase) -> CrateGraphStats {
+    let crate_graph = db.crate_graph();
+    let mut crates = 0;
+    let mut edges = 0;
+    let mut root_files = FxHashMap::default();
+    let mut largest_crates = Vec::new();
+    for krate in crate_graph.iter() {
+        let data = &crate_graph[krate];
+        crates += 1;
+        edges += data.dependencies.len();
+        let source_root = db.file_source_root(data.root_file_id);
+        let files = *root_files
+            .entry(source_root)
+            .or_insert_with(|| db.source_root(source_root).iter().count());
+        let name = data
+            .display_name
+            .as_ref()
+            .map_or_else(|| "<unknown>".to_owned(), |it| it.canonical_name().as_str().to_owned());
+        largest_crates.push((name, files));
+    }
+    largest_crates.sort_by(|(name1, files1), (name2, files2)| {
+        files2.cmp(files1).then_with(|| name1.cmp(name2))
+    });
+    largest_crates.truncate(LARGEST_CRATES_CAP);
+    CrateGraphStats {
+        crates,
+        edges,
+        source_roots: root_files.len(),
+        files: root_files.values().sum(),
+        largest_crates,
+    }
+}
@@ -18,6 +18,7 @@ use ide_db::{
     base_db::{CrateOrigin, LangCrateOrigin, ReleaseChannel, SourceDatabase},
     defs::{Definition, NameClass, NameRefClass},
     documentation::{docs_with_rangemap, Documentation, HasDocs},
+    famous_defs::FamousDefs,
     helpers::pick_best_token,
     RootDatabase,
 };
@@ -169,6 +170,50 @@ pub(crate) fn external_docs(
     Some(get_doc_links(db, definition, target_dir, sysroot))
 }
 
+/// Returns the rendered markdown documentation of the definition at the given
+/// position, merging its doc comments and `#[doc]` attributes.
+///
+/// Intra-doc links are rewritten to point at their targets where they resolve,
+/// and left as plain text otherwise.
+pub(crate) fn docs_for_position(
+    db: &RootDatabase,
+    FilePosition { file_id, offset }: FilePosition,
+) -> Option<String> {
+    let sema = &Semantics::new(db);
+    let file = sema.parse_guess_edition(file_id).syntax().clone();
+    let token = pick_best_token(file.token_at_offset(offset), |kind| match kind {
+        IDENT | INT_NUMBER | T![self] => 3,
+        T!['('] | T![')'] => 2,
+        kind if kind.is_trivia() => 0,
+        _ => 1,
+    })?;
+    let token = sema.descend_into_macros_single(DescendPreference::None, token);
+
+    let node = token.parent()?;
+    let definition = match_ast! {
+        match node {
+            ast::NameRef(name_ref) => match NameRefClass::classify(sema, &name_ref)? {
+                NameRefClass::Definition(def) => def,
+                NameRefClass::FieldShorthand { local_ref: _, field_ref } => {
+                    Definition::Field(field_ref)
+                }
+                NameRefClass::ExternCrateShorthand { decl, .. } => {
+                    Definition::ExternCrateDecl(decl)
+                }
+            },
+            ast::Name(name) => match NameClass::classify(sema, &name)? {
+                NameClass::Definition(it) | NameClass::ConstReference(it) => it,
+                NameClass::PatFieldShorthand { local_def: _, field_ref } => Definition::Field(field_ref),
+            },
+            _ => return None
+        }
+    };
+
+    let famous_defs = FamousDefs(sema, sema.scope(&node)?.krate());
+    let docs = definition.docs(db, Some(&famous_defs))?;
+    Some(rewrite_links(db, docs.as_str(), definition))
+}
+
 /// Extracts all links from a given markdown text returning the definition text range, link-text
 /// and the namespace if known.
 pub(crate) fn extract_definitions_from_docs(
@@ -49,6 +49,12 @@ fn check_rewrite(ra_fixture: &str, expect: Expect) {
     expect.assert_eq(&res)
 }
 
+fn check_docs_for_position(ra_fixture: &str, expect: Expect) {
+    let (analysis, position) = fixture::position(ra_fixture);
+    let docs = analysis.docs_for_position(position).unwrap().unwrap();
+    expect.assert_eq(&docs);
+}
+
 fn check_doc_links(ra_fixture: &str) {
     let key_fn = |&(FileRange { file_id, range }, _): &_| (file_id, range.start());
 
@@ -686,3 +692,30 @@ fn rewrite_intra_doc_link_with_anchor() {
         expect!["[PartialEq#derivable](https://doc.rust-lang.org/stable/core/cmp/trait.PartialEq.html#derivable)"],
     );
 }
+
+#[test]
+fn docs_for_position_merges_comments_and_attrs() {
+    check_docs_for_position(
+        r#"
+//- minicore: eq, derive
+//- /main.rs crate:foo
+/// Doc comment referencing [PartialEq].
+#[doc = "Doc attribute line."]
+fn foo$0() {}
+"#,
+        expect![[r#"
+            Doc comment referencing [PartialEq](https://doc.rust-lang.org/stable/core/cmp/trait.PartialEq.html).
+            Doc attribute line."#]],
+    );
+}
+
+#[test]
+fn docs_for_position_keeps_unresolved_links_as_text() {
+    check_docs_for_position(
+        r#"
+/// See [does_not_exist].
+fn foo$0() {}
+"#,
+        expect!["See [does_not_exist](does_not_exist)."],
+    );
+}
@@ -0,0 +1,268 @@
+use std::iter::Peekable;
+
+use cfg::{CfgAtom, CfgExpr, CfgOptions};
+use hir::{Semantics, Symbol};
+use ide_db::{FilePosition, RootDatabase};
+use syntax::{
+    algo::ancestors_at_offset,
+    ast::{self, AstToken},
+    AstNode, NodeOrToken, SyntaxKind, SyntaxToken, T,
+};
+
+/// Result of evaluating the condition of a `#[cfg]` attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalCfgResult {
+    /// Whether the condition holds for the containing crate, or `None` if it
+    /// is syntactically invalid.
+    pub enabled: Option<bool>,
+    /// The innermost predicate that decided the result: the first failing one
+    /// of an `all(...)`, the first matching one of an `any(...)`.
+    pub decided_by: Option<String>,
+}
+
+// Feature: Evaluate Cfg
+//
+// Shows whether the `#[cfg(...)]` attribute under the cursor evaluates to true
+// or false with the cfg set of the containing crate, together with the
+// predicate that decided the result.
+pub(crate) fn eval_cfg(db: &RootDatabase, position: FilePosition) -> Option<EvalCfgResult> {
+    let sema = Semantics::new(db);
+    let source_file = sema.parse_guess_edition(position.file_id);
+
+    let attr =
+        ancestors_at_offset(source_file.syntax(), position.offset).find_map(ast::Attr::cast)?;
+    if attr.simple_name()? != "cfg" {
+        return None;
+    }
+    let cfg = parse_cfg(&attr.token_tree()?);
+
+    let krate = sema.file_to_module_def(position.file_id)?.krate();
+    let cfg_options = krate.cfg(db);
+
+    let (enabled, decided_by) = eval_and_explain(&cfg, &cfg_options);
+    Some(EvalCfgResult { enabled, decided_by: decided_by.map(|atom| atom.to_string()) })
+}
+
+/// Evaluates `cfg` and returns the atom whose value determined the result, if
+/// there is a single such atom.
+fn eval_and_explain<'e>(
+    cfg: &'e CfgExpr,
+    cfg_options: &CfgOptions,
+) -> (Option<bool>, Option<&'e CfgAtom>) {
+    match cfg {
+        CfgExpr::Invalid => (None, None),
+        CfgExpr::Atom(atom) => (Some(cfg_options.check_atom(atom)), Some(atom)),
+        CfgExpr::All(preds) => {
+            for pred in preds {
+                match eval_and_explain(pred, cfg_options) {
+                    (None, _) => return (None, None),
+                    (Some(false), decided_by) => return (Some(false), decided_by),
+                    (Some(true), _) => {}
+                }
+            }
+            (Some(true), None)
+        }
+        CfgExpr::Any(preds) => {
+            for pred in preds {
+                match eval_and_explain(pred, cfg_options) {
+                    (None, _) => return (None, None),
+                    (Some(true), decided_by) => return (Some(true), decided_by),
+                    (Some(false), _) => {}
+                }
+            }
+            (Some(false), None)
+        }
+        CfgExpr::Not(pred) => {
+            let (enabled, decided_by) = eval_and_explain(pred, cfg_options);
+            (enabled.map(|it| !it), decided_by)
+        }
+    }
+}
+
+/// Parses the token tree of a `#[cfg(...)]` attribute into a [`CfgExpr`].
+///
+/// This mirrors [`CfgExpr::parse`], which is not usable here as it operates on
+/// the `tt` produced during attribute lowering rather than the syntax tree.
+fn parse_cfg(tt: &ast::TokenTree) -> CfgExpr {
+    next_cfg_expr(&mut tt.token_trees_and_tokens().peekable()).unwrap_or(CfgExpr::Invalid)
+}
+
+fn next_cfg_expr(
+    it: &mut Peekable<impl Iterator<Item = NodeOrToken<ast::TokenTree, SyntaxToken>>>,
+) -> Option<CfgExpr> {
+    let name = loop {
+        match it.next()? {
+            NodeOrToken::Token(token) if skip(&token) => {}
+            NodeOrToken::Token(token) if token.kind() == SyntaxKind::IDENT => break token,
+            _ => return Some(CfgExpr::Invalid),
+        }
+    };
+
+    while matches!(it.peek(), Some(NodeOrToken::Token(token)) if token.kind().is_trivia()) {
+        it.next();
+    }
+    let ret = match it.peek() {
+        Some(NodeOrToken::Token(token)) if token.kind() == T![=] => {
+            it.next();
+            while matches!(it.peek(), Some(NodeOrToken::Token(token)) if token.kind().is_trivia())
+            {
+                it.next();
+            }
+            match it.next() {
+                Some(NodeOrToken::Token(token)) => {
+                    match ast::String::cast(token).map(|string| string.value().map(|it| Symbol::intern(&it)))
+                    {
+                        Some(Ok(value)) => {
+                            CfgAtom::KeyValue { key: Symbol::intern(name.text()), value }.into()
+                        }
+                        _ => CfgExpr::Invalid,
+                    }
+                }
+                _ => CfgExpr::Invalid,
+            }
+        }
+        Some(NodeOrToken::Node(_)) => {
+            let Some(NodeOrToken::Node(subtree)) = it.next() else { return None };
+            let mut sub_it = subtree.token_trees_and_tokens().peekable();
+            let mut subs = std::iter::from_fn(|| next_cfg_expr(&mut sub_it));
+            match name.text() {
+                "all" => CfgExpr::All(subs.collect()),
+                "any" => CfgExpr::Any(subs.collect()),
+                "not" => CfgExpr::Not(Box::new(subs.next().unwrap_or(CfgExpr::Invalid))),
+                _ => CfgExpr::Invalid,
+            }
+        }
+        _ => CfgAtom::Flag(Symbol::intern(name.text())).into(),
+    };
+
+    // Eat the comma separator.
+    while matches!(it.peek(), Some(NodeOrToken::Token(token)) if token.kind().is_trivia()) {
+        it.next();
+    }
+    if matches!(it.peek(), Some(NodeOrToken::Token(token)) if token.kind() == T![,]) {
+        it.next();
+    }
+    Some(ret)
+}
+
+fn skip(token: &SyntaxToken) -> bool {
+    token.kind().is_trivia() || matches!(token.kind(), T!['('] | T![')'])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture;
+
+    #[track_caller]
+    fn check(ra_fixture: &str, enabled: Option<bool>, decided_by: Option<&str>) {
+        let (analysis, position) = fixture::position(ra_fixture);
+        let result = analysis.eval_cfg(position).unwrap().expect("no cfg attribute at position");
+        assert_eq!(result.enabled, enabled);
+        assert_eq!(result.decided_by.as_deref(), decided_by);
+    }
+
+    #[test]
+    fn flag() {
+        check(
+            r#"
+//- /lib.rs cfg:test
+#[cfg($0test)]
+fn f() {}
+"#,
+            Some(true),
+            Some("test"),
+        );
+        check(
+            r#"
+//- /lib.rs cfg:test
+#[cfg($0miri)]
+fn f() {}
+"#,
+            Some(false),
+            Some("miri"),
+        );
+    }
+
+    #[test]
+    fn key_value() {
+        check(
+            r#"
+//- /lib.rs cfg:feature=foo
+#[cfg(feature = $0"foo")]
+fn f() {}
+"#,
+            Some(true),
+            Some(r#"feature = "foo""#),
+        );
+        check(
+            r#"
+//- /lib.rs cfg:feature=foo
+#[cfg(feature = $0"bar")]
+fn f() {}
+"#,
+            Some(false),
+            Some(r#"feature = "bar""#),
+        );
+    }
+
+    #[test]
+    fn all_reports_first_failing_predicate() {
+        check(
+            r#"
+//- /lib.rs cfg:test,feature=foo
+#[cfg(all($0test, feature = "bar", miri))]
+fn f() {}
+"#,
+            Some(false),
+            Some(r#"feature = "bar""#),
+        );
+        check(
+            r#"
+//- /lib.rs cfg:test,feature=foo
+#[cfg($0all(test, feature = "foo"))]
+fn f() {}
+"#,
+            Some(true),
+            None,
+        );
+    }
+
+    #[test]
+    fn any_reports_first_matching_predicate() {
+        check(
+            r#"
+//- /lib.rs cfg:feature=foo
+#[cfg(any(miri, $0feature = "foo", test))]
+fn f() {}
+"#,
+            Some(true),
+            Some(r#"feature = "foo""#),
+        );
+    }
+
+    #[test]
+    fn not() {
+        check(
+            r#"
+//- /lib.rs cfg:test
+#[cfg(not(any(miri, $0test)))]
+fn f() {}
+"#,
+            Some(false),
+            Some("test"),
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        check(
+            r#"
+//- /lib.rs cfg:test
+#[cfg(all(test, $0not()))]
+fn f() {}
+"#,
+            None,
+            None,
+        );
+    }
+}
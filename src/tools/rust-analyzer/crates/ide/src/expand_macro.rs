@@ -3,7 +3,7 @@ use ide_db::{
     helpers::pick_best_token, syntax_helpers::insert_whitespace_into_node::insert_ws_into, FileId,
     RootDatabase,
 };
-use syntax::{ast, ted, AstNode, NodeOrToken, SyntaxKind, SyntaxNode, T};
+use syntax::{ast, ted, AstNode, NodeOrToken, SyntaxKind, SyntaxNode, TextRange, T};
 
 use crate::FilePosition;
 
@@ -12,6 +12,13 @@ pub struct ExpandedMacro {
     pub expansion: String,
 }
 
+pub struct ExpandedMacroStep {
+    pub name: String,
+    pub expansion: String,
+    /// Ranges inside `expansion` covering macro calls that can be expanded further.
+    pub expandable_ranges: Vec<TextRange>,
+}
+
 // Feature: Expand Macro Recursively
 //
 // Shows the full macro expansion of the macro at the current caret position.
@@ -106,6 +113,54 @@ pub(crate) fn expand_macro(db: &RootDatabase, position: FilePosition) -> Option<
     Some(ExpandedMacro { name, expansion })
 }
 
+// Feature: Expand Macro One Level
+//
+// Shows a single level of the macro expansion of the macro at the current caret position,
+// marking the macro calls inside it that can be expanded further.
+pub(crate) fn expand_macro_step(
+    db: &RootDatabase,
+    position: FilePosition,
+) -> Option<ExpandedMacroStep> {
+    let sema = Semantics::new(db);
+    let file = sema.parse_guess_edition(position.file_id);
+
+    let tok = pick_best_token(file.syntax().token_at_offset(position.offset), |kind| match kind {
+        SyntaxKind::IDENT => 1,
+        _ => 0,
+    })?;
+
+    let mut anc = tok.parent_ancestors();
+    let (name, expanded) = loop {
+        let node = anc.next()?;
+
+        if let Some(item) = ast::Item::cast(node.clone()) {
+            if let Some(def) = sema.resolve_attr_macro_call(&item) {
+                break (
+                    def.name(db).display(db).to_string(),
+                    sema.expand_attr_macro(&item)?.clone_for_update(),
+                );
+            }
+        }
+        if let Some(mac) = ast::MacroCall::cast(node) {
+            let mut name = mac.path()?.segment()?.name_ref()?.to_string();
+            name.push('!');
+            break (name, sema.expand_allowed_builtins(&mac)?.clone_for_update());
+        }
+    };
+
+    // Skip `rustfmt` here so that the ranges of nested macro calls stay valid for the
+    // text we hand out.
+    let expanded = insert_ws_into(expanded);
+    let expansion = expanded.to_string();
+    let expandable_ranges = expanded
+        .descendants()
+        .filter_map(ast::MacroCall::cast)
+        .map(|it| it.syntax().text_range())
+        .collect();
+
+    Some(ExpandedMacroStep { name, expansion, expandable_ranges })
+}
+
 fn expand_macro_recur(
     sema: &Semantics<'_, RootDatabase>,
     macro_call: &ast::Item,
@@ -229,6 +284,29 @@ mod tests {
         expect.assert_eq(&actual);
     }
 
+    #[track_caller]
+    fn check_step(ra_fixture: &str, expect: Expect) {
+        let (analysis, pos) = fixture::position(ra_fixture);
+        let step = analysis.expand_macro_step(pos).unwrap().unwrap();
+        let actual = format!("{}\n{}\n{:?}", step.name, step.expansion, step.expandable_ranges);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn expand_macro_one_level_marks_nested_calls() {
+        check_step(
+            r#"
+macro_rules! inner { () => { 0 } }
+macro_rules! outer { () => { inner!() } }
+fn f() { let _ = out$0er!(); }
+"#,
+            expect![[r#"
+                outer!
+                inner!()
+                [0..8]"#]],
+        );
+    }
+
     #[test]
     fn expand_allowed_builtin_macro() {
         check(
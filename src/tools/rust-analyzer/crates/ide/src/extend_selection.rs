@@ -509,6 +509,18 @@ fn bar(){}
         );
     }
 
+    #[test]
+    fn test_extend_selection_raw_string() {
+        do_check(
+            r#"
+fn foo() {
+    let x = r"raw lit$0eral contents";
+}
+"#,
+            &["literal", "r\"raw literal contents\"", "let x = r\"raw literal contents\";"],
+        );
+    }
+
     #[test]
     fn test_extend_trait_bounds_list_in_where_clause() {
         do_check(
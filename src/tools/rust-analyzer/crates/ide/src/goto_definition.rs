@@ -640,6 +640,39 @@ mod $0foo;
         );
     }
 
+    #[test]
+    fn goto_def_for_module_declaration_with_path_attr() {
+        check(
+            r#"
+//- /lib.rs
+#[path = "custom/a.rs"]
+mod $0foo;
+
+//- /custom/a.rs
+// empty
+//^file
+"#,
+        );
+
+        // `#[path]` attributes nest: the inner one is resolved relative to the
+        // directory of the file the outer one points at.
+        check(
+            r#"
+//- /lib.rs
+#[path = "custom/a.rs"]
+mod foo;
+
+//- /custom/a.rs
+#[path = "b/c.rs"]
+mod $0bar;
+
+//- /custom/b/c.rs
+// empty
+//^file
+"#,
+        );
+    }
+
     #[test]
     fn goto_def_for_macros() {
         check(
@@ -1752,6 +1785,76 @@ fn main() {
         );
     }
 
+    #[test]
+    fn goto_include_bytes() {
+        check(
+            r#"
+//- /main.rs
+
+#[rustc_builtin_macro]
+macro_rules! include_bytes {}
+
+fn main() {
+    let bytes = include_bytes!("foo.bin$0");
+}
+//- /foo.bin
+// empty
+//^file
+"#,
+        );
+    }
+
+    #[test]
+    fn goto_include_rs() {
+        check(
+            r#"
+//- minicore: include
+//- /main.rs
+
+include!("foo.rs$0");
+
+//- /foo.rs
+// empty
+//^file
+"#,
+        );
+    }
+
+    #[test]
+    fn goto_include_subdirectory_path() {
+        check(
+            r#"
+//- /main.rs
+
+#[rustc_builtin_macro]
+macro_rules! include_str {}
+
+fn main() {
+    let str = include_str!("data/foo.txt$0");
+}
+//- /data/foo.txt
+// empty
+//^file
+"#,
+        );
+    }
+
+    #[test]
+    fn goto_include_missing_file() {
+        check_unresolved(
+            r#"
+//- /main.rs
+
+#[rustc_builtin_macro]
+macro_rules! include_str {}
+
+fn main() {
+    let str = include_str!("nonexistent.txt$0");
+}
+"#,
+        );
+    }
+
     #[test]
     fn goto_include_has_eager_input() {
         check(
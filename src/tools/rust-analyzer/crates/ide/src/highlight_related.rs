@@ -783,6 +783,38 @@ fn foo() {
         );
     }
 
+    #[test]
+    fn test_hl_local_compound_assignment() {
+        check(
+            r#"
+fn foo() {
+    let mut bar$0 = 3;
+         // ^^^ write
+    bar += 1;
+ // ^^^ write
+    bar;
+ // ^^^ read
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_hl_local_mut_borrow() {
+        check(
+            r#"
+fn foo() {
+    let mut bar$0 = 3;
+         // ^^^ write
+    &mut bar;
+      // ^^^ write
+    &bar;
+  // ^^^ read
+}
+"#,
+        );
+    }
+
     #[test]
     fn test_hl_local_in_attr() {
         check(
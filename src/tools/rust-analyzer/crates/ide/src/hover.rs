@@ -34,6 +34,49 @@ pub struct HoverConfig {
     pub max_trait_assoc_items_count: Option<usize>,
     pub max_fields_count: Option<usize>,
     pub max_enum_variants_count: Option<usize>,
+    pub actions: HoverActionsConfig,
+}
+
+/// Which [`HoverAction`]s get assembled into hover results at all. Disabled
+/// kinds are skipped here rather than filtered out by the client, so no work
+/// is spent computing them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HoverActionsConfig {
+    pub implementations: bool,
+    pub references: bool,
+    pub run: bool,
+    pub debug: bool,
+    pub goto_type_def: bool,
+}
+
+impl HoverActionsConfig {
+    pub const NO_ACTIONS: Self = Self {
+        implementations: false,
+        references: false,
+        run: false,
+        debug: false,
+        goto_type_def: false,
+    };
+
+    pub const ALL_ACTIONS: Self = Self {
+        implementations: true,
+        references: true,
+        run: true,
+        debug: true,
+        goto_type_def: true,
+    };
+
+    pub fn any(&self) -> bool {
+        self.implementations || self.references || self.runnable() || self.goto_type_def
+    }
+
+    pub fn none(&self) -> bool {
+        !self.any()
+    }
+
+    pub fn runnable(&self) -> bool {
+        self.run || self.debug
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -66,7 +109,14 @@ pub enum HoverAction {
 }
 
 impl HoverAction {
-    fn goto_type_from_targets(db: &RootDatabase, targets: Vec<hir::ModuleDef>) -> Option<Self> {
+    fn goto_type_from_targets(
+        db: &RootDatabase,
+        targets: Vec<hir::ModuleDef>,
+        config: &HoverConfig,
+    ) -> Option<Self> {
+        if !config.actions.goto_type_def {
+            return None;
+        }
         let targets = targets
             .into_iter()
             .filter_map(|it| {
@@ -374,13 +424,14 @@ pub(crate) fn hover_for_definition(
     HoverResult {
         markup: render::process_markup(sema.db, def, &markup, config),
         actions: [
-            show_fn_references_action(sema.db, def),
-            show_implementations_action(sema.db, def),
-            runnable_action(sema, def, file_id),
-            goto_type_action_for_def(sema.db, def, &notable_traits),
+            config.actions.references.then(|| show_fn_references_action(sema.db, def)),
+            config.actions.implementations.then(|| show_implementations_action(sema.db, def)),
+            config.actions.runnable().then(|| runnable_action(sema, def, file_id)),
+            Some(goto_type_action_for_def(sema.db, def, &notable_traits, config)),
         ]
         .into_iter()
         .flatten()
+        .flatten()
         .collect(),
     }
 }
@@ -469,6 +520,7 @@ fn goto_type_action_for_def(
     db: &RootDatabase,
     def: Definition,
     notable_traits: &[(hir::Trait, Vec<(Option<hir::Type>, hir::Name)>)],
+    config: &HoverConfig,
 ) -> Option<HoverAction> {
     let mut targets: Vec<hir::ModuleDef> = Vec::new();
     let mut push_new_def = |item: hir::ModuleDef| {
@@ -499,13 +551,13 @@ fn goto_type_action_for_def(
             Definition::GenericParam(hir::GenericParam::ConstParam(it)) => it.ty(db),
             Definition::Field(field) => field.ty(db),
             Definition::Function(function) => function.ret_type(db),
-            _ => return HoverAction::goto_type_from_targets(db, targets),
+            _ => return HoverAction::goto_type_from_targets(db, targets, config),
         };
 
         walk_and_push_ty(db, &ty, &mut push_new_def);
     }
 
-    HoverAction::goto_type_from_targets(db, targets)
+    HoverAction::goto_type_from_targets(db, targets, config)
 }
 
 fn walk_and_push_ty(
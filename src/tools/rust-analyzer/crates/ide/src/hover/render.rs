@@ -53,7 +53,7 @@ pub(super) fn closure_expr(
 
 pub(super) fn try_expr(
     sema: &Semantics<'_, RootDatabase>,
-    _config: &HoverConfig,
+    config: &HoverConfig,
     try_expr: &ast::TryExpr,
 ) -> Option<HoverResult> {
     let inner_ty = sema.type_of_expr(&try_expr.expr()?)?.original;
@@ -117,7 +117,7 @@ pub(super) fn try_expr(
     };
     walk_and_push_ty(sema.db, &inner_ty, &mut push_new_def);
     walk_and_push_ty(sema.db, &body_ty, &mut push_new_def);
-    if let Some(actions) = HoverAction::goto_type_from_targets(sema.db, targets) {
+    if let Some(actions) = HoverAction::goto_type_from_targets(sema.db, targets, config) {
         res.actions.push(actions);
     }
 
@@ -144,7 +144,7 @@ pub(super) fn try_expr(
 
 pub(super) fn deref_expr(
     sema: &Semantics<'_, RootDatabase>,
-    _config: &HoverConfig,
+    config: &HoverConfig,
     deref_expr: &ast::PrefixExpr,
 ) -> Option<HoverResult> {
     let inner_ty = sema.type_of_expr(&deref_expr.expr()?)?.original;
@@ -197,7 +197,7 @@ pub(super) fn deref_expr(
         )
         .into()
     };
-    if let Some(actions) = HoverAction::goto_type_from_targets(sema.db, targets) {
+    if let Some(actions) = HoverAction::goto_type_from_targets(sema.db, targets, config) {
         res.actions.push(actions);
     }
 
@@ -257,17 +257,70 @@ pub(super) fn keyword(
     let parent = token.parent()?;
     let famous_defs = FamousDefs(sema, sema.scope(&parent)?.krate());
 
-    let KeywordHint { description, keyword_mod, actions } = keyword_hints(sema, token, parent);
+    let KeywordHint { description, keyword_mod, actions } =
+        keyword_hints(sema, token, parent, config);
 
-    let doc_owner = find_std_module(&famous_defs, &keyword_mod)?;
-    let docs = doc_owner.docs(sema.db)?;
-    let markup = process_markup(
-        sema.db,
-        Definition::Module(doc_owner),
-        &markup(Some(docs.into()), description, None),
-        config,
-    );
-    Some(HoverResult { markup, actions })
+    if let Some(doc_owner) = find_std_module(&famous_defs, &keyword_mod) {
+        if let Some(docs) = doc_owner.docs(sema.db) {
+            let markup = process_markup(
+                sema.db,
+                Definition::Module(doc_owner),
+                &markup(Some(docs.into()), description, None),
+                config,
+            );
+            return Some(HoverResult { markup, actions });
+        }
+    }
+
+    // Fall back to a short built-in description when the `#[doc(keyword)]` modules of the
+    // standard library are not available, e.g. for sysroots without source.
+    let docs = builtin_keyword_doc(token.text())?;
+    Some(HoverResult { markup: markup(Some(docs.to_owned()), description, None), actions })
+}
+
+/// One-line summaries of the keyword docs in the standard library, for use when those are
+/// not loaded.
+fn builtin_keyword_doc(keyword: &str) -> Option<&'static str> {
+    let doc = match keyword {
+        "as" => "Cast between types, or rename an import.",
+        "async" => "Returns a `Future` instead of blocking the current thread.",
+        "await" => "Suspend execution until the result of a `Future` is ready.",
+        "break" => "Exit early from a loop or labelled block.",
+        "const" => "Compile-time constants, compile-time evaluable functions, and raw pointers.",
+        "continue" => "Skip to the next iteration of a loop.",
+        "crate" => "A Rust binary or library.",
+        "dyn" => "`dyn` is a prefix of a trait object's type.",
+        "else" => "What expression to evaluate when an `if` condition evaluates to `false`.",
+        "enum" => "A type that can be any one of several variants.",
+        "extern" => "Link to or import external code.",
+        "fn" => "A function or function pointer.",
+        "for" => "Iteration with `in`, trait implementation with `impl`, or higher-ranked trait bounds (`for<'a>`).",
+        "if" => "Evaluate a block if a condition holds.",
+        "impl" => "Implementations of functionality for a type, or a type implementing some functionality.",
+        "in" => "Iterate over a series of values with `for`.",
+        "let" => "Bind a value to a variable.",
+        "loop" => "Loop indefinitely.",
+        "match" => "Control flow based on pattern matching.",
+        "mod" => "Organize code into modules.",
+        "move" => "Capture a closure's environment by value.",
+        "mut" => "A mutable variable, reference, or pointer.",
+        "pub" => "Make an item visible to others.",
+        "ref" => "Bind by reference during pattern matching.",
+        "return" => "Returns a value from a function.",
+        "self" => "The receiver of a method, or the current module.",
+        "static" => "A static item is a value which is valid for the entire duration of your program (a `'static` lifetime).",
+        "struct" => "A type that is composed of other types.",
+        "super" => "The parent of the current module.",
+        "trait" => "A common interface for a group of types.",
+        "type" => "Define an alias for an existing type.",
+        "union" => "The Rust equivalent of a C-style union.",
+        "unsafe" => "Code or interfaces whose memory safety cannot be verified by the type system.",
+        "use" => "Import or rename items from other crates or modules.",
+        "where" => "Add constraints that must be upheld to use an item.",
+        "while" => "Loop while a condition is upheld.",
+        _ => return None,
+    };
+    Some(doc)
 }
 
 /// Returns missing types in a record pattern.
@@ -275,7 +328,7 @@ pub(super) fn keyword(
 /// i.e. `let S {a, ..} = S {a: 1, b: 2}`
 pub(super) fn struct_rest_pat(
     sema: &Semantics<'_, RootDatabase>,
-    _config: &HoverConfig,
+    config: &HoverConfig,
     pattern: &ast::RecordPat,
 ) -> HoverResult {
     let missing_fields = sema.record_pattern_missing_fields(pattern);
@@ -306,7 +359,7 @@ pub(super) fn struct_rest_pat(
 
         Markup::fenced_block(&s)
     };
-    if let Some(actions) = HoverAction::goto_type_from_targets(sema.db, targets) {
+    if let Some(actions) = HoverAction::goto_type_from_targets(sema.db, targets, config) {
         res.actions.push(actions);
     }
     res
@@ -697,7 +750,7 @@ fn type_info(
         format_to!(desc, "{}", original.display(db));
         Markup::fenced_block(&desc)
     };
-    if let Some(actions) = HoverAction::goto_type_from_targets(db, targets) {
+    if let Some(actions) = HoverAction::goto_type_from_targets(db, targets, config) {
         res.actions.push(actions);
     }
     Some(res)
@@ -762,7 +815,7 @@ fn closure_ty(
     );
 
     let mut res = HoverResult::default();
-    if let Some(actions) = HoverAction::goto_type_from_targets(sema.db, targets) {
+    if let Some(actions) = HoverAction::goto_type_from_targets(sema.db, targets, config) {
         res.actions.push(actions);
     }
     res.markup = markup.into();
@@ -888,6 +941,7 @@ fn keyword_hints(
     sema: &Semantics<'_, RootDatabase>,
     token: &SyntaxToken,
     parent: syntax::SyntaxNode,
+    config: &HoverConfig,
 ) -> KeywordHint {
     match token.kind() {
         T![await] | T![loop] | T![match] | T![unsafe] | T![as] | T![try] | T![if] | T![else] => {
@@ -910,7 +964,7 @@ fn keyword_hints(
                     KeywordHint {
                         description,
                         keyword_mod,
-                        actions: HoverAction::goto_type_from_targets(sema.db, targets)
+                        actions: HoverAction::goto_type_from_targets(sema.db, targets, config)
                             .into_iter()
                             .collect(),
                     }
@@ -3,7 +3,8 @@ use ide_db::{base_db::SourceDatabase, FileRange};
 use syntax::TextRange;
 
 use crate::{
-    fixture, HoverConfig, HoverDocFormat, MemoryLayoutHoverConfig, MemoryLayoutHoverRenderKind,
+    fixture, HoverActionsConfig, HoverConfig, HoverDocFormat, MemoryLayoutHoverConfig,
+    MemoryLayoutHoverRenderKind,
 };
 
 const HOVER_BASE_CONFIG: HoverConfig = HoverConfig {
@@ -20,6 +21,7 @@ const HOVER_BASE_CONFIG: HoverConfig = HoverConfig {
     max_trait_assoc_items_count: None,
     max_fields_count: Some(5),
     max_enum_variants_count: Some(5),
+    actions: HoverActionsConfig::ALL_ACTIONS,
 };
 
 fn check_hover_no_result(ra_fixture: &str) {
@@ -5842,6 +5844,25 @@ mod return_keyword {}
     );
 }
 
+#[test]
+fn hover_keyword_falls_back_to_builtin_docs() {
+    check(
+        r#"
+//- /main.rs crate:main
+fn f() { retur$0n; }
+"#,
+        expect![[r#"
+            *return*
+            ```rust
+            return
+            ```
+            ___
+
+            Returns a value from a function.
+        "#]],
+    );
+}
+
 #[test]
 fn hover_keyword_doc() {
     check(
@@ -21,11 +21,14 @@ pub(super) fn hints(
         return None;
     }
 
-    let ret_type = closure.ret_type().map(|rt| (rt.thin_arrow_token(), rt.ty().is_some()));
-    let arrow = match ret_type {
-        Some((_, true)) => return None,
-        Some((arrow, _)) => arrow,
-        None => None,
+    let ret_type = closure.ret_type().map(|rt| (rt.thin_arrow_token(), rt.ty()));
+    let (arrow, infer_ty) = match ret_type {
+        // A spelled-out return type needs no hint, but `-> _` explicitly asks
+        // for inference, so the inferred type is rendered after the `_`.
+        Some((arrow, Some(ast::Type::InferType(it)))) => (arrow, Some(it)),
+        Some((_, Some(_))) => return None,
+        Some((arrow, None)) => (arrow, None),
+        None => (None, None),
     };
 
     let has_block_body = closure_has_block_body(&closure);
@@ -45,11 +48,14 @@ pub(super) fn hints(
 
     let mut label = label_of_ty(famous_defs, config, &ty)?;
 
-    if arrow.is_none() {
+    if infer_ty.is_some() {
+        label.prepend_str(": ");
+    } else if arrow.is_none() {
         label.prepend_str(" -> ");
     }
     // FIXME?: We could provide text edit to insert braces for closures with non-block body.
-    let text_edit = if has_block_body {
+    // FIXME?: A hint for `-> _` could provide a text edit replacing the `_`.
+    let text_edit = if has_block_body && infer_ty.is_none() {
         ty_to_text_edit(
             sema,
             closure.syntax(),
@@ -65,7 +71,10 @@ pub(super) fn hints(
     };
 
     acc.push(InlayHint {
-        range: param_list.syntax().text_range(),
+        range: match &infer_ty {
+            Some(it) => it.syntax().text_range(),
+            None => param_list.syntax().text_range(),
+        },
         kind: InlayKind::Type,
         label,
         text_edit,
@@ -95,6 +104,22 @@ fn main() {
           //^^ -> i32
     let b = || 0;
           //^^ -> i32
+}"#,
+        );
+    }
+
+    #[test]
+    fn return_type_hints_for_explicit_infer_type() {
+        check_with_config(
+            InlayHintsConfig {
+                closure_return_type_hints: ClosureReturnTypeHints::WithBlock,
+                ..DISABLED_CONFIG
+            },
+            r#"
+fn main() {
+    let a = |x: i32| -> _ { x + 1 };
+                      //^ : i32
+    let b = || -> u32 { 0 };
 }"#,
         );
     }
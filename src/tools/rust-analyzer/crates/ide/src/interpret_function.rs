@@ -1,4 +1,4 @@
-use hir::Semantics;
+use hir::{ConstEvalError, Semantics};
 use ide_db::{base_db::SourceRootDatabase, FilePosition, LineIndexDatabase, RootDatabase};
 use std::{fmt::Write, time::Instant};
 use syntax::{algo::ancestors_at_offset, ast, AstNode, TextRange};
@@ -28,10 +28,6 @@ fn find_and_interpret(db: &RootDatabase, position: FilePosition) -> Option<Strin
     let item = ancestors_at_offset(source_file.syntax(), position.offset)
         .filter(|it| !ast::MacroCall::can_cast(it.kind()))
         .find_map(ast::Item::cast)?;
-    let def = match item {
-        ast::Item::Fn(it) => sema.to_def(&it)?,
-        _ => return None,
-    };
     let span_formatter = |file_id, text_range: TextRange| {
         let path = &db
             .source_root(db.file_source_root(file_id))
@@ -43,5 +39,33 @@ fn find_and_interpret(db: &RootDatabase, position: FilePosition) -> Option<Strin
             None => format!("file://{path} range {text_range:?}"),
         }
     };
-    Some(def.eval(db, span_formatter))
+    match item {
+        ast::Item::Fn(it) => {
+            let def = sema.to_def(&it)?;
+            Some(def.eval(db, span_formatter))
+        }
+        // A position inside a `const` evaluates the constant instead, showing
+        // its value or an explanation of why const-eval failed. The
+        // interpreter's execution and memory limits guard against runaway
+        // const expressions.
+        ast::Item::Const(it) => {
+            let def = sema.to_def(&it)?;
+            Some(match def.render_eval(db) {
+                Ok(it) => it,
+                Err(err) => {
+                    let mut r = String::new();
+                    _ = match err {
+                        ConstEvalError::MirLowerError(e) => {
+                            e.pretty_print(&mut r, db, span_formatter)
+                        }
+                        ConstEvalError::MirEvalError(e) => {
+                            e.pretty_print(&mut r, db, span_formatter)
+                        }
+                    };
+                    r
+                }
+            })
+        }
+        _ => None,
+    }
 }
@@ -0,0 +1,251 @@
+//! Computes the qualified path of the item referenced at a position, for
+//! copying fully-qualified references or generating cross-references in docs
+//! tooling.
+
+use hir::{
+    AsAssocItem, AssocItem, AssocItemContainer, DescendPreference, ImportPathConfig, ItemInNs,
+    ModuleDef, PrefixKind, Semantics,
+};
+use ide_db::{
+    defs::{Definition, NameClass, NameRefClass},
+    helpers::pick_best_token,
+    FilePosition, RootDatabase,
+};
+use itertools::Itertools;
+use syntax::{ast, match_ast, AstNode, SyntaxKind::*, T};
+
+/// The qualified path of an item, in two flavors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemPath {
+    /// The path through the item's defining modules, e.g. `crate::foo::Bar::baz`.
+    /// Items from other crates are prefixed with the crate name instead of
+    /// `crate`.
+    pub canonical: String,
+    /// The shortest path under which the item (or, for associated items, its
+    /// container) can be imported from the position's module, taking visibility
+    /// and re-exports into account.
+    pub importable: Option<String>,
+}
+
+pub(crate) fn item_path(db: &RootDatabase, position: FilePosition) -> Option<ItemPath> {
+    let sema = Semantics::new(db);
+    let file = sema.parse_guess_edition(position.file_id).syntax().clone();
+    let token = pick_best_token(file.token_at_offset(position.offset), |kind| match kind {
+        IDENT | T![self] => 3,
+        kind if kind.is_trivia() => 0,
+        _ => 1,
+    })?;
+    let from_module = sema.scope(&token.parent()?)?.module();
+    let token = sema.descend_into_macros_single(DescendPreference::None, token);
+
+    let node = token.parent()?;
+    let definition = match_ast! {
+        match node {
+            ast::NameRef(name_ref) => match NameRefClass::classify(&sema, &name_ref)? {
+                NameRefClass::Definition(def) => def,
+                NameRefClass::FieldShorthand { local_ref: _, field_ref } => {
+                    Definition::Field(field_ref)
+                }
+                NameRefClass::ExternCrateShorthand { decl, .. } => {
+                    Definition::ExternCrateDecl(decl)
+                }
+            },
+            ast::Name(name) => match NameClass::classify(&sema, &name)? {
+                NameClass::Definition(it) | NameClass::ConstReference(it) => it,
+                NameClass::PatFieldShorthand { local_def: _, field_ref } => Definition::Field(field_ref),
+            },
+            _ => return None
+        }
+    };
+
+    let canonical = canonical_path(db, definition, from_module)?;
+    let importable = import_target(db, definition).and_then(|item| {
+        let path = from_module.find_use_path(
+            db,
+            item,
+            PrefixKind::Plain,
+            ImportPathConfig {
+                prefer_no_std: false,
+                prefer_prelude: true,
+                prefer_absolute: false,
+                prefer_reexports: false,
+            },
+        )?;
+        let path = path.display(db).to_string();
+        Some(path)
+    });
+    Some(ItemPath { canonical, importable })
+}
+
+/// Renders the path through the item's defining modules, with the container
+/// type or trait spliced in for associated items.
+fn canonical_path(db: &RootDatabase, def: Definition, from_module: hir::Module) -> Option<String> {
+    let krate = def.module(db).map(|it| it.krate()).or_else(|| match def {
+        Definition::Module(it) => Some(it.krate()),
+        _ => None,
+    })?;
+    let prefix = if krate == from_module.krate() {
+        "crate".to_owned()
+    } else {
+        krate.display_name(db)?.canonical_name().as_str().to_owned()
+    };
+
+    let mut segments = Vec::new();
+    match def {
+        Definition::Module(it) => {
+            segments.extend(it.path_to_root(db).into_iter().filter_map(|m| m.name(db)));
+            segments.reverse();
+        }
+        _ => {
+            let name = def.name(db)?;
+            match as_assoc_item(db, def).map(|it| it.container(db)) {
+                Some(AssocItemContainer::Trait(it)) => {
+                    segments.extend(container_segments(db, ModuleDef::Trait(it)))
+                }
+                Some(AssocItemContainer::Impl(impl_)) => match impl_.self_ty(db).as_adt() {
+                    Some(adt) => segments.extend(container_segments(db, ModuleDef::Adt(adt))),
+                    // Inherent impls on non-ADT types have no nameable
+                    // container, fall back to the surrounding module.
+                    None => segments.extend(
+                        impl_
+                            .module(db)
+                            .path_to_root(db)
+                            .into_iter()
+                            .rev()
+                            .filter_map(|m| m.name(db)),
+                    ),
+                },
+                None => segments.extend(
+                    def.module(db)?.path_to_root(db).into_iter().rev().filter_map(|m| m.name(db)),
+                ),
+            }
+            segments.push(name);
+        }
+    }
+
+    let path = segments.iter().map(|name| name.display(db)).join("::");
+    Some(if path.is_empty() { prefix } else { format!("{prefix}::{path}") })
+}
+
+fn container_segments(db: &RootDatabase, container: ModuleDef) -> Vec<hir::Name> {
+    let mut segments: Vec<_> = match container.module(db) {
+        Some(module) => {
+            module.path_to_root(db).into_iter().rev().filter_map(|m| m.name(db)).collect()
+        }
+        None => Vec::new(),
+    };
+    segments.extend(container.name(db));
+    segments
+}
+
+fn as_assoc_item(db: &RootDatabase, def: Definition) -> Option<AssocItem> {
+    match def {
+        Definition::Function(it) => it.as_assoc_item(db),
+        Definition::Const(it) => it.as_assoc_item(db),
+        Definition::TypeAlias(it) => it.as_assoc_item(db),
+        _ => None,
+    }
+}
+
+/// The item a `use` would have to name to bring the definition into scope.
+fn import_target(db: &RootDatabase, def: Definition) -> Option<ItemInNs> {
+    if let Some(assoc) = as_assoc_item(db, def) {
+        return match assoc.container(db) {
+            AssocItemContainer::Trait(it) => Some(ModuleDef::Trait(it).into()),
+            AssocItemContainer::Impl(impl_) => {
+                impl_.self_ty(db).as_adt().map(|adt| ModuleDef::Adt(adt).into())
+            }
+        };
+    }
+    match def {
+        Definition::Module(it) => Some(ModuleDef::Module(it).into()),
+        Definition::Function(it) => Some(ModuleDef::Function(it).into()),
+        Definition::Adt(it) => Some(ModuleDef::Adt(it).into()),
+        Definition::Variant(it) => Some(ModuleDef::Variant(it).into()),
+        Definition::Const(it) => Some(ModuleDef::Const(it).into()),
+        Definition::Static(it) => Some(ModuleDef::Static(it).into()),
+        Definition::Trait(it) => Some(ModuleDef::Trait(it).into()),
+        Definition::TraitAlias(it) => Some(ModuleDef::TraitAlias(it).into()),
+        Definition::TypeAlias(it) => Some(ModuleDef::TypeAlias(it).into()),
+        Definition::Macro(it) => Some(it.into()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture;
+
+    #[track_caller]
+    fn check(ra_fixture: &str, canonical: &str, importable: Option<&str>) {
+        let (analysis, position) = fixture::position(ra_fixture);
+        let item_path = analysis.item_path(position).unwrap().expect("no item at position");
+        assert_eq!(item_path.canonical, canonical);
+        assert_eq!(item_path.importable.as_deref(), importable);
+    }
+
+    #[test]
+    fn canonical_path_for_free_function() {
+        check(
+            r#"
+mod foo {
+    pub mod bar {
+        pub fn baz() {}
+    }
+}
+use foo::bar;
+fn main() { bar::baz$0(); }
+"#,
+            "crate::foo::bar::baz",
+            Some("foo::bar::baz"),
+        );
+    }
+
+    #[test]
+    fn canonical_path_for_assoc_item() {
+        check(
+            r#"
+mod foo {
+    pub struct Bar;
+    impl Bar {
+        pub fn baz() {}
+    }
+}
+fn main() { foo::Bar::baz$0(); }
+"#,
+            "crate::foo::Bar::baz",
+            Some("foo::Bar"),
+        );
+    }
+
+    #[test]
+    fn path_for_item_in_other_crate() {
+        check(
+            r#"
+//- /main.rs crate:main deps:dep
+fn main() { dep::public$0(); }
+//- /dep.rs crate:dep
+pub fn public() {}
+"#,
+            "dep::public",
+            Some("dep::public"),
+        );
+    }
+
+    #[test]
+    fn importable_path_prefers_shortest_reexport() {
+        check(
+            r#"
+mod foo {
+    mod bar {
+        pub fn baz() {}
+    }
+    pub use bar::baz;
+}
+fn main() { foo::baz$0(); }
+"#,
+            "crate::foo::bar::baz",
+            Some("foo::baz"),
+        );
+    }
+}
@@ -20,7 +20,10 @@ mod navigation_target;
 
 mod annotations;
 mod call_hierarchy;
+mod colors;
+mod crate_graph_stats;
 mod doc_links;
+mod eval_cfg;
 mod expand_macro;
 mod extend_selection;
 mod fetch_crates;
@@ -34,7 +37,9 @@ mod highlight_related;
 mod hover;
 mod inlay_hints;
 mod interpret_function;
+mod item_path;
 mod join_lines;
+mod linked_editing;
 mod markdown_remove;
 mod matching_brace;
 mod moniker;
@@ -43,6 +48,7 @@ mod parent_module;
 mod references;
 mod rename;
 mod runnables;
+mod shuffle_crate_graph;
 mod signature_help;
 mod ssr;
 mod static_index;
@@ -50,7 +56,10 @@ mod status;
 mod syntax_highlighting;
 mod syntax_tree;
 mod test_explorer;
+mod todo_comments;
+mod type_of;
 mod typing;
+mod unresolved_imports;
 mod view_crate_graph;
 mod view_hir;
 mod view_item_tree;
@@ -79,19 +88,23 @@ use crate::navigation_target::ToNav;
 pub use crate::{
     annotations::{Annotation, AnnotationConfig, AnnotationKind, AnnotationLocation},
     call_hierarchy::CallItem,
-    expand_macro::ExpandedMacro,
+    colors::ColorInfo,
+    crate_graph_stats::CrateGraphStats,
+    eval_cfg::EvalCfgResult,
+    expand_macro::{ExpandedMacro, ExpandedMacroStep},
     file_structure::{StructureNode, StructureNodeKind},
     folding_ranges::{Fold, FoldKind},
     highlight_related::{HighlightRelatedConfig, HighlightedRange},
     hover::{
-        HoverAction, HoverConfig, HoverDocFormat, HoverGotoTypeData, HoverResult,
-        MemoryLayoutHoverConfig, MemoryLayoutHoverRenderKind,
+        HoverAction, HoverActionsConfig, HoverConfig, HoverDocFormat, HoverGotoTypeData,
+        HoverResult, MemoryLayoutHoverConfig, MemoryLayoutHoverRenderKind,
     },
     inlay_hints::{
         AdjustmentHints, AdjustmentHintsMode, ClosureReturnTypeHints, DiscriminantHints,
         GenericParameterHints, InlayFieldsToResolve, InlayHint, InlayHintLabel, InlayHintLabelPart,
         InlayHintPosition, InlayHintsConfig, InlayKind, InlayTooltip, LifetimeElisionHints,
     },
+    item_path::ItemPath,
     join_lines::JoinLinesConfig,
     markup::Markup,
     moniker::{
@@ -110,13 +123,16 @@ pub use crate::{
         HighlightConfig, HlRange,
     },
     test_explorer::{TestItem, TestItemKind},
+    todo_comments::{TodoComment, TodoCommentsConfig},
+    unresolved_imports::UnresolvedImport,
 };
 pub use hir::Semantics;
 pub use ide_assists::{
-    Assist, AssistConfig, AssistId, AssistKind, AssistResolveStrategy, SingleResolve,
+    Assist, AssistConfig, AssistId, AssistKind, AssistResolveStrategy, SingleResolve, ASSIST_IDS,
 };
 pub use ide_completion::{
     CallableSnippets, CompletionConfig, CompletionItem, CompletionItemKind, CompletionRelevance,
+    CompletionScope, CompletionScoringWeights,
     Snippet, SnippetScope,
 };
 pub use ide_db::{
@@ -172,6 +188,10 @@ impl AnalysisHost {
         self.db.update_base_query_lru_capacities(lru_capacity);
     }
 
+    pub fn update_macro_expansion_lru_capacity(&mut self, capacity: u16) {
+        self.db.update_macro_expansion_lru_capacity(capacity);
+    }
+
     pub fn update_lru_capacities(&mut self, lru_capacities: &FxHashMap<Box<str>, u16>) {
         self.db.update_lru_capacities(lru_capacities);
     }
@@ -195,6 +215,11 @@ impl AnalysisHost {
     pub fn request_cancellation(&mut self) {
         self.db.request_cancellation();
     }
+    /// Rebuilds the crate graph with freshly allocated, shuffled `CrateId`s. A debugging
+    /// aid for catching accidental dependence on crate id stability.
+    pub fn shuffle_crate_graph(&mut self) {
+        shuffle_crate_graph::shuffle_crate_graph(&mut self.db);
+    }
     pub fn raw_database(&self) -> &RootDatabase {
         &self.db
     }
@@ -284,6 +309,13 @@ impl Analysis {
         self.with_db(move |db| prime_caches::parallel_prime_caches(db, num_worker_threads, &cb))
     }
 
+    /// Eagerly computes the analysis caches of a single crate, making its
+    /// items available to e.g. import-inserting completions without priming
+    /// the whole workspace.
+    pub fn load_crate(&self, crate_id: CrateId) -> Cancellable<()> {
+        self.with_db(|db| prime_caches::prime_crate(db, crate_id))
+    }
+
     /// Gets the text of the source file.
     pub fn file_text(&self, file_id: FileId) -> Cancellable<Arc<str>> {
         self.with_db(|db| SourceDatabase::file_text(db, file_id))
@@ -368,14 +400,43 @@ impl Analysis {
         self.with_db(|db| view_crate_graph::view_crate_graph(db, full))
     }
 
+    /// Returns the crate graph currently loaded in the database.
+    pub fn crate_graph(&self) -> Cancellable<Arc<CrateGraph>> {
+        self.with_db(|db| db.crate_graph())
+    }
+
+    /// Returns summary statistics about the crate graph.
+    pub fn crate_graph_stats(&self) -> Cancellable<CrateGraphStats> {
+        self.with_db(crate_graph_stats::crate_graph_stats)
+    }
+
     pub fn fetch_crates(&self) -> Cancellable<FxIndexSet<CrateInfo>> {
         self.with_db(fetch_crates::fetch_crates)
     }
 
+    /// Collects `TODO`/`FIXME` style markers from comments across the workspace.
+    pub fn todo_comments(&self, config: &TodoCommentsConfig) -> Cancellable<Vec<TodoComment>> {
+        self.with_db(|db| todo_comments::todo_comments(db, config))
+    }
+
     pub fn expand_macro(&self, position: FilePosition) -> Cancellable<Option<ExpandedMacro>> {
         self.with_db(|db| expand_macro::expand_macro(db, position))
     }
 
+    /// Expands the macro call at the given position by a single level.
+    pub fn expand_macro_step(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Option<ExpandedMacroStep>> {
+        self.with_db(|db| expand_macro::expand_macro_step(db, position))
+    }
+
+    /// Evaluates the `#[cfg]` attribute at the position against the cfg set of
+    /// the containing crate.
+    pub fn eval_cfg(&self, position: FilePosition) -> Cancellable<Option<EvalCfgResult>> {
+        self.with_db(|db| eval_cfg::eval_cfg(db, position))
+    }
+
     /// Returns an edit to remove all newlines in the range, cleaning up minor
     /// stuff like trailing commas.
     pub fn join_lines(&self, config: &JoinLinesConfig, frange: FileRange) -> Cancellable<TextEdit> {
@@ -385,6 +446,15 @@ impl Analysis {
         })
     }
 
+    /// Returns the ranges in the current file that refer to the same local
+    /// binding or field as the one under the cursor, for editing them together.
+    pub fn linked_editing_ranges(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Option<Vec<TextRange>>> {
+        self.with_db(|db| linked_editing::linked_editing_ranges(db, position))
+    }
+
     /// Returns an edit which should be applied when opening a new line, fixing
     /// up minor stuff like continuing the comment.
     /// The edit will be a snippet (with `$0`).
@@ -424,6 +494,22 @@ impl Analysis {
         })
     }
 
+    /// Returns the color literals in the file, matched against the given call path
+    /// patterns.
+    pub fn document_colors(
+        &self,
+        patterns: &[String],
+        file_id: FileId,
+    ) -> Cancellable<Vec<ColorInfo>> {
+        // FIXME: Edition
+        self.with_db(|db| {
+            colors::document_colors(
+                &db.parse(EditionedFileId::current_edition(file_id)).tree(),
+                patterns,
+            )
+        })
+    }
+
     /// Returns a list of the places in the file where type hints can be displayed.
     pub fn inlay_hints(
         &self,
@@ -517,6 +603,11 @@ impl Analysis {
         self.with_db(|db| hover::hover(db, range, config))
     }
 
+    /// Returns the inferred type of the expression covering the range, if any.
+    pub fn type_of_range(&self, range: FileRange) -> Cancellable<Option<String>> {
+        self.with_db(|db| type_of::type_of_range(db, range))
+    }
+
     /// Returns moniker of symbol at position.
     pub fn moniker(
         &self,
@@ -540,6 +631,24 @@ impl Analysis {
         })
     }
 
+    /// Returns the rendered markdown documentation of the symbol under the
+    /// cursor, with intra-doc links resolved to their targets where possible.
+    pub fn docs_for_position(&self, position: FilePosition) -> Cancellable<Option<String>> {
+        self.with_db(|db| doc_links::docs_for_position(db, position))
+    }
+
+    /// Returns the canonical and the shortest importable path of the item
+    /// referenced at the position.
+    pub fn item_path(&self, position: FilePosition) -> Cancellable<Option<ItemPath>> {
+        self.with_db(|db| item_path::item_path(db, position))
+    }
+
+    /// Returns the `use` paths in the file that don't resolve, for bulk
+    /// autoimport or removal tooling.
+    pub fn unresolved_imports(&self, file_id: FileId) -> Cancellable<Vec<UnresolvedImport>> {
+        self.with_db(|db| unresolved_imports::unresolved_imports(db, file_id))
+    }
+
     /// Computes parameter information at the given position.
     pub fn signature_help(&self, position: FilePosition) -> Cancellable<Option<SignatureHelp>> {
         self.with_db(|db| signature_help::signature_help(db, position))
@@ -578,6 +687,22 @@ impl Analysis {
         self.with_db(|db| db.crate_graph().transitive_rev_deps(crate_id).collect())
     }
 
+    /// Returns the crates with the given display name.
+    pub fn crates_with_name(&self, name: &str) -> Cancellable<Vec<CrateId>> {
+        self.with_db(|db| {
+            let graph = db.crate_graph();
+            graph
+                .iter()
+                .filter(|&crate_id| {
+                    graph[crate_id]
+                        .display_name
+                        .as_ref()
+                        .is_some_and(|it| it.canonical_name().as_str() == name)
+                })
+                .collect()
+        })
+    }
+
     /// Returns crates this file *might* belong too.
     pub fn relevant_crates_for(&self, file_id: FileId) -> Cancellable<Vec<CrateId>> {
         self.with_db(|db| db.relevant_crates(file_id).iter().copied().collect())
@@ -588,6 +713,21 @@ impl Analysis {
         self.with_db(|db| db.crate_graph()[crate_id].edition)
     }
 
+    /// Returns the canonical display name of the given crate.
+    pub fn crate_name(&self, crate_id: CrateId) -> Cancellable<Option<String>> {
+        self.with_db(|db| {
+            db.crate_graph()[crate_id]
+                .display_name
+                .as_ref()
+                .map(|it| it.canonical_name().as_str().to_owned())
+        })
+    }
+
+    /// Returns the version of the given crate.
+    pub fn crate_version(&self, crate_id: CrateId) -> Cancellable<Option<String>> {
+        self.with_db(|db| db.crate_graph()[crate_id].version.clone())
+    }
+
     /// Returns true if this crate has `no_std` or `no_core` specified.
     pub fn is_crate_no_std(&self, crate_id: CrateId) -> Cancellable<bool> {
         self.with_db(|db| hir::db::DefDatabase::crate_def_map(db, crate_id).is_no_std())
@@ -756,10 +896,19 @@ impl Analysis {
         &self,
         file_id: FileId,
         new_name_stem: &str,
-    ) -> Cancellable<Option<SourceChange>> {
+    ) -> Cancellable<Option<Result<SourceChange, RenameError>>> {
         self.with_db(|db| rename::will_rename_file(db, file_id, new_name_stem))
     }
 
+    pub fn will_move_file(
+        &self,
+        file_id: FileId,
+        new_parent_file_id: FileId,
+        new_name_stem: &str,
+    ) -> Cancellable<Option<Result<SourceChange, RenameError>>> {
+        self.with_db(|db| rename::will_move_file(db, file_id, new_parent_file_id, new_name_stem))
+    }
+
     pub fn structural_search_replace(
         &self,
         query: &str,
@@ -0,0 +1,176 @@
+use hir::Semantics;
+use ide_db::{
+    defs::{Definition, IdentClass},
+    search::{FileReference, FileReferenceNode, SearchScope},
+    RootDatabase,
+};
+use span::EditionedFileId;
+use syntax::{ast, AstNode, SyntaxKind::IDENT, TextRange};
+
+use crate::{navigation_target::ToNav, TryToNav};
+
+// Feature: Linked Editing
+//
+// When the cursor is on a local binding or a field, computes the set of ranges
+// in the current file that refer to the same definition, so the client can
+// edit all of them simultaneously. Backs the `textDocument/linkedEditingRange`
+// LSP request.
+pub(crate) fn linked_editing_ranges(
+    db: &RootDatabase,
+    ide_db::FilePosition { file_id, offset }: ide_db::FilePosition,
+) -> Option<Vec<TextRange>> {
+    let _p = tracing::info_span!("linked_editing_ranges").entered();
+    let sema = Semantics::new(db);
+    let file_id = sema
+        .attach_first_edition(file_id)
+        .unwrap_or_else(|| EditionedFileId::current_edition(file_id));
+    let syntax = sema.parse(file_id).syntax().clone();
+
+    let token = syntax.token_at_offset(offset).find(|it| it.kind() == IDENT)?;
+    let def = match IdentClass::classify_token(&sema, &token)?.definitions().as_slice() {
+        &[def] => def,
+        _ => return None,
+    };
+    // Only locals and fields are linked. Anything else can have references
+    // outside of the current file, which editing the linked ranges would miss.
+    if !matches!(def, Definition::Local(_) | Definition::Field(_)) {
+        return None;
+    }
+
+    let mut ranges: Vec<TextRange> = def
+        .usages(&sema)
+        .in_scope(&SearchScope::single_file(file_id))
+        .include_self_refs()
+        .all()
+        .references
+        .remove(&file_id)
+        .into_iter()
+        .flatten()
+        // Shorthands reference both a field and a local at once, renaming them
+        // textually would change which field or local is referenced.
+        .filter(|FileReference { name, .. }| !is_record_shorthand(name))
+        .map(|FileReference { range, .. }| range)
+        .collect();
+    match def {
+        Definition::Local(local) => ranges.extend(
+            local
+                .sources(sema.db)
+                .into_iter()
+                .flat_map(|it| it.to_nav(sema.db))
+                .filter(|decl| decl.file_id == file_id)
+                .filter_map(|decl| decl.focus_range),
+        ),
+        def => {
+            if let Some(navs) = def.try_to_nav(sema.db) {
+                ranges.extend(
+                    navs.into_iter()
+                        .filter(|nav| nav.file_id == file_id)
+                        .filter_map(|nav| nav.focus_range),
+                );
+            }
+        }
+    }
+
+    // Linked editing requires all ranges to hold the very same text.
+    ranges.retain(|&range| syntax.text().slice(range) == *token.text());
+    ranges.sort_by_key(|range| range.start());
+    ranges.dedup();
+    if ranges.is_empty() {
+        return None;
+    }
+    Some(ranges)
+}
+
+fn is_record_shorthand(name: &FileReferenceNode) -> bool {
+    match name {
+        FileReferenceNode::NameRef(name_ref) => ast::RecordExprField::for_name_ref(name_ref)
+            .is_some_and(|field| field.name_ref().is_none()),
+        FileReferenceNode::Name(name) => ast::RecordPatField::for_field_name(name).is_some(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture;
+
+    #[track_caller]
+    fn check(ra_fixture: &str) {
+        let (analysis, position, mut expected) = fixture::annotations(ra_fixture);
+        let mut ranges = analysis.linked_editing_ranges(position).unwrap().unwrap_or_default();
+        ranges.sort_by_key(|range| range.start());
+        expected.sort_by_key(|&(range, _)| range.range.start());
+        assert_eq!(ranges, expected.into_iter().map(|(range, _)| range.range).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn local_binding() {
+        check(
+            r#"
+fn foo() {
+    let bar$0 = 92;
+      //^^^
+    bar;
+  //^^^
+    let bar = bar + 1;
+            //^^^
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn local_shadowing_links_only_in_scope_binding() {
+        check(
+            r#"
+fn foo() {
+    let bar = 92;
+    let bar = 10;
+      //^^^
+    bar$0;
+  //^^^
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn field() {
+        check(
+            r#"
+struct Foo { bar: u32 }
+           //^^^
+fn foo(foo: Foo) {
+    foo.bar$0;
+      //^^^
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn field_init_shorthand_is_not_linked() {
+        check(
+            r#"
+struct Foo { bar: u32 }
+fn make(bar$0: u32) -> Foo {
+      //^^^
+    let _ = Foo { bar };
+    Foo { bar: bar }
+             //^^^
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn items_are_not_linked() {
+        check(
+            r#"
+fn foo$0() {
+    foo();
+}
+"#,
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::{iter::once, mem};
 use hir::Semantics;
 use ide_db::{helpers::pick_best_token, FileRange, RootDatabase};
 use itertools::Itertools;
-use syntax::{algo, ast, match_ast, AstNode, SyntaxElement, SyntaxKind, SyntaxNode, TextRange};
+use syntax::{ast, match_ast, AstNode, SyntaxElement, SyntaxKind, SyntaxNode, TextRange};
 use text_edit::{TextEdit, TextEditBuilder};
 
 #[derive(Copy, Clone, Debug)]
@@ -155,23 +155,49 @@ fn replace_nodes<'a>(
         None
     };
 
+    let (first_range, first_text) = extend_with_leading_comments(first);
+    let (second_range, second_text) = extend_with_leading_comments(second);
+
     let first_with_cursor = match cursor_offset {
         Some(offset) => {
-            let mut item_text = first.text().to_string();
-            item_text.insert_str(offset.into(), "$0");
+            let mut item_text = first_text;
+            // `cursor_offset` is relative to the node, which sits after its
+            // leading comments in the extended text.
+            let prefix_len = first.text_range().start() - first_range.start();
+            item_text.insert_str((offset + prefix_len).into(), "$0");
             item_text
         }
-        None => first.text().to_string(),
+        None => first_text,
     };
 
     let mut edit = TextEditBuilder::default();
 
-    algo::diff(first, second).into_text_edit(&mut edit);
-    edit.replace(second.text_range(), first_with_cursor);
+    edit.replace(first_range, second_text);
+    edit.replace(second_range, first_with_cursor);
 
     edit.finish()
 }
 
+/// Extends a node's range with the comment block directly above it (no blank
+/// line in between), so that comments travel with the item they document when
+/// it is moved.
+fn extend_with_leading_comments(node: &SyntaxNode) -> (TextRange, String) {
+    let mut start = node.text_range().start();
+    let mut element = node.prev_sibling_or_token();
+    while let Some(current) = element {
+        match current.kind() {
+            SyntaxKind::COMMENT => start = current.text_range().start(),
+            SyntaxKind::WHITESPACE
+                if current.as_token().is_some_and(|it| it.text().matches('\n').count() <= 1) => {}
+            _ => break,
+        }
+        element = current.prev_sibling_or_token();
+    }
+    let range = TextRange::new(start, node.text_range().end());
+    let text = node.ancestors().last().unwrap().text().slice(range).to_string();
+    (range, text)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::fixture;
@@ -884,6 +910,82 @@ fn main() {}
         );
     }
 
+    #[test]
+    fn test_leading_comments_move_with_item() {
+        check(
+            r#"
+fn main() {}
+
+// A comment describing `foo`.
+// It spans two lines.
+fn foo() {}$0$0
+"#,
+            expect![[r#"
+                // A comment describing `foo`.
+                // It spans two lines.
+                fn foo() {}$0
+
+                fn main() {}
+            "#]],
+            Direction::Up,
+        );
+        check(
+            r#"
+// A comment describing `main`.
+fn main() {}$0$0
+
+fn foo() {}
+"#,
+            expect![[r#"
+                fn foo() {}
+
+                // A comment describing `main`.
+                fn main() {}$0
+            "#]],
+            Direction::Down,
+        );
+    }
+
+    #[test]
+    fn test_detached_comment_stays_in_place() {
+        check(
+            r#"
+// A comment about the module, not about `main`.
+
+fn main() {}
+
+fn foo() {}$0$0
+"#,
+            expect![[r#"
+                // A comment about the module, not about `main`.
+
+                fn foo() {}$0
+
+                fn main() {}
+            "#]],
+            Direction::Up,
+        );
+    }
+
+    #[test]
+    fn test_moves_item_with_cfg_attr() {
+        check(
+            r#"
+fn main() {}
+
+#[cfg(test)]
+fn foo() {}$0$0
+"#,
+            expect![[r#"
+                #[cfg(test)]
+                fn foo() {}$0
+
+                fn main() {}
+            "#]],
+            Direction::Up,
+        );
+    }
+
     #[test]
     fn handles_empty_file() {
         check(r#"$0$0"#, expect![[r#""#]], Direction::Up);
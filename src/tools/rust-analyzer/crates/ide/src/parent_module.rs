@@ -141,6 +141,23 @@ $0
         );
     }
 
+    #[test]
+    fn test_resolve_parent_module_with_nested_path_attrs() {
+        check(
+            r#"
+//- /main.rs
+#[path = "custom/a.rs"]
+mod foo;
+//- /custom/a.rs
+#[path = "b/c.rs"]
+mod bar;
+  //^^^
+//- /custom/b/c.rs
+$0
+"#,
+        );
+    }
+
     #[test]
     fn test_resolve_crate_root() {
         let (analysis, file_id) = fixture::file(
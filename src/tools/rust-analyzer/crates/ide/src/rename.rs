@@ -4,7 +4,7 @@
 //! tests. This module also implements a couple of magic tricks, like renaming
 //! `self` and to `self` (to switch between associated function and method).
 
-use hir::{AsAssocItem, HirFileIdExt, InFile, Semantics};
+use hir::{AsAssocItem, HirFileIdExt, InFile, ModuleSource, ScopeDef, Semantics};
 use ide_db::{
     defs::{Definition, NameClass, NameRefClass},
     rename::{bail, format_err, source_edit_from_references, IdentifierKind},
@@ -15,8 +15,9 @@ use itertools::Itertools;
 use span::Edition;
 use stdx::{always, never};
 use syntax::{
-    ast, utils::is_raw_identifier, AstNode, SmolStr, SyntaxKind, SyntaxNode, TextRange, TextSize,
-    ToSmolStr,
+    ast::{self, HasModuleItem, HasName},
+    utils::is_raw_identifier,
+    AstNode, SmolStr, SyntaxKind, SyntaxNode, TextRange, TextSize, ToSmolStr,
 };
 
 use text_edit::TextEdit;
@@ -132,6 +133,7 @@ pub(crate) fn rename(
             .collect(),
         None => defs
             .map(|(.., def)| {
+                check_conflicts(&sema, def, new_name)?;
                 if let Definition::Local(local) = def {
                     if let Some(self_param) = local.as_self_param(sema.db) {
                         cov_mark::hit!(rename_self_to_param);
@@ -154,21 +156,230 @@ pub(crate) fn rename(
 }
 
 /// Called by the client when it is about to rename a file.
+///
+/// Returns `None` if the file is not a module root, in which case there is
+/// nothing to update.
 pub(crate) fn will_rename_file(
     db: &RootDatabase,
     file_id: FileId,
     new_name_stem: &str,
-) -> Option<SourceChange> {
+) -> Option<RenameResult<SourceChange>> {
     let sema = Semantics::new(db);
     let module = sema.file_to_module_def(file_id)?;
+    Some(rename_module_to(&sema, module, new_name_stem))
+}
+
+fn rename_module_to(
+    sema: &Semantics<'_, RootDatabase>,
+    module: hir::Module,
+    new_name_stem: &str,
+) -> RenameResult<SourceChange> {
+    if let Some(parent) = module.parent(sema.db) {
+        if sibling_module_exists(sema.db, parent, module, new_name_stem) {
+            bail!(
+                "Cannot rename to `{new_name_stem}`: the parent module already has a child module with that name"
+            );
+        }
+    }
     let def = Definition::Module(module);
     let mut change = if is_raw_identifier(new_name_stem) {
-        def.rename(&sema, &SmolStr::from_iter(["r#", new_name_stem])).ok()?
+        def.rename(sema, &SmolStr::from_iter(["r#", new_name_stem]))?
     } else {
-        def.rename(&sema, new_name_stem).ok()?
+        def.rename(sema, new_name_stem)?
     };
     change.file_system_edits.clear();
-    Some(change)
+    Ok(change)
+}
+
+/// Called by the client when it is about to move a file into a different
+/// directory: re-parents the module under the module owning the target
+/// directory by rewriting the `mod` declaration and all references.
+///
+/// Returns `None` if either file is not a module root, in which case there is
+/// nothing to update.
+pub(crate) fn will_move_file(
+    db: &RootDatabase,
+    file_id: FileId,
+    new_parent_file_id: FileId,
+    new_name_stem: &str,
+) -> Option<RenameResult<SourceChange>> {
+    let sema = Semantics::new(db);
+    let module = sema.file_to_module_def(file_id)?;
+    let new_parent = sema.file_to_module_def(new_parent_file_id)?;
+    Some(move_module(&sema, module, new_parent, new_name_stem))
+}
+
+fn move_module(
+    sema: &Semantics<'_, RootDatabase>,
+    module: hir::Module,
+    new_parent: hir::Module,
+    new_name_stem: &str,
+) -> RenameResult<SourceChange> {
+    let db = sema.db;
+    if new_parent.krate() != module.krate() {
+        bail!("Cannot move `{new_name_stem}`: the destination belongs to a different crate");
+    }
+    if new_parent == module || new_parent.path_to_root(db).contains(&module) {
+        bail!("Cannot move `{new_name_stem}` into one of its own submodules");
+    }
+    if sibling_module_exists(db, new_parent, module, new_name_stem) {
+        bail!(
+            "Cannot move to `{new_name_stem}`: the destination already contains a module with that name"
+        );
+    }
+
+    let src = module.declaration_source(db).ok_or_else(|| {
+        format_err!("Cannot move `{new_name_stem}`: the module has no `mod` declaration")
+    })?;
+    if src.file_id.is_macro() {
+        bail!("Cannot move `{new_name_stem}`: the `mod` declaration comes from a macro expansion");
+    }
+    let decl_file_id = src.file_id.original_file(db);
+    let decl_range = src.value.syntax().text_range();
+    // Swallow the line break after the declaration so its removal doesn't
+    // leave a blank line behind.
+    let mut delete_range = decl_range;
+    if let Some(ws) = src
+        .value
+        .syntax()
+        .next_sibling_or_token()
+        .and_then(|it| it.into_token())
+        .filter(|it| it.kind() == SyntaxKind::WHITESPACE)
+    {
+        if let Some(line_break) = ws.text().find('\n') {
+            delete_range = TextRange::new(
+                delete_range.start(),
+                ws.text_range().start() + TextSize::new(line_break as u32 + 1),
+            );
+        }
+    }
+
+    // Reuse the declaration verbatim, only substituting the name, so that
+    // attributes, visibility and doc comments survive the move.
+    let new_name = if is_raw_identifier(new_name_stem) {
+        format!("r#{new_name_stem}")
+    } else {
+        new_name_stem.to_owned()
+    };
+    let decl_text = src.value.syntax().text().to_string();
+    let name_range = src
+        .value
+        .name()
+        .ok_or_else(|| {
+            format_err!("Cannot move `{new_name_stem}`: the `mod` declaration has no name")
+        })?
+        .syntax()
+        .text_range()
+        - decl_range.start();
+    let mut new_decl = String::new();
+    new_decl.push_str(&decl_text[..usize::from(name_range.start())]);
+    new_decl.push_str(&new_name);
+    new_decl.push_str(&decl_text[usize::from(name_range.end())..]);
+
+    let dst = new_parent.definition_source(db);
+    if dst.file_id.is_macro() {
+        bail!("Cannot move `{new_name_stem}`: the destination module comes from a macro expansion");
+    }
+    let dst_file_id = dst.file_id.original_file(db);
+    let (insert_offset, insert_text) = match &dst.value {
+        ModuleSource::SourceFile(it) => {
+            let last_mod_decl = it
+                .items()
+                .filter_map(|item| match item {
+                    ast::Item::Module(it) if it.item_list().is_none() => Some(it),
+                    _ => None,
+                })
+                .last();
+            match last_mod_decl {
+                Some(it) => (it.syntax().text_range().end(), format!("\n{new_decl}")),
+                None => match it.items().next() {
+                    Some(first) => (first.syntax().text_range().start(), format!("{new_decl}\n")),
+                    None => (it.syntax().text_range().end(), format!("{new_decl}\n")),
+                },
+            }
+        }
+        _ => bail!("Cannot move `{new_name_stem}`: the destination module is not backed by a file"),
+    };
+
+    // The canonical path of the module after the move, without the leading
+    // `crate`/crate-name segment.
+    let local_path = new_parent
+        .path_to_root(db)
+        .into_iter()
+        .rev()
+        .filter_map(|it| it.name(db))
+        .map(|it| it.display(db).to_string())
+        .chain(std::iter::once(new_name.clone()))
+        .join("::");
+
+    let mut source_change = SourceChange::default();
+    let usages = Definition::Module(module).usages(sema).all();
+    for (usage_file_id, references) in usages.iter() {
+        let using_krate = sema
+            .file_to_module_def(usage_file_id.file_id())
+            .map_or_else(|| module.krate(), |it| it.krate());
+        let prefix = if using_krate == module.krate() {
+            "crate".to_owned()
+        } else {
+            match module.krate().display_name(db) {
+                Some(it) => it.canonical_name().to_string(),
+                None => {
+                    bail!("Cannot move `{new_name_stem}`: it is used from a crate without a name")
+                }
+            }
+        };
+        let new_path_text = format!("{prefix}::{local_path}");
+        let mut edit = TextEdit::builder();
+        for reference in references {
+            let Some(name_ref) = reference.name.as_name_ref() else { continue };
+            if name_ref.syntax().text_range() != reference.range {
+                bail!(
+                    "Cannot move `{new_name_stem}`: a usage inside a macro expansion cannot be mapped back onto the path"
+                );
+            }
+            let Some(path) = name_ref
+                .syntax()
+                .parent()
+                .and_then(ast::PathSegment::cast)
+                .map(|it| it.parent_path())
+            else {
+                continue;
+            };
+            // In `use a::{foo, bar}` the qualifier up to `foo` is shared with
+            // the other trees, so it cannot be rewritten in place.
+            if path.syntax().ancestors().filter_map(ast::UseTree::cast).any(|it| {
+                it.syntax().parent().is_some_and(|it| it.kind() == SyntaxKind::USE_TREE_LIST)
+            }) {
+                bail!(
+                    "Cannot move `{new_name_stem}`: it is referenced in a `use` declaration with nested trees; flatten the `use` first"
+                );
+            }
+            edit.replace(path.syntax().text_range(), new_path_text.clone());
+        }
+        let edit = edit.finish();
+        if !edit.is_empty() {
+            source_change.insert_source_edit(usage_file_id.file_id(), edit);
+        }
+    }
+    // FIXME: Relative paths (e.g. `super::`) *inside* the moved file still
+    // resolve against the old location and are not rewritten.
+    source_change.insert_source_edit(decl_file_id.file_id(), TextEdit::delete(delete_range));
+    source_change
+        .insert_source_edit(dst_file_id.file_id(), TextEdit::insert(insert_offset, insert_text));
+    Ok(source_change)
+}
+
+fn sibling_module_exists(
+    db: &RootDatabase,
+    parent: hir::Module,
+    module: hir::Module,
+    name_stem: &str,
+) -> bool {
+    parent
+        .children(db)
+        .filter(|it| *it != module)
+        .filter_map(|it| it.name(db))
+        .any(|name| name.as_str() == name_stem)
 }
 
 // FIXME: Should support `extern crate`.
@@ -315,6 +526,146 @@ fn find_definitions(
     }
 }
 
+/// Refuses renames that would collide with an existing name: a sibling field,
+/// a binding that is already visible from the definition site, or an item in
+/// the same module (or impl/trait) and namespace. Applying such a rename would
+/// produce code that no longer compiles or that silently changes meaning.
+fn check_conflicts(
+    sema: &Semantics<'_, RootDatabase>,
+    def: Definition,
+    new_name: &str,
+) -> RenameResult<()> {
+    let new_name = new_name.trim_start_matches("r#");
+    match def {
+        Definition::Field(field) => {
+            let variant = field.parent_def(sema.db);
+            if variant.fields(sema.db).iter().any(|it| it.name(sema.db).as_str() == new_name) {
+                bail!("Cannot rename to `{new_name}`: a field with that name already exists");
+            }
+        }
+        Definition::Local(local) => {
+            let src = local.primary_source(sema.db);
+            let root = sema.parse_or_expand(src.file());
+            let node = src.syntax_ptr().value.to_node(&root);
+            if let Some(param_list) = node.ancestors().find_map(ast::ParamList::cast) {
+                // Parameter patterns are not part of any expression scope, so
+                // compare against the sibling parameters instead.
+                let conflicts = param_list
+                    .params()
+                    .filter_map(|param| param.pat())
+                    .flat_map(|pat| pat.syntax().descendants().filter_map(ast::IdentPat::cast))
+                    .filter(|pat| pat.syntax() != &node)
+                    .any(|pat| {
+                        pat.name().is_some_and(|it| it.text().trim_start_matches("r#") == new_name)
+                    });
+                if conflicts {
+                    bail!(
+                        "Cannot rename to `{new_name}`: a parameter with that name already exists"
+                    );
+                }
+                return Ok(());
+            }
+            // The binding only becomes visible after the enclosing statement,
+            // so that is where existing bindings would collide with it.
+            let scope_node = node
+                .ancestors()
+                .find_map(ast::LetStmt::cast)
+                .map_or(node.clone(), |it| it.syntax().clone());
+            let offset = scope_node.text_range().end();
+            let Some(scope) = sema.scope_at_offset(&scope_node, offset) else { return Ok(()) };
+            let mut conflicts = false;
+            scope.process_all_names(&mut |name, scope_def| {
+                if let ScopeDef::Local(it) = scope_def {
+                    conflicts |= it != local && name.as_str() == new_name;
+                }
+            });
+            if conflicts {
+                bail!(
+                    "Cannot rename to `{new_name}`: a binding with that name is already in scope"
+                );
+            }
+        }
+        Definition::Function(_)
+        | Definition::Const(_)
+        | Definition::TypeAlias(_)
+        | Definition::Module(_)
+        | Definition::Adt(_)
+        | Definition::Trait(_)
+        | Definition::TraitAlias(_)
+        | Definition::Static(_) => {
+            if let Some(item) = assoc_item(sema, def) {
+                let items = match item.container(sema.db) {
+                    hir::AssocItemContainer::Trait(it) => it.items(sema.db),
+                    hir::AssocItemContainer::Impl(it) => it.items(sema.db),
+                };
+                let conflicts = items.into_iter().any(|it| {
+                    it != item
+                        && assoc_namespace(&it) == assoc_namespace(&item)
+                        && it.name(sema.db).is_some_and(|it| it.as_str() == new_name)
+                });
+                if conflicts {
+                    bail!(
+                        "Cannot rename to `{new_name}`: an associated item with that name already exists"
+                    );
+                }
+            } else if let Some(module) = def.module(sema.db) {
+                let conflicts = module.declarations(sema.db).into_iter().any(|decl| {
+                    let decl = Definition::from(decl);
+                    decl != def
+                        && namespace(decl).is_some()
+                        && namespace(decl) == namespace(def)
+                        && decl.name(sema.db).is_some_and(|it| it.as_str() == new_name)
+                });
+                if conflicts {
+                    bail!(
+                        "Cannot rename to `{new_name}`: an item with that name already exists in this module"
+                    );
+                }
+            }
+        }
+        _ => (),
+    }
+    return Ok(());
+
+    fn assoc_item(sema: &Semantics<'_, RootDatabase>, def: Definition) -> Option<hir::AssocItem> {
+        match def {
+            Definition::Function(it) => it.as_assoc_item(sema.db),
+            Definition::Const(it) => it.as_assoc_item(sema.db),
+            Definition::TypeAlias(it) => it.as_assoc_item(sema.db),
+            _ => None,
+        }
+    }
+
+    fn assoc_namespace(item: &hir::AssocItem) -> Ns {
+        match item {
+            hir::AssocItem::Function(_) | hir::AssocItem::Const(_) => Ns::Values,
+            hir::AssocItem::TypeAlias(_) => Ns::Types,
+        }
+    }
+
+    /// The namespace a name lives in, or `None` if the definition kind doesn't
+    /// participate in conflict checking.
+    fn namespace(def: Definition) -> Option<Ns> {
+        match def {
+            Definition::Module(_)
+            | Definition::Adt(_)
+            | Definition::Trait(_)
+            | Definition::TraitAlias(_)
+            | Definition::TypeAlias(_) => Some(Ns::Types),
+            Definition::Function(_) | Definition::Const(_) | Definition::Static(_) => {
+                Some(Ns::Values)
+            }
+            _ => None,
+        }
+    }
+
+    #[derive(PartialEq)]
+    enum Ns {
+        Types,
+        Values,
+    }
+}
+
 fn rename_to_self(
     sema: &Semantics<'_, RootDatabase>,
     local: hir::Local,
@@ -451,7 +802,7 @@ mod tests {
 
     use crate::fixture;
 
-    use super::{RangeInfo, RenameError};
+    use super::{FileRange, RangeInfo, RenameError};
 
     #[track_caller]
     fn check(new_name: &str, ra_fixture_before: &str, ra_fixture_after: &str) {
@@ -501,11 +852,28 @@ mod tests {
 
     fn check_expect_will_rename_file(new_name: &str, ra_fixture: &str, expect: Expect) {
         let (analysis, position) = fixture::position(ra_fixture);
-        let source_change = analysis
+        let result = analysis
             .will_rename_file(position.file_id, new_name)
             .unwrap()
-            .expect("Expect returned a RenameError");
-        expect.assert_eq(&filter_expect(source_change))
+            .expect("Expect returned no SourceChange");
+        match result {
+            Ok(source_change) => expect.assert_eq(&filter_expect(source_change)),
+            Err(err) => expect.assert_eq(&format!("error: {err}")),
+        }
+    }
+
+    fn check_expect_will_move_file(new_name: &str, ra_fixture: &str, expect: Expect) {
+        let (analysis, position, annotations) = fixture::annotations(ra_fixture);
+        let &(FileRange { file_id: new_parent_file_id, .. }, _) =
+            annotations.first().expect("expected an annotation on the destination module");
+        let result = analysis
+            .will_move_file(position.file_id, new_parent_file_id, new_name)
+            .unwrap()
+            .expect("Expect returned no SourceChange");
+        match result {
+            Ok(source_change) => expect.assert_eq(&filter_expect(source_change)),
+            Err(err) => expect.assert_eq(&format!("error: {err}")),
+        }
     }
 
     fn check_prepare(ra_fixture: &str, expect: Expect) {
@@ -535,6 +903,91 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_rename_local_to_existing_binding() {
+        check(
+            "a",
+            r#"
+fn f() {
+    let a = 1;
+    let b$0 = 2;
+    let _ = (a, b);
+}
+"#,
+            "error: Cannot rename to `a`: a binding with that name is already in scope",
+        );
+    }
+
+    #[test]
+    fn test_rename_local_shadowing_is_not_a_conflict() {
+        check(
+            "a",
+            r#"
+fn f() {
+    let b$0 = 2;
+    let a = 1;
+    let _ = a;
+}
+"#,
+            r#"
+fn f() {
+    let a = 2;
+    let a = 1;
+    let _ = a;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_rename_field_to_existing_field() {
+        check(
+            "b",
+            r#"struct S { a$0: u32, b: u32 }"#,
+            "error: Cannot rename to `b`: a field with that name already exists",
+        );
+    }
+
+    #[test]
+    fn test_rename_item_to_existing_item() {
+        check(
+            "Bar",
+            r#"
+struct Foo$0;
+struct Bar;
+"#,
+            "error: Cannot rename to `Bar`: an item with that name already exists in this module",
+        );
+        // A function and a struct live in different namespaces.
+        check(
+            "foo",
+            r#"
+struct S;
+fn bar$0() {}
+mod foo {}
+"#,
+            r#"
+struct S;
+fn foo() {}
+mod foo {}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_rename_method_to_existing_assoc_item() {
+        check(
+            "bar",
+            r#"
+trait T {
+    fn foo$0();
+    fn bar();
+}
+"#,
+            "error: Cannot rename to `bar`: an associated item with that name already exists",
+        );
+    }
+
     #[test]
     fn test_prepare_rename_namelikes() {
         check_prepare(r"fn name$0<'lifetime>() {}", expect![[r#"3..7: name"#]]);
@@ -1407,6 +1860,134 @@ mod bar$0;
         )
     }
 
+    #[test]
+    fn test_rename_mod_to_existing_sibling() {
+        check_expect_will_rename_file(
+            "bar",
+            r#"
+//- /lib.rs
+mod foo;
+mod bar;
+//- /foo.rs
+fn f$0() {}
+//- /bar.rs
+"#,
+            expect!["error: Cannot rename to `bar`: the parent module already has a child module with that name"],
+        )
+    }
+
+    #[test]
+    fn test_move_file_to_other_directory() {
+        check_expect_will_move_file(
+            "foo",
+            r#"
+//- /main.rs
+mod a;
+mod b;
+
+fn main() {
+    crate::a::foo::f();
+}
+//- /a.rs
+pub mod foo;
+use crate::a::foo::f as g;
+//- /a/foo.rs
+pub fn f$0() {}
+//- /b.rs
+pub fn existing() {}
+     //^^^^^^^^ dest
+"#,
+            expect![[r#"
+                source_file_edits: [
+                    (
+                        FileId(
+                            0,
+                        ),
+                        [
+                            Indel {
+                                insert: "crate::b::foo",
+                                delete: 31..44,
+                            },
+                        ],
+                    ),
+                    (
+                        FileId(
+                            1,
+                        ),
+                        [
+                            Indel {
+                                insert: "",
+                                delete: 0..13,
+                            },
+                            Indel {
+                                insert: "crate::b::foo",
+                                delete: 17..30,
+                            },
+                        ],
+                    ),
+                    (
+                        FileId(
+                            3,
+                        ),
+                        [
+                            Indel {
+                                insert: "pub mod foo;\n",
+                                delete: 0..0,
+                            },
+                        ],
+                    ),
+                ]
+                file_system_edits: []
+            "#]],
+        )
+    }
+
+    #[test]
+    fn test_move_file_to_existing_module() {
+        check_expect_will_move_file(
+            "foo",
+            r#"
+//- /main.rs
+mod a;
+mod b;
+//- /a.rs
+pub mod foo;
+//- /a/foo.rs
+pub fn f$0() {}
+//- /b.rs
+pub mod foo;
+     //^^^ dest
+//- /b/foo.rs
+"#,
+            expect!["error: Cannot move to `foo`: the destination already contains a module with that name"],
+        )
+    }
+
+    #[test]
+    fn test_move_file_used_in_nested_use_tree() {
+        check_expect_will_move_file(
+            "foo",
+            r#"
+//- /main.rs
+mod a;
+mod b;
+use crate::a::{foo, other};
+//- /a.rs
+pub mod foo;
+pub mod other;
+//- /a/foo.rs
+pub fn f$0() {}
+//- /a/other.rs
+//- /b.rs
+pub fn existing() {}
+     //^^^^^^^^ dest
+"#,
+            expect![[
+                "error: Cannot move `foo`: it is referenced in a `use` declaration with nested trees; flatten the `use` first"
+            ]],
+        )
+    }
+
     #[test]
     fn test_rename_mod_to_raw_ident() {
         check_expect(
@@ -174,6 +174,8 @@ impl StaticIndex<'_> {
             max_trait_assoc_items_count: None,
             max_fields_count: Some(5),
             max_enum_variants_count: Some(5),
+            // Static indexes have no use for hover action links.
+            actions: crate::HoverActionsConfig::NO_ACTIONS,
         };
         let tokens = tokens.filter(|token| {
             matches!(
@@ -0,0 +1,159 @@
+//! Collects `TODO`/`FIXME` style markers from comments across the workspace.
+
+use ide_db::{
+    base_db::{SourceDatabase, SourceRootDatabase},
+    symbol_index::SymbolsDatabase,
+    FileId, FileRange, RootDatabase,
+};
+use span::EditionedFileId;
+use syntax::{ast, AstToken, TextRange, TextSize};
+
+#[derive(Debug, Clone)]
+pub struct TodoCommentsConfig {
+    /// The markers to look for, e.g. `TODO` or `FIXME`. Markers match whole
+    /// words in comments, case-sensitively.
+    pub markers: Vec<String>,
+    /// Whether to scan dependency and sysroot sources as well.
+    pub scan_dependencies: bool,
+    /// Maximum number of results; scanning stops once it is reached.
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct TodoComment {
+    pub marker: String,
+    /// The comment text from the marker to the end of its line.
+    pub text: String,
+    pub range: FileRange,
+}
+
+pub(crate) fn todo_comments(db: &RootDatabase, config: &TodoCommentsConfig) -> Vec<TodoComment> {
+    let _p = tracing::info_span!("todo_comments").entered();
+
+    let mut res = Vec::new();
+    if config.markers.is_empty() || config.limit == 0 {
+        return res;
+    }
+
+    let mut roots: Vec<_> = db.local_roots().iter().copied().collect();
+    if config.scan_dependencies {
+        roots.extend(db.library_roots().iter().copied());
+    }
+    roots.sort();
+    for root_id in roots {
+        let source_root = db.source_root(root_id);
+        let mut files: Vec<_> = source_root
+            .iter()
+            .filter(|file_id| {
+                // Source roots also contain non-Rust files like `Cargo.toml`.
+                source_root
+                    .path_for_file(file_id)
+                    .and_then(|path| path.name_and_extension())
+                    .is_some_and(|(_, extension)| extension == Some("rs"))
+            })
+            .collect();
+        files.sort();
+        for file_id in files {
+            // The markers are purely lexical, so an edition guess is fine.
+            let parse = db.parse(EditionedFileId::current_edition(file_id));
+            let comments = parse
+                .syntax_node()
+                .descendants_with_tokens()
+                .filter_map(|it| it.into_token().and_then(ast::Comment::cast));
+            for comment in comments {
+                scan_comment(&mut res, config, file_id, &comment);
+                if res.len() >= config.limit {
+                    res.truncate(config.limit);
+                    return res;
+                }
+            }
+        }
+    }
+    res
+}
+
+fn scan_comment(
+    acc: &mut Vec<TodoComment>,
+    config: &TodoCommentsConfig,
+    file_id: FileId,
+    comment: &ast::Comment,
+) {
+    let text = comment.text();
+    let mut matches: Vec<(usize, &String)> = config
+        .markers
+        .iter()
+        .flat_map(|marker| {
+            text.match_indices(marker.as_str())
+                .filter(|&(idx, _)| is_word(text, idx, marker.len()))
+                .map(move |(idx, _)| (idx, marker))
+        })
+        .collect();
+    matches.sort_by_key(|&(idx, _)| idx);
+
+    for (idx, marker) in matches {
+        let line_end = text[idx..].find(['\n', '\r']).map_or(text.len(), |it| idx + it);
+        let entry_text = text[idx..line_end].trim_end();
+        let start = comment.syntax().text_range().start() + TextSize::new(idx as u32);
+        acc.push(TodoComment {
+            marker: marker.clone(),
+            text: entry_text.to_owned(),
+            range: FileRange { file_id, range: TextRange::at(start, TextSize::of(entry_text)) },
+        });
+    }
+}
+
+/// Checks that the match at `idx` of length `len` in `text` is not part of a
+/// larger word, so that a `TODO` marker does not fire inside `TODOS_DONE`.
+fn is_word(text: &str, idx: usize, len: usize) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    !text[..idx].chars().next_back().is_some_and(is_word_char)
+        && !text[idx + len..].chars().next().is_some_and(is_word_char)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture;
+
+    use super::TodoCommentsConfig;
+
+    fn config() -> TodoCommentsConfig {
+        TodoCommentsConfig {
+            markers: vec!["TODO".to_owned(), "FIXME".to_owned()],
+            scan_dependencies: false,
+            limit: 100,
+        }
+    }
+
+    #[test]
+    fn collects_markers_from_workspace_files() {
+        let (analysis, _) = fixture::file(
+            r#"
+// TODO: get to this eventually
+fn f() {
+    /* FIXME(someone): nested
+       second line is not part of the entry */
+    let _todo = 1; // not a TODO_MARKER
+}
+"#,
+        );
+        let res = analysis.todo_comments(&config()).unwrap();
+        let res: Vec<_> = res.iter().map(|it| (it.marker.as_str(), it.text.as_str())).collect();
+        assert_eq!(
+            res,
+            [("TODO", "TODO: get to this eventually"), ("FIXME", "FIXME(someone): nested")]
+        );
+    }
+
+    #[test]
+    fn respects_limit() {
+        let (analysis, _) = fixture::file(
+            r#"
+// TODO: one
+// TODO: two
+// TODO: three
+"#,
+        );
+        let res = analysis.todo_comments(&TodoCommentsConfig { limit: 2, ..config() }).unwrap();
+        assert_eq!(res.len(), 2);
+    }
+}
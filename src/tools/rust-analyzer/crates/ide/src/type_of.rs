@@ -0,0 +1,89 @@
+use either::Either;
+use hir::{HirDisplay, Semantics};
+use ide_db::{FileRange, RootDatabase};
+use syntax::{ast, AstNode};
+
+// Feature: Type of Expression
+//
+// Returns the inferred type of the expression (or pattern) covering the
+// selection, as a plain string. This is a lightweight alternative to hover for
+// tooling that wants just the type, without markup or actions.
+pub(crate) fn type_of_range(
+    db: &RootDatabase,
+    FileRange { file_id, range }: FileRange,
+) -> Option<String> {
+    let sema = Semantics::new(db);
+    let file = sema.parse_guess_edition(file_id).syntax().clone();
+    let expr_or_pat = file
+        .covering_element(range)
+        .ancestors()
+        .take_while(|it| ast::MacroCall::can_cast(it.kind()) || !ast::Item::can_cast(it.kind()))
+        .find_map(Either::<ast::Expr, ast::Pat>::cast)?;
+    let ty = match &expr_or_pat {
+        Either::Left(expr) => sema.type_of_expr(expr)?,
+        Either::Right(pat) => sema.type_of_pat(pat)?,
+    };
+    Some(ty.original.display(db).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture;
+
+    fn check(ra_fixture: &str, expect: &str) {
+        let (analysis, frange) = fixture::range(ra_fixture);
+        let ty = analysis.type_of_range(frange).unwrap().expect("no type for selection");
+        assert_eq!(ty, expect);
+    }
+
+    fn check_no_type(ra_fixture: &str) {
+        let (analysis, frange) = fixture::range(ra_fixture);
+        assert_eq!(analysis.type_of_range(frange).unwrap(), None);
+    }
+
+    #[test]
+    fn type_of_selected_expr() {
+        check(
+            r#"
+fn foo() {
+    let x = $01 + 2$0;
+}
+"#,
+            "i32",
+        );
+    }
+
+    #[test]
+    fn type_of_pat() {
+        check(
+            r#"
+fn foo() {
+    let $0(x, y)$0 = (1, "a");
+}
+"#,
+            "(i32, &str)",
+        );
+    }
+
+    #[test]
+    fn type_of_expands_to_covering_expr() {
+        check(
+            r#"
+fn bar() -> u64 { 0 }
+fn foo() {
+    let x = ba$0r$0();
+}
+"#,
+            "fn bar() -> u64",
+        );
+    }
+
+    #[test]
+    fn no_type_outside_of_expressions() {
+        check_no_type(
+            r#"
+st$0ruct S$0;
+"#,
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Collects the `use` paths in a file that fail to resolve.
+
+use hir::Semantics;
+use ide_db::{
+    imports::import_assets::NameToImport,
+    items_locator::{self, AssocSearchMode},
+    FileId, RootDatabase,
+};
+use syntax::{ast, AstNode, TextRange};
+
+/// An unresolved `use` path in a file.
+#[derive(Debug, Clone)]
+pub struct UnresolvedImport {
+    pub range: TextRange,
+    /// The path as written, e.g. `std::fmt::Debg`.
+    pub path: String,
+    /// Whether an importable item with the same name exists elsewhere, i.e.
+    /// whether the path looks more like a typo than a missing dependency.
+    pub has_import_candidate: bool,
+}
+
+/// Collects the `use` paths in the file that don't resolve, for "fix all
+/// imports" style tooling. More targeted than scanning all diagnostics for
+/// `unresolved-import`.
+pub(crate) fn unresolved_imports(db: &RootDatabase, file_id: FileId) -> Vec<UnresolvedImport> {
+    let sema = Semantics::new(db);
+    let source_file = sema.parse_guess_edition(file_id);
+    let krate = sema.scope(source_file.syntax()).map(|it| it.krate());
+
+    source_file
+        .syntax()
+        .descendants()
+        .filter_map(ast::UseTree::cast)
+        // Only look at the leaves; an unresolved prefix is reported once per
+        // leaf path it makes unresolved.
+        .filter(|tree| tree.use_tree_list().is_none())
+        .filter_map(|tree| {
+            let path = tree.path()?;
+            if sema.resolve_path(&path).is_some() {
+                return None;
+            }
+            let has_import_candidate = match (krate, path.segment()?.name_ref()) {
+                (Some(krate), Some(name)) => items_locator::items_with_name(
+                    &sema,
+                    krate,
+                    NameToImport::exact_case_sensitive(name.text().to_string()),
+                    AssocSearchMode::Exclude,
+                )
+                .next()
+                .is_some(),
+                _ => false,
+            };
+            Some(UnresolvedImport {
+                range: path.syntax().text_range(),
+                path: path.to_string(),
+                has_import_candidate,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture;
+
+    fn check(ra_fixture: &str, expected: &[(&str, bool)]) {
+        let (analysis, file_id) = fixture::file(ra_fixture);
+        let unresolved = analysis.unresolved_imports(file_id).unwrap();
+        let actual: Vec<_> = unresolved
+            .iter()
+            .map(|import| (import.path.as_str(), import.has_import_candidate))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn resolved_imports_are_not_reported() {
+        check(
+            r#"
+mod foo {
+    pub struct Exists;
+}
+use foo::Exists;
+"#,
+            &[],
+        );
+    }
+
+    #[test]
+    fn distinguishes_typos_from_missing_items() {
+        check(
+            r#"
+mod foo {
+    pub struct Exists;
+}
+use foo::Missing;
+use bar::Exists;
+"#,
+            &[("foo::Missing", false), ("bar::Exists", true)],
+        );
+    }
+
+    #[test]
+    fn reports_each_leaf_of_a_use_tree() {
+        check(
+            r#"
+mod foo {
+    pub struct Exists;
+}
+use foo::{Exists, Missing, AlsoMissing};
+"#,
+            &[("Missing", false), ("AlsoMissing", false)],
+        );
+    }
+}
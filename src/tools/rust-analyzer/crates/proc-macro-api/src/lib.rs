@@ -79,12 +79,26 @@ impl PartialEq for ProcMacro {
     }
 }
 
+/// The tag the proc-macro server puts into errors for dylibs built by a
+/// different rustc than the server itself. Clients match on it to tell ABI
+/// mismatches apart from other loading failures.
+pub const ABI_MISMATCH_TAG: &str = "mismatched ABI";
+
 #[derive(Clone, Debug)]
 pub struct ServerError {
     pub message: String,
     pub io: Option<Arc<io::Error>>,
 }
 
+impl ServerError {
+    /// Whether the server rejected a proc-macro dylib because it was built by
+    /// a different rustc than the server. The message carries the expected and
+    /// found version strings.
+    pub fn is_abi_mismatch(&self) -> bool {
+        self.message.contains(ABI_MISMATCH_TAG)
+    }
+}
+
 impl fmt::Display for ServerError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.message.fmt(f)?;
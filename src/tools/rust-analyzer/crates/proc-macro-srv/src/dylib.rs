@@ -87,7 +87,11 @@ impl fmt::Display for LoadProcMacroDylibError {
             Self::Io(e) => e.fmt(f),
             Self::AbiMismatch(v) => {
                 use crate::RUSTC_VERSION_STRING;
-                write!(f, "mismatched ABI expected: `{RUSTC_VERSION_STRING}`, got `{v}`")
+                write!(
+                    f,
+                    "{} expected: `{RUSTC_VERSION_STRING}`, got `{v}`",
+                    proc_macro_api::ABI_MISMATCH_TAG
+                )
             }
             Self::LibLoading(e) => e.fmt(f),
         }
@@ -409,7 +409,19 @@ impl WorkspaceBuildScripts {
             _ => {
                 let mut cmd = sysroot.tool(Tool::Cargo);
 
-                cmd.args(["check", "--quiet", "--workspace", "--message-format=json"]);
+                cmd.args(["check", "--quiet", "--message-format=json"]);
+                match &config.build_script_packages {
+                    // Only build the packages the user scoped build scripts to;
+                    // everything else misses out on `OUT_DIR` but is cheap.
+                    Some(packages) => {
+                        for package in packages {
+                            cmd.args(["-p", package]);
+                        }
+                    }
+                    None => {
+                        cmd.arg("--workspace");
+                    }
+                }
                 cmd.args(&config.extra_args);
 
                 cmd.arg("--manifest-path");
@@ -93,6 +93,9 @@ pub struct CargoConfig {
     pub wrap_rustc_in_build_scripts: bool,
     /// The command to run instead of `cargo check` for building build scripts.
     pub run_build_script_command: Option<Vec<String>>,
+    /// Restrict running build scripts to these packages, `None` runs them for
+    /// the whole workspace.
+    pub build_script_packages: Option<Vec<String>>,
     /// Extra args to pass to the cargo command.
     pub extra_args: Vec<String>,
     /// Extra env vars to set when invoking the cargo command
@@ -121,9 +124,16 @@ pub struct PackageData {
     /// Targets provided by the crate (lib, bin, example, test, ...)
     pub targets: Vec<Target>,
     /// Does this package come from the local filesystem (and is editable)?
+    ///
+    /// This includes `path` dependencies living outside the opened workspace
+    /// root; their source roots are loaded into the VFS and watched for
+    /// changes just like workspace members.
     pub is_local: bool,
     /// Whether this package is a member of the workspace
     pub is_member: bool,
+    /// Whether this package is one of the workspace's `default-members`. All
+    /// members are default members when the manifest does not set the key.
+    pub is_default_member: bool,
     /// List of packages this package depends on
     pub dependencies: Vec<PackageDependency>,
     /// Rust edition for this package
@@ -343,6 +353,12 @@ impl CargoWorkspace {
         let mut targets = Arena::default();
 
         let ws_members = &meta.workspace_members;
+        // Cargo only reports `workspace_default_members` since 1.71; when the
+        // field is missing, every member counts as a default member.
+        let ws_default_members = (!cargo_metadata::workspace_default_members_is_missing(
+            &meta.workspace_default_members,
+        ))
+        .then(|| &*meta.workspace_default_members);
 
         meta.packages.sort_by(|a, b| a.id.cmp(&b.id));
         for meta_pkg in meta.packages {
@@ -377,10 +393,17 @@ impl CargoWorkspace {
                     Edition::CURRENT
                 }
             };
+            // Note that `[patch]` replacements are already applied in the package list and
+            // resolve graph reported by `cargo metadata`: a dependency patched with a local
+            // path shows up here with its patched manifest and no `source`, so the patched
+            // sources are what gets indexed and navigated to.
+            //
             // We treat packages without source as "local" packages. That includes all members of
             // the current workspace, as well as any path dependency outside the workspace.
             let is_local = source.is_none();
             let is_member = ws_members.contains(&id);
+            let is_default_member =
+                ws_default_members.map_or(is_member, |members| members.contains(&id));
 
             let manifest = AbsPathBuf::assert(manifest_path);
             let pkg = packages.alloc(PackageData {
@@ -391,6 +414,7 @@ impl CargoWorkspace {
                 targets: Vec::new(),
                 is_local,
                 is_member,
+                is_default_member,
                 edition,
                 repository,
                 authors,
@@ -515,6 +539,14 @@ impl CargoWorkspace {
         None
     }
 
+    /// Returns the workspace's `default-members`, or `None` when they do not
+    /// restrict what `cargo` operates on from the workspace root.
+    pub fn default_members(&self) -> Option<Vec<Package>> {
+        let restricted =
+            self.packages().any(|pkg| self[pkg].is_member && !self[pkg].is_default_member);
+        restricted.then(|| self.packages().filter(|&pkg| self[pkg].is_default_member).collect())
+    }
+
     /// Returns the union of the features of all member crates in this workspace.
     pub fn workspace_features(&self) -> FxHashSet<String> {
         self.packages()
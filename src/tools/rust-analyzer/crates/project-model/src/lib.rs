@@ -224,7 +224,8 @@ impl CfgOverrides {
     }
 }
 
-fn parse_cfg(s: &str) -> Result<cfg::CfgAtom, String> {
+/// Parses a `key` or `key="value"` string into a cfg atom.
+pub fn parse_cfg(s: &str) -> Result<cfg::CfgAtom, String> {
     let res = match s.split_once('=') {
         Some((key, value)) => {
             if !(value.starts_with('"') && value.ends_with('"')) {
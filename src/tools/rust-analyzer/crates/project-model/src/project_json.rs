@@ -122,18 +122,26 @@ impl ProjectJson {
                         None => None,
                     };
 
+                    let env_file = crate_data.env_file.map(absolutize_on_base);
+                    let mut edition = crate_data.edition.into();
+                    let mut cfg = crate_data.cfg;
+                    if let Some(env_file) = &env_file {
+                        apply_env_file(env_file, &mut edition, &mut cfg);
+                    }
+
                     Crate {
                         display_name: crate_data
                             .display_name
                             .as_deref()
                             .map(CrateDisplayName::from_canonical_name),
                         root_module,
-                        edition: crate_data.edition.into(),
+                        edition,
                         version: crate_data.version.as_ref().map(ToString::to_string),
                         deps: crate_data.deps,
-                        cfg: crate_data.cfg,
+                        cfg,
                         target: crate_data.target,
                         env: crate_data.env,
+                        env_file,
                         proc_macro_dylib_path: crate_data
                             .proc_macro_dylib_path
                             .map(absolutize_on_base),
@@ -200,6 +208,49 @@ impl ProjectJson {
     pub fn runnables(&self) -> &[Runnable] {
         &self.runnables
     }
+
+    /// Returns the env-files referenced by the project's crates. Their
+    /// contents feed into the crate graph, so a change to one of them needs
+    /// to trigger a workspace reload.
+    pub fn env_files(&self) -> impl Iterator<Item = &AbsPath> {
+        self.crates.iter().filter_map(|krate| krate.env_file.as_deref())
+    }
+}
+
+/// Merges `cfg` and `edition` overrides from a dotenv-style env-file into a
+/// crate's configuration: `EDITION` replaces the edition, while the atoms of
+/// the comma-separated `CFG` are appended to the cfgs. Unknown keys are
+/// ignored so that unrelated variables can live in the same file.
+fn apply_env_file(path: &AbsPath, edition: &mut Edition, cfg: &mut Vec<CfgAtom>) {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(it) => it,
+        Err(err) => {
+            tracing::warn!("failed to read env-file {path}: {err}");
+            return;
+        }
+    };
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else { continue };
+        match key.trim() {
+            "EDITION" => match value.trim().parse() {
+                Ok(it) => *edition = it,
+                Err(err) => tracing::warn!("invalid edition in env-file {path}: {err}"),
+            },
+            "CFG" => {
+                for atom in value.split(',').map(str::trim).filter(|it| !it.is_empty()) {
+                    match crate::parse_cfg(atom) {
+                        Ok(it) => cfg.push(it),
+                        Err(err) => tracing::warn!("invalid cfg in env-file {path}: {err}"),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 /// A crate points to the root module of a crate and lists the dependencies of the crate. This is
@@ -214,6 +265,7 @@ pub struct Crate {
     pub(crate) cfg: Vec<CfgAtom>,
     pub(crate) target: Option<String>,
     pub(crate) env: FxHashMap<String, String>,
+    pub(crate) env_file: Option<AbsPathBuf>,
     pub(crate) proc_macro_dylib_path: Option<AbsPathBuf>,
     pub(crate) is_workspace_member: bool,
     pub(crate) include: Vec<AbsPathBuf>,
@@ -311,10 +363,37 @@ pub struct ProjectJsonData {
     runnables: Vec<RunnableData>,
 }
 
+impl ProjectJsonData {
+    /// Checks the data for problems that loading would silently gloss over:
+    /// dependencies referencing crates that are not in the `crates` array and
+    /// root modules that don't exist on disk. Returns a human-readable message
+    /// per problem found, paths are resolved relative to `base`.
+    pub fn validate(&self, base: &AbsPath) -> Vec<String> {
+        let mut problems = Vec::new();
+        let n_crates = self.crates.len();
+        for (idx, krate) in self.crates.iter().enumerate() {
+            let root_module = base.absolutize(krate.root_module.clone());
+            if std::fs::metadata(&root_module).is_err() {
+                problems.push(format!("crate {idx}: root module `{root_module}` does not exist"));
+            }
+            for dep in &krate.deps {
+                if dep.krate.0 >= n_crates {
+                    problems.push(format!(
+                        "crate {idx}: dependency `{}` references nonexistent crate {}",
+                        dep.name, dep.krate.0
+                    ));
+                }
+            }
+        }
+        problems
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 struct CrateData {
     display_name: Option<String>,
     root_module: Utf8PathBuf,
+    #[serde(default)]
     edition: EditionData,
     #[serde(default)]
     version: Option<semver::Version>,
@@ -325,6 +404,11 @@ struct CrateData {
     target: Option<String>,
     #[serde(default)]
     env: FxHashMap<String, String>,
+    /// Dotenv-style file to read `cfg` and `edition` overrides from, for
+    /// build systems that communicate the crate configuration through the
+    /// environment rather than the generated JSON.
+    #[serde(default)]
+    env_file: Option<Utf8PathBuf>,
     proc_macro_dylib_path: Option<Utf8PathBuf>,
     is_workspace_member: Option<bool>,
     source: Option<CrateSource>,
@@ -363,9 +447,11 @@ mod cfg_ {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Eq, PartialEq)]
 #[serde(rename = "edition")]
 enum EditionData {
+    // Cargo's default for missing `edition` fields, so ours as well.
+    #[default]
     #[serde(rename = "2015")]
     Edition2015,
     #[serde(rename = "2018")]
@@ -245,6 +245,25 @@ fn rust_project_is_proc_macro_has_proc_macro_dep() {
     crate_data.dependencies.iter().find(|&dep| dep.name.deref() == "proc_macro").unwrap();
 }
 
+#[test]
+fn rust_project_crate_edition_defaults_to_2015() {
+    // Missing `edition` fields fall back to 2015, same as cargo.
+    let data = serde_json::from_value::<ProjectJsonData>(serde_json::json!({
+        "sysroot_src": null,
+        "crates": [
+            {
+                "display_name": "example",
+                "root_module": "src/lib.rs",
+                "deps": []
+            }
+        ]
+    }))
+    .unwrap();
+    let project = rooted_project_json(data);
+    let (_, krate) = project.crates().next().unwrap();
+    assert_eq!(krate.edition, span::Edition::Edition2015);
+}
+
 #[test]
 fn crate_graph_dedup_identical() {
     let (mut crate_graph, proc_macros) =
@@ -531,6 +531,17 @@ impl ProjectWorkspace {
         }
     }
 
+    /// The env-files referenced by this workspace's crates, if it is a
+    /// `rust-project.json` workspace.
+    pub fn env_files(&self) -> impl Iterator<Item = &AbsPath> + '_ {
+        match &self.kind {
+            ProjectWorkspaceKind::Json(project) => Some(project.env_files()),
+            _ => None,
+        }
+        .into_iter()
+        .flatten()
+    }
+
     pub fn find_sysroot_proc_macro_srv(&self) -> anyhow::Result<AbsPathBuf> {
         self.sysroot.discover_proc_macro_srv()
     }
@@ -1076,6 +1087,11 @@ fn cargo_to_crate_graph(
 
     // Now add a dep edge from all targets of upstream to the lib
     // target of downstream.
+    //
+    // Note that dev-dependency edges are added to every non-build-script target
+    // here, not just to tests, examples and benches. This does not match what
+    // cargo compiles, but libraries are analyzed with `cfg(test)` enabled, so
+    // their test modules need the dev-dependencies to resolve.
     for pkg in cargo.packages() {
         for dep in &cargo[pkg].dependencies {
             let Some(&to) = pkg_to_lib_crate.get(&dep.pkg) else { continue };
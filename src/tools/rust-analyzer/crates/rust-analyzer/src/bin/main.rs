@@ -10,10 +10,10 @@ extern crate rustc_driver as _;
 
 mod rustc_wrapper;
 
-use std::{env, fs, path::PathBuf, process::ExitCode, sync::Arc};
+use std::{env, fs, net::TcpListener, path::PathBuf, process::ExitCode, sync::Arc};
 
 use anyhow::Context;
-use lsp_server::Connection;
+use lsp_server::{Connection, IoThreads};
 use paths::Utf8PathBuf;
 use rust_analyzer::{
     cli::flags,
@@ -74,7 +74,7 @@ fn actual_main() -> anyhow::Result<ExitCode> {
             with_extra_thread(
                 "LspServer",
                 stdx::thread::ThreadIntent::LatencySensitive,
-                run_server,
+                move || run_server(cmd.listen),
             )?;
         }
         flags::RustAnalyzerCmd::Parse(cmd) => cmd.run()?,
@@ -82,6 +82,7 @@ fn actual_main() -> anyhow::Result<ExitCode> {
         flags::RustAnalyzerCmd::Highlight(cmd) => cmd.run()?,
         flags::RustAnalyzerCmd::AnalysisStats(cmd) => cmd.run(verbosity)?,
         flags::RustAnalyzerCmd::Diagnostics(cmd) => cmd.run()?,
+        flags::RustAnalyzerCmd::Index(cmd) => cmd.run()?,
         flags::RustAnalyzerCmd::Ssr(cmd) => cmd.run()?,
         flags::RustAnalyzerCmd::Search(cmd) => cmd.run()?,
         flags::RustAnalyzerCmd::Lsif(cmd) => cmd.run()?,
@@ -162,11 +163,34 @@ fn with_extra_thread(
     Ok(())
 }
 
-fn run_server() -> anyhow::Result<()> {
+fn run_server(listen_address: Option<String>) -> anyhow::Result<()> {
     tracing::info!("server version {} will start", rust_analyzer::version());
 
-    let (connection, io_threads) = Connection::stdio();
+    match listen_address {
+        None => {
+            let (connection, io_threads) = Connection::stdio();
+            run_session(connection, io_threads)
+        }
+        Some(address) => {
+            let listener = TcpListener::bind(&address)
+                .with_context(|| format!("failed to listen on {address}"))?;
+            loop {
+                tracing::info!("waiting for a TCP connection on {address}");
+                let (connection, io_threads) = Connection::accept(&listener)?;
+                // A client dropping the connection mid-session must not take the
+                // server down: the session's state is torn down when `main_loop`
+                // returns, so just log the error and wait for the next client. A
+                // clean shutdown exits as usual.
+                match run_session(connection, io_threads) {
+                    Ok(()) => break Ok(()),
+                    Err(e) => tracing::error!("session ended with an error: {e}"),
+                }
+            }
+        }
+    }
+}
 
+fn run_session(connection: Connection, io_threads: IoThreads) -> anyhow::Result<()> {
     let (initialize_id, initialize_params) = match connection.initialize_start() {
         Ok(it) => it,
         Err(e) => {
@@ -6,6 +6,7 @@ mod analysis_stats;
 mod diagnostics;
 pub mod flags;
 mod highlight;
+mod index;
 mod lsif;
 mod parse;
 mod run_tests;
@@ -441,6 +441,7 @@ impl flags::AnalysisStats {
                                 prefer_no_std: false,
                                 prefer_prelude: true,
                                 prefer_absolute: false,
+                                prefer_reexports: false,
                             },
                         )
                         .unwrap();
@@ -981,6 +982,7 @@ impl flags::AnalysisStats {
                     enabled: true,
                     proc_macros_enabled: true,
                     proc_attr_macros_enabled: true,
+                    proc_macro_diagnostic_severity: ide::Severity::WeakWarning,
                     disable_experimental: false,
                     disabled: Default::default(),
                     expr_fill_default: Default::default(),
@@ -995,7 +997,9 @@ impl flags::AnalysisStats {
                     prefer_no_std: false,
                     prefer_prelude: true,
                     prefer_absolute: false,
+                    prefer_reexports: false,
                     style_lints: false,
+                    unused_pub_items: false,
                     term_search_fuel: 400,
                     term_search_borrowck: true,
                 },
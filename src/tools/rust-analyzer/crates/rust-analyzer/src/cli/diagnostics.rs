@@ -5,7 +5,10 @@ use project_model::{CargoConfig, RustLibSource};
 use rustc_hash::FxHashSet;
 
 use hir::{db::HirDatabase, Crate, HirFileIdExt, Module};
-use ide::{AnalysisHost, AssistResolveStrategy, Diagnostic, DiagnosticsConfig, Severity};
+use ide::{
+    AnalysisHost, AssistResolveStrategy, Diagnostic, DiagnosticCode, DiagnosticsConfig, LineCol,
+    Severity,
+};
 use ide_db::{base_db::SourceRootDatabase, LineIndexDatabase};
 use load_cargo::{load_workspace_at, LoadCargoConfig, ProcMacroServerChoice};
 
@@ -45,6 +48,7 @@ impl flags::Diagnostics {
 
         let mut found_error = false;
         let mut visited_files = FxHashSet::default();
+        let mut sarif_results = Vec::new();
 
         let work = all_modules(db).into_iter().filter(|module| {
             let file_id = module.definition_source_file_id(db).original_file(db);
@@ -79,12 +83,42 @@ impl flags::Diagnostics {
                     let start = line_index.line_col(range.range.start());
                     let end = line_index.line_col(range.range.end());
                     println!("{severity:?} {code:?} from {start:?} to {end:?}: {message}");
+
+                    if self.sarif.is_some() {
+                        sarif_results.push(sarif_result(
+                            &code,
+                            &message,
+                            severity,
+                            &_vfs.file_path(range.file_id.into()).to_string(),
+                            start,
+                            end,
+                        ));
+                    }
                 }
 
                 visited_files.insert(file_id);
             }
         }
 
+        if let Some(path) = &self.sarif {
+            let report = serde_json::json!({
+                "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+                "version": "2.1.0",
+                "runs": [{
+                    "tool": {
+                        "driver": {
+                            "name": "rust-analyzer",
+                            "version": crate::version::version().to_string(),
+                            "informationUri": "https://rust-analyzer.github.io/",
+                        }
+                    },
+                    "results": sarif_results,
+                }],
+            });
+            std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+            println!("wrote SARIF report to {}", path.display());
+        }
+
         println!();
         println!("diagnostic scan complete");
 
@@ -97,6 +131,39 @@ impl flags::Diagnostics {
     }
 }
 
+fn sarif_result(
+    code: &DiagnosticCode,
+    message: &str,
+    severity: Severity,
+    uri: &str,
+    start: LineCol,
+    end: LineCol,
+) -> serde_json::Value {
+    let level = match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::WeakWarning => "note",
+        Severity::Allow => "none",
+    };
+    serde_json::json!({
+        "ruleId": code.as_str(),
+        "level": level,
+        "message": { "text": message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": uri },
+                // SARIF lines and columns are 1-based
+                "region": {
+                    "startLine": start.line + 1,
+                    "startColumn": start.col + 1,
+                    "endLine": end.line + 1,
+                    "endColumn": end.col + 1,
+                },
+            },
+        }],
+    })
+}
+
 fn all_modules(db: &dyn HirDatabase) -> Vec<Module> {
     let mut worklist: Vec<_> =
         Crate::all(db).into_iter().map(|krate| krate.root_module()).collect();
@@ -34,6 +34,11 @@ xflags::xflags! {
 
             /// Dump a LSP config JSON schema.
             optional --print-config-schema
+
+            /// Listen for a TCP connection on the given address, e.g. `127.0.0.1:27631`,
+            /// instead of using stdio. One client is served at a time; when it
+            /// disconnects, the server waits for the next connection.
+            optional --listen addr: String
         }
 
         /// Parse stdin.
@@ -122,6 +127,23 @@ xflags::xflags! {
             optional --disable-proc-macros
             /// Run a custom proc-macro-srv binary.
             optional --proc-macro-srv path: PathBuf
+            /// Additionally write all diagnostics to the given path as a SARIF 2.1.0 report.
+            optional --sarif path: PathBuf
+        }
+
+        /// Load and fully index the project without starting an LSP server, then exit.
+        cmd index {
+            /// Directory with Cargo.toml.
+            required path: PathBuf
+
+            /// Don't run build scripts or load `OUT_DIR` values by running `cargo check` before analysis.
+            optional --disable-build-scripts
+            /// Don't use expand proc macros.
+            optional --disable-proc-macros
+            /// Run a custom proc-macro-srv binary.
+            optional --proc-macro-srv path: PathBuf
+            /// Dump diagnostics for all workspace files to stdout as JSON, one object per line.
+            optional --diagnostics
         }
 
         cmd ssr {
@@ -175,6 +197,7 @@ pub enum RustAnalyzerCmd {
     RunTests(RunTests),
     RustcTests(RustcTests),
     Diagnostics(Diagnostics),
+    Index(Index),
     Ssr(Ssr),
     Search(Search),
     Lsif(Lsif),
@@ -185,6 +208,7 @@ pub enum RustAnalyzerCmd {
 pub struct LspServer {
     pub version: bool,
     pub print_config_schema: bool,
+    pub listen: Option<String>,
 }
 
 #[derive(Debug)]
@@ -242,6 +266,17 @@ pub struct Diagnostics {
     pub disable_build_scripts: bool,
     pub disable_proc_macros: bool,
     pub proc_macro_srv: Option<PathBuf>,
+    pub sarif: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub struct Index {
+    pub path: PathBuf,
+
+    pub disable_build_scripts: bool,
+    pub disable_proc_macros: bool,
+    pub proc_macro_srv: Option<PathBuf>,
+    pub diagnostics: bool,
 }
 
 #[derive(Debug)]
@@ -0,0 +1,96 @@
+//! Load and fully index a project without an LSP connection, then exit. Lets CI warm up caches
+//! and, with `--diagnostics`, use rust-analyzer as a batch linter with machine-readable output.
+
+use hir::{db::HirDatabase, Crate, HirFileIdExt, Module};
+use ide::{AnalysisHost, AssistResolveStrategy, Diagnostic, DiagnosticsConfig, Severity};
+use ide_db::{base_db::SourceRootDatabase, LineIndexDatabase};
+use load_cargo::{load_workspace_at, LoadCargoConfig, ProcMacroServerChoice};
+use project_model::{CargoConfig, RustLibSource};
+use rustc_hash::FxHashSet;
+
+use crate::cli::flags;
+
+impl flags::Index {
+    pub fn run(self) -> anyhow::Result<()> {
+        let cargo_config =
+            CargoConfig { sysroot: Some(RustLibSource::Discover), ..Default::default() };
+        let with_proc_macro_server = if let Some(p) = &self.proc_macro_srv {
+            let path = vfs::AbsPathBuf::assert_utf8(std::env::current_dir()?.join(p));
+            ProcMacroServerChoice::Explicit(path)
+        } else {
+            ProcMacroServerChoice::Sysroot
+        };
+        let load_cargo_config = LoadCargoConfig {
+            load_out_dirs_from_check: !self.disable_build_scripts,
+            with_proc_macro_server,
+            // This is the whole point of the command: index everything eagerly.
+            prefill_caches: true,
+        };
+        let (db, vfs, _proc_macro) =
+            load_workspace_at(&self.path, &cargo_config, &load_cargo_config, &|_| {})?;
+        let host = AnalysisHost::with_database(db);
+        let db = host.raw_database();
+        let analysis = host.analysis();
+
+        if !self.diagnostics {
+            return Ok(());
+        }
+
+        let mut found_error = false;
+        let mut visited_files = FxHashSet::default();
+        for module in all_modules(db) {
+            let file_id = module.definition_source_file_id(db).original_file(db);
+            let source_root = db.file_source_root(file_id.into());
+            if db.source_root(source_root).is_library || !visited_files.insert(file_id) {
+                continue;
+            }
+            let file_path = vfs.file_path(file_id.into());
+            for diagnostic in analysis.full_diagnostics(
+                &DiagnosticsConfig::test_sample(),
+                AssistResolveStrategy::None,
+                file_id.into(),
+            )? {
+                if matches!(diagnostic.severity, Severity::Error) {
+                    found_error = true;
+                }
+
+                let Diagnostic { code, message, range, severity, .. } = diagnostic;
+                let line_index = db.line_index(range.file_id);
+                let start = line_index.line_col(range.range.start());
+                let end = line_index.line_col(range.range.end());
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "file": file_path.to_string(),
+                        "code": code.as_str(),
+                        "severity": format!("{severity:?}"),
+                        "message": message,
+                        "range": {
+                            "start": { "line": start.line, "character": start.col },
+                            "end": { "line": end.line, "character": end.col },
+                        },
+                    })
+                );
+            }
+        }
+
+        if found_error {
+            anyhow::bail!("diagnostic error detected")
+        }
+
+        Ok(())
+    }
+}
+
+fn all_modules(db: &dyn HirDatabase) -> Vec<Module> {
+    let mut worklist: Vec<_> =
+        Crate::all(db).into_iter().map(|krate| krate.root_module()).collect();
+    let mut modules = Vec::new();
+
+    while let Some(module) = worklist.pop() {
+        modules.push(module);
+        worklist.extend(module.children(db));
+    }
+
+    modules
+}
@@ -3,16 +3,17 @@
 //! Of particular interest is the `feature_flags` hash map: while other fields
 //! configure the server itself, feature flags are passed into analysis, and
 //! tweak things like automatic insertion of `()` in completions.
-use std::{fmt, iter, ops::Not, sync::OnceLock};
+use std::{fmt, iter, num::NonZeroUsize, ops::Not, sync::OnceLock, time::Duration};
 
 use cfg::{CfgAtom, CfgDiff};
 use dirs::config_dir;
 use hir::Symbol;
 use ide::{
-    AssistConfig, CallableSnippets, CompletionConfig, DiagnosticsConfig, ExprFillDefaultMode,
-    GenericParameterHints, HighlightConfig, HighlightRelatedConfig, HoverConfig, HoverDocFormat,
-    InlayFieldsToResolve, InlayHintsConfig, JoinLinesConfig, MemoryLayoutHoverConfig,
-    MemoryLayoutHoverRenderKind, Snippet, SnippetScope, SourceRootId,
+    AssistConfig, CallableSnippets, CompletionConfig, CompletionScope, CompletionScoringWeights,
+    DiagnosticsConfig, ExprFillDefaultMode, GenericParameterHints, HighlightConfig,
+    HighlightRelatedConfig, HoverActionsConfig, HoverConfig, HoverDocFormat, InlayFieldsToResolve,
+    InlayHintsConfig, JoinLinesConfig, MemoryLayoutHoverConfig, MemoryLayoutHoverRenderKind,
+    Severity, Snippet, SnippetScope, SourceRootId, TodoCommentsConfig,
 };
 use ide_db::{
     imports::insert_use::{ImportGranularity, InsertUseConfig, PrefixKind},
@@ -34,7 +35,7 @@ use triomphe::Arc;
 use vfs::{AbsPath, AbsPathBuf, VfsPath};
 
 use crate::{
-    diagnostics::DiagnosticsMapConfig,
+    diagnostics::{ChildSpans, DiagnosticsMapConfig},
     flycheck::{CargoOptions, FlycheckConfig},
     lsp::capabilities::ClientCapabilities,
     lsp_ext::{WorkspaceSymbolSearchKind, WorkspaceSymbolSearchScope},
@@ -112,6 +113,11 @@ config_data! {
         /// ```
         /// .
         cargo_buildScripts_overrideCommand: Option<Vec<String>> = None,
+        /// Only run build scripts for the listed packages, skipping the rest of
+        /// the workspace. `OUT_DIR` and other build script outputs are then
+        /// only available for the listed packages. The default `null` runs
+        /// build scripts for all workspace packages.
+        cargo_buildScripts_packages: Option<Vec<String>> = None,
         /// Rerun proc-macros building/build-scripts running when proc-macro
         /// or build-script sources change and are saved.
         cargo_buildScripts_rebuildOnSave: bool = true,
@@ -139,7 +145,9 @@ config_data! {
         /// Relative path to the sysroot, or "discover" to try to automatically find it via
         /// "rustc --print sysroot".
         ///
-        /// Unsetting this disables sysroot loading.
+        /// Unsetting this or setting it to "none" disables sysroot loading, leaving `core` and
+        /// `std` unresolved. This is what you want for bare-metal crates shipping their own
+        /// `core`.
         ///
         /// This option does not take effect until rust-analyzer is restarted.
         cargo_sysroot: Option<String>    = Some("discover".to_owned()),
@@ -166,8 +174,26 @@ config_data! {
         /// Check all targets and tests (`--all-targets`). Defaults to
         /// `#rust-analyzer.cargo.allTargets#`.
         check_allTargets | checkOnSave_allTargets: Option<bool>          = None,
+        /// Check `#[bench]` targets (`--benches`). No effect if
+        /// `#rust-analyzer.check.allTargets#` is enabled.
+        check_benches: bool = false,
         /// Cargo command to use for `cargo check`.
+        ///
+        /// Cargo aliases from `.cargo/config.toml` work here as well, so a team's
+        /// canonical `cargo lint` alias can back the editor checks. The alias is
+        /// resolved by cargo itself and the usual flycheck arguments (package
+        /// selection, `--message-format`, ...) are appended after it. Diagnostics
+        /// are attributed to the underlying tool where detectable, e.g. lints with
+        /// a `clippy::` scoped code get `clippy` as their source.
         check_command | checkOnSave_command: String                      = "check".to_owned(),
+        /// Whether a workspace-wide check respects the workspace's `default-members`,
+        /// like `cargo check` run from the workspace root does. When disabled, all
+        /// members are checked via `--workspace`. No effect if the workspace does not
+        /// declare `default-members`.
+        check_defaultMembers: bool = true,
+        /// Check example targets (`--examples`). No effect if
+        /// `#rust-analyzer.check.allTargets#` is enabled.
+        check_examples: bool = false,
         /// Extra arguments for `cargo check`.
         check_extraArgs | checkOnSave_extraArgs: Vec<String>             = vec![],
         /// Extra environment variables that will be set when running `cargo check`.
@@ -196,9 +222,22 @@ config_data! {
         /// This config only has an effect when `#rust-analyzer.check.overrideCommand#`
         /// is set.
         check_invocationStrategy | checkOnSave_invocationStrategy: InvocationStrategy = InvocationStrategy::PerWorkspace,
+        /// Whether to pass `--keep-going` to `cargo check` so that diagnostics
+        /// of crates that do not depend on a failing crate are still produced.
+        ///
+        /// The flag is only passed to toolchains that support it (cargo 1.74
+        /// and newer) and is silently dropped otherwise.
+        check_keepGoing | checkOnSave_keepGoing: bool = false,
         /// Whether to pass `--no-default-features` to Cargo. Defaults to
         /// `#rust-analyzer.cargo.noDefaultFeatures#`.
         check_noDefaultFeatures | checkOnSave_noDefaultFeatures: Option<bool>         = None,
+        /// Whether to restrict checks on save to the packages containing the saved files
+        /// and their reverse dependencies, by passing `-p` for each instead of
+        /// `--workspace`. The packages accumulate until a check run completes, so saves
+        /// racing with an in-flight check are not lost. Checks not triggered by a save
+        /// still cover the whole workspace. Has no effect if `#rust-analyzer.check.workspace#`
+        /// is `false`.
+        check_onlyChangedPackages: bool = false,
         /// Override the command rust-analyzer uses instead of `cargo check` for
         /// diagnostics on save. The command is required to output json and
         /// should therefore include `--message-format=json` or a similar option
@@ -227,6 +266,16 @@ config_data! {
         /// ```
         /// .
         check_overrideCommand | checkOnSave_overrideCommand: Option<Vec<String>>             = None,
+        /// Optional path to a rust-analyzer specific target directory for the check command.
+        /// Defaults to `#rust-analyzer.cargo.targetDir#`.
+        ///
+        /// Keeping editor checks out of the directory used by CLI builds prevents
+        /// the two from invalidating each other's caches, at the cost of the disk
+        /// space for a second set of build artifacts.
+        ///
+        /// A `CARGO_TARGET_DIR` set via `#rust-analyzer.check.extraEnv#` or
+        /// `#rust-analyzer.cargo.extraEnv#` takes precedence over this setting.
+        check_targetDir | checkOnSave_targetDir: Option<TargetDirectory> = None,
         /// Check for specific targets. Defaults to `#rust-analyzer.cargo.target#` if empty.
         ///
         /// Can be a single target, e.g. `"x86_64-unknown-linux-gnu"` or a list of targets, e.g.
@@ -234,10 +283,27 @@ config_data! {
         ///
         /// Aliased as `"checkOnSave.targets"`.
         check_targets | checkOnSave_targets | checkOnSave_target: Option<CheckOnSaveTargets> = None,
+        /// Check test targets (`--tests`), so test-only compile errors show up
+        /// on save. No effect if `#rust-analyzer.check.allTargets#` is enabled.
+        check_tests: bool = false,
         /// Whether `--workspace` should be passed to `cargo check`.
         /// If false, `-p <package>` will be passed instead.
         check_workspace: bool = true,
 
+        /// List of call path patterns that produce colors, e.g. `"Color::rgb"` or `"rgba!"`.
+        /// When non-empty, the server answers `textDocument/documentColor` with swatches for
+        /// matching calls and for hex color strings like `"#RRGGBB"`. A pattern matches call
+        /// paths that equal it or end with `::` followed by it; patterns ending in `!` match
+        /// macro calls instead. Arguments are read as `u8` (0-255) integer or `f32` (0.0-1.0)
+        /// float literals, with an optional fourth alpha component.
+        colors_patterns: Vec<String> = vec![],
+
+        /// How the child spans of a cargo diagnostic (notes and helps with their own
+        /// spans) are rendered. `hints` attaches them to the primary diagnostic as
+        /// `relatedInformation` and additionally publishes them as separate hint-level
+        /// diagnostics, `related` only attaches them, and `ignore` drops them entirely.
+        /// Children carrying a quickfix are kept as separate diagnostics in all modes.
+        diagnostics_childSpans: ChildSpansDef = ChildSpansDef::Hints,
         /// List of rust-analyzer diagnostics to disable.
         diagnostics_disabled: FxHashSet<String> = FxHashSet::default(),
         /// Whether to show native rust-analyzer diagnostics.
@@ -245,11 +311,28 @@ config_data! {
         /// Whether to show experimental rust-analyzer diagnostics that might
         /// have more false positives than usual.
         diagnostics_experimental_enable: bool    = false,
+        /// Map of regular expressions to replacement texts that is applied to
+        /// diagnostic messages before they are published, e.g. to link lint
+        /// names to an internal lint database. Capture groups can be referenced
+        /// with `$1` etc. Ranges and diagnostic codes are left untouched.
+        diagnostics_messageReplacements: FxHashMap<String, String> = FxHashMap::default(),
+        /// When to compute native diagnostics for the first time. `eager` refreshes them as
+        /// soon as the workspace has loaded, `deferred` waits until a document is opened or
+        /// modified afterwards, which gets large workspaces interactive sooner. Once
+        /// triggered, diagnostics for all open files are refreshed as usual.
+        diagnostics_onStartup: DiagnosticsOnStartupDef = DiagnosticsOnStartupDef::Eager,
         /// Map of prefixes to be substituted when parsing diagnostic file paths.
         /// This should be the reverse mapping of what is passed to `rustc` as `--remap-path-prefix`.
         diagnostics_remapPrefix: FxHashMap<String, String> = FxHashMap::default(),
         /// Whether to run additional style lints.
         diagnostics_styleLints_enable: bool =    false,
+        /// Whether to show a diagnostic for `pub` items that are not used anywhere
+        /// in the workspace. Items reachable through the public API of a library
+        /// crate are not reported.
+        ///
+        /// This searches the reverse references of every `pub` item and is therefore
+        /// expensive in large workspaces, which is why it is off by default.
+        diagnostics_unusedPubItems_enable: bool = false,
         /// List of warnings that should be displayed with hint severity.
         ///
         /// The warnings will be indicated by faded text or three dots in code
@@ -261,10 +344,24 @@ config_data! {
         /// and a blue icon in the `Problems Panel`.
         diagnostics_warningsAsInfo: Vec<String> = vec![],
 
+        /// The encoding used to decode source files that are not valid UTF-8.
+        ///
+        /// With the default `utf8`, such files are ignored. With `latin1` they
+        /// are decoded as ISO-8859-1 instead. The decoded text is processed as
+        /// UTF-8 internally and any returned edits are UTF-8; the client is
+        /// responsible for writing the file back in its original encoding.
+        files_encoding: FilesEncodingDef = FilesEncodingDef::Utf8,
         /// These directories will be ignored by rust-analyzer. They are
         /// relative to the workspace root, and globs are not supported. You may
         /// also need to add the folders to Code's `files.watcherExclude`.
         files_excludeDirs: Vec<Utf8PathBuf> = vec![],
+        /// Soft cap on the number of documents the client may keep open at the
+        /// same time.
+        ///
+        /// When exceeded, rust-analyzer keeps working but logs a warning for
+        /// each newly opened document; this is a safety valve against
+        /// misbehaving clients. Set to `null` to disable the warning.
+        files_openDocumentsLimit: Option<NonZeroUsize> = NonZeroUsize::new(2000),
 
 
         /// Disable project auto-discovery in favor of explicitly specified set
@@ -277,6 +374,10 @@ config_data! {
 
         /// Number of syntax trees rust-analyzer keeps in memory. Defaults to 128.
         lru_capacity: Option<u16>                 = None,
+        /// Number of macro expansions rust-analyzer keeps in memory. Defaults to four
+        /// times `#rust-analyzer.lru.capacity#`. A dedicated size keeps macro-heavy
+        /// files responsive when navigation churns through the syntax tree cache.
+        lru_macroExpansionCapacity: Option<u16> = None,
         /// Sets the LRU capacity of the specified queries.
         lru_query_capacities: FxHashMap<Box<str>, u16> = FxHashMap::default(),
 
@@ -284,6 +385,11 @@ config_data! {
         ///
         /// This config takes a map of crate names with the exported proc-macro names to ignore as values.
         procMacro_ignored: FxHashMap<Box<str>, Box<[Box<str>]>>          = FxHashMap::default(),
+        /// Timeout in milliseconds after which an in-flight request is answered with an error
+        /// and its analysis is cancelled, instead of leaving the client waiting indefinitely.
+        /// `null` disables the timeout. Only LSP requests are affected; long-running background
+        /// operations such as flycheck or cache priming never time out.
+        requestTimeout: Option<u64> = None,
 
         /// Command to be executed instead of 'cargo' for runnables.
         runnables_command: Option<String> = None,
@@ -321,6 +427,10 @@ config_data! {
         /// `textDocument/rangeFormatting` request. The rustfmt option is unstable and only
         /// available on a nightly build.
         rustfmt_rangeFormatting_enable: bool = false,
+        /// The rustup toolchain to invoke rustfmt with, for example `"nightly"` for projects
+        /// that rely on unstable rustfmt features. Sets `RUSTUP_TOOLCHAIN` for the spawned
+        /// process. This option is ignored when `rustfmt.overrideCommand` is set.
+        rustfmt_toolchain: Option<String> = None,
 
         /// Enables automatic discovery of projects using [`DiscoverWorkspaceConfig::command`].
         ///
@@ -422,6 +532,9 @@ config_data! {
 config_data! {
     /// Local configurations can be defined per `SourceRoot`. This almost always corresponds to a `Crate`.
     local: struct LocalDefaultConfigData <- LocalConfigInput ->  {
+        /// List of assist ids that should never be offered as code actions.
+        /// The full list of ids can be queried with the `rust-analyzer/listAssists` request.
+        assist_disabled: Vec<String> = vec![],
         /// Whether to insert #[must_use] when generating `as_` methods
         /// for enum variants.
         assist_emitMustUse: bool               = false,
@@ -444,6 +557,9 @@ config_data! {
         imports_preferNoStd | imports_prefer_no_std: bool = false,
          /// Whether to prefer import paths containing a `prelude` module.
         imports_preferPrelude: bool                       = false,
+        /// Whether to prefer import paths going through a re-export (glob or explicit) over
+        /// the path through the item's defining module.
+        imports_preferReexports: bool                     = false,
         /// The path structure for newly inserted paths to use.
         imports_prefix: ImportPrefixDef               = ImportPrefixDef::Plain,
         /// Whether to prefix external (including std, core) crate imports with `::`. e.g. "use ::std::io::Read;".
@@ -458,6 +574,10 @@ config_data! {
         /// Toggles the additional completions that automatically add imports when completed.
         /// Note that your client must specify the `additionalTextEdits` LSP client capability to truly have this feature enabled.
         completion_autoimport_enable: bool       = true,
+        /// A list of module paths that should be excluded from the completions that automatically
+        /// add imports. Any item whose import path starts with one of these prefixes is not
+        /// proposed, which is useful for hiding internal or re-exporting crates like `foo_macros`.
+        completion_autoimport_exclude: Vec<String> = vec![],
         /// Toggles the additional completions that automatically show method calls and field accesses
         /// with `self` prefixed to them when inside a method.
         completion_autoself_enable: bool        = true,
@@ -465,12 +585,37 @@ config_data! {
         completion_callable_snippets: CallableCompletionDef  = CallableCompletionDef::FillArguments,
         /// Whether to show full function/method signatures in completion docs.
         completion_fullFunctionSignatures_enable: bool = false,
+        /// Whether to render type and signature information into the `labelDetails` structure
+        /// introduced in LSP 3.17 rather than appending it to the completion label. Only takes
+        /// effect when the client declares support for label details.
+        completion_labelDetails_enable: bool = true,
         /// Maximum number of completions to return. If `None`, the limit is infinite.
         completion_limit: Option<usize> = None,
         /// Whether to show postfix snippets like `dbg`, `if`, `not`, etc.
         completion_postfix_enable: bool         = true,
         /// Enables completions of private items and fields that are defined in the current workspace even if they are not visible at the current position.
         completion_privateEditable_enable: bool = false,
+        /// Which crates the completions that automatically add imports are searched in.
+        ///
+        /// Restricting the scope to the current crate and the sysroot crates trades completeness
+        /// for latency on workspaces with large dependency trees. Explicitly invoked completions
+        /// always search the whole workspace.
+        completion_scope: CompletionScopeDef = CompletionScopeDef::Workspace,
+        /// Additional ranking weight for struct and union fields. The scoring weights are
+        /// layered on top of the built-in completion ranking; the default of `0` for all of
+        /// them leaves that ranking untouched.
+        completion_scoring_weights_field: usize = 0,
+        /// Additional ranking weight for completions that add an import.
+        completion_scoring_weights_import: usize = 0,
+        /// Additional ranking weight for locals.
+        completion_scoring_weights_local: usize = 0,
+        /// Additional ranking weight for methods.
+        completion_scoring_weights_method: usize = 0,
+        /// Additional ranking weight for completions whose name matches what was typed exactly.
+        completion_scoring_weights_prefixMatch: usize = 0,
+        /// Additional ranking weight for completions that were recently accepted, where
+        /// resolving a completion counts as accepting it.
+        completion_scoring_weights_recency: usize = 0,
         /// Custom completion snippets.
         completion_snippets_custom: FxHashMap<String, SnippetDef> = serde_json::from_str(r#"{
             "Arc::new": {
@@ -518,9 +663,20 @@ config_data! {
         /// Term search fuel in "units of work" for autocompletion (Defaults to 1000).
         completion_termSearch_fuel: usize = 1000,
 
+        /// How many worker threads to spawn for computing native diagnostics.
+        /// Diagnostics run on a dedicated, lower-priority thread pool so that a
+        /// workspace-wide recompute never starves interactive requests. The
+        /// default `null` means a quarter of `#rust-analyzer.numThreads#`.
+        diagnostics_numThreads: Option<NumThreads> = None,
+
         /// Controls file watching implementation.
         files_watcher: FilesWatcherDef = FilesWatcherDef::Client,
 
+        /// Whether to return folding ranges for multi-line comment blocks. When
+        /// disabled, only syntactic constructs (braces, use groups, regions, ...)
+        /// are folded.
+        foldingRanges_comments_enable: bool = true,
+
         /// Enables highlighting of related references while the cursor is on `break`, `loop`, `while`, or `for` keywords.
         highlightRelated_breakPoints_enable: bool = true,
         /// Enables highlighting of all captures of a closure while the cursor is on the `|` or move keyword of a closure.
@@ -676,11 +832,20 @@ config_data! {
 
         /// Expand attribute macros. Requires `#rust-analyzer.procMacro.enable#` to be set.
         procMacro_attributes_enable: bool = true,
+        /// The severity to use for diagnostics about proc-macros that could not be expanded,
+        /// e.g. when intentionally running without a proc-macro server. Use `"off"` to disable
+        /// these diagnostics entirely.
+        procMacro_diagnosticSeverity: ProcMacroDiagnosticSeverityDef = ProcMacroDiagnosticSeverityDef::Hint,
         /// Enable support for procedural macros, implies `#rust-analyzer.cargo.buildScripts.enable#`.
         procMacro_enable: bool                     = true,
         /// Internal config, path to proc-macro server executable.
         procMacro_server: Option<Utf8PathBuf>          = None,
 
+        /// Upper bound for the number of references `rust-analyzer/referenceCount` counts.
+        /// Counting stops at the limit and the response is marked as capped, which keeps the
+        /// request cheap for extremely popular items. If `None`, everything is counted.
+        references_countLimit: Option<usize> = None,
+
         /// Exclude imports from find-all-references.
         references_excludeImports: bool = false,
 
@@ -729,9 +894,28 @@ config_data! {
         /// Show documentation.
         signatureInfo_documentation_enable: bool                       = true,
 
+        /// Limits the number of results returned by a `rust-analyzer/todoComments` scan.
+        todoComments_limit: usize = 500,
+        /// Comment markers collected by the `rust-analyzer/todoComments` request.
+        /// Markers match whole words in comments, case-sensitively.
+        todoComments_markers: Vec<String> = vec!["TODO".to_owned(), "FIXME".to_owned()],
+        /// Whether `rust-analyzer/todoComments` also scans dependency and sysroot
+        /// sources rather than only workspace crates.
+        todoComments_scanDependencies: bool = false,
+
         /// Whether to insert closing angle brackets when typing an opening angle bracket of a generic argument list.
         typing_autoClosingAngleBrackets_enable: bool = false,
 
+        /// Whether workspace `rust-analyzer.toml` files take precedence over the
+        /// client's own settings for the options they define.
+        ///
+        /// The merge precedence is, from strongest to weakest:
+        /// workspace `rust-analyzer.toml`, client settings, user config file,
+        /// built-in defaults. Disabling this option swaps the first two, so that
+        /// checked-in project files only provide defaults that each developer's
+        /// editor settings may override.
+        workspace_ratomlTakesPrecedence: bool = true,
+
         /// Workspace symbol search kind.
         workspace_symbol_search_kind: WorkspaceSymbolSearchKindDef = WorkspaceSymbolSearchKindDef::OnlyTypes,
         /// Limits the number of items returned from a workspace symbol search (Defaults to 128).
@@ -1202,49 +1386,25 @@ impl LensConfig {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct HoverActionsConfig {
-    pub implementations: bool,
-    pub references: bool,
-    pub run: bool,
-    pub debug: bool,
-    pub goto_type_def: bool,
-}
-
-impl HoverActionsConfig {
-    pub const NO_ACTIONS: Self = Self {
-        implementations: false,
-        references: false,
-        run: false,
-        debug: false,
-        goto_type_def: false,
-    };
-
-    pub fn any(&self) -> bool {
-        self.implementations || self.references || self.runnable() || self.goto_type_def
-    }
-
-    pub fn none(&self) -> bool {
-        !self.any()
-    }
-
-    pub fn runnable(&self) -> bool {
-        self.run || self.debug
-    }
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FilesConfig {
     pub watcher: FilesWatcher,
     pub exclude: Vec<AbsPathBuf>,
+    pub encoding: FileEncoding,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FilesWatcher {
     Client,
     Server,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileEncoding {
+    Utf8,
+    Latin1,
+}
+
 #[derive(Debug, Clone)]
 pub struct NotificationsConfig {
     pub cargo_toml_not_found: bool,
@@ -1252,7 +1412,7 @@ pub struct NotificationsConfig {
 
 #[derive(Debug, Clone)]
 pub enum RustfmtConfig {
-    Rustfmt { extra_args: Vec<String>, enable_range_formatting: bool },
+    Rustfmt { extra_args: Vec<String>, enable_range_formatting: bool, toolchain: Option<String> },
     CustomCommand { command: String, args: Vec<String> },
 }
 
@@ -1386,6 +1546,29 @@ impl Config {
         self.workspace_roots.extend(paths);
     }
 
+    /// Returns the fully resolved configuration as a JSON object, mapping each
+    /// dotted key to its effective value and the source that set it:
+    /// `"client"`, `"user"` (the user's `rust-analyzer.toml`) or `"default"`.
+    /// Crate-level `rust-analyzer.toml` overrides apply per source root and are
+    /// not reflected here. The values of `extraEnv` maps are redacted, they
+    /// tend to hold tokens and other secrets.
+    pub fn effective_config(&self) -> serde_json::Value {
+        let mut map = serde_json::Map::new();
+        self.effective_global_config(&mut map);
+        self.effective_local_config(&mut map);
+        self.effective_client_config(&mut map);
+        for (key, entry) in map.iter_mut() {
+            if key.ends_with("extraEnv") {
+                if let Some(env) = entry.get_mut("value").and_then(|it| it.as_object_mut()) {
+                    for value in env.values_mut() {
+                        *value = serde_json::Value::from("<redacted>");
+                    }
+                }
+            }
+        }
+        serde_json::Value::Object(map)
+    }
+
     pub fn json_schema() -> serde_json::Value {
         let mut s = FullConfigInput::json_schema();
 
@@ -1416,11 +1599,13 @@ impl Config {
         AssistConfig {
             snippet_cap: self.snippet_cap(),
             allowed: None,
+            disabled: self.assist_disabled(source_root).clone(),
             insert_use: self.insert_use_config(source_root),
             prefer_no_std: self.imports_preferNoStd(source_root).to_owned(),
             assist_emit_must_use: self.assist_emitMustUse(source_root).to_owned(),
             prefer_prelude: self.imports_preferPrelude(source_root).to_owned(),
             prefer_absolute: self.imports_prefixExternPrelude(source_root).to_owned(),
+            prefer_reexports: self.imports_preferReexports(source_root).to_owned(),
             term_search_fuel: self.assist_termSearch_fuel(source_root).to_owned() as u64,
             term_search_borrowck: self.assist_termSearch_borrowcheck(source_root).to_owned(),
         }
@@ -1431,6 +1616,7 @@ impl Config {
             enable_postfix_completions: self.completion_postfix_enable().to_owned(),
             enable_imports_on_the_fly: self.completion_autoimport_enable().to_owned()
                 && self.caps.completion_item_edit_resolve(),
+            autoimport_exclude: self.completion_autoimport_exclude().clone(),
             enable_self_on_the_fly: self.completion_autoself_enable().to_owned(),
             enable_private_editable: self.completion_privateEditable_enable().to_owned(),
             full_function_signatures: self.completion_fullFunctionSignatures_enable().to_owned(),
@@ -1444,13 +1630,34 @@ impl Config {
             prefer_no_std: self.imports_preferNoStd(source_root).to_owned(),
             prefer_prelude: self.imports_preferPrelude(source_root).to_owned(),
             prefer_absolute: self.imports_prefixExternPrelude(source_root).to_owned(),
+            prefer_reexports: self.imports_preferReexports(source_root).to_owned(),
             snippets: self.snippets.clone().to_vec(),
             limit: self.completion_limit().to_owned(),
             enable_term_search: self.completion_termSearch_enable().to_owned(),
             term_search_fuel: self.completion_termSearch_fuel().to_owned() as u64,
+            scope: match self.completion_scope() {
+                CompletionScopeDef::Crate => CompletionScope::Crate,
+                CompletionScopeDef::Workspace => CompletionScope::Workspace,
+            },
+            scoring: CompletionScoringWeights {
+                local: *self.completion_scoring_weights_local() as u32,
+                field: *self.completion_scoring_weights_field() as u32,
+                method: *self.completion_scoring_weights_method() as u32,
+                import: *self.completion_scoring_weights_import() as u32,
+                recency: *self.completion_scoring_weights_recency() as u32,
+                prefix_match: *self.completion_scoring_weights_prefixMatch() as u32,
+            },
         }
     }
 
+    pub fn completion_item_docs_resolve(&self) -> bool {
+        self.caps.completion_item_docs_resolve()
+    }
+
+    pub fn completion_label_details(&self) -> bool {
+        self.caps.completion_label_details_support() && *self.completion_labelDetails_enable()
+    }
+
     pub fn detached_files(&self) -> &Vec<AbsPathBuf> {
         // FIXME @alibektas : This is the only config that is confusing. If it's a proper configuration
         // why is it not among the others? If it's client only which I doubt it is current state should be alright
@@ -1462,6 +1669,12 @@ impl Config {
             enabled: *self.diagnostics_enable(source_root),
             proc_attr_macros_enabled: self.expand_proc_attr_macros(),
             proc_macros_enabled: *self.procMacro_enable(),
+            proc_macro_diagnostic_severity: match self.procMacro_diagnosticSeverity() {
+                ProcMacroDiagnosticSeverityDef::Error => Severity::Error,
+                ProcMacroDiagnosticSeverityDef::Warning => Severity::Warning,
+                ProcMacroDiagnosticSeverityDef::Hint => Severity::WeakWarning,
+                ProcMacroDiagnosticSeverityDef::Off => Severity::Allow,
+            },
             disable_experimental: !self.diagnostics_experimental_enable(source_root),
             disabled: self.diagnostics_disabled(source_root).clone(),
             expr_fill_default: match self.assist_expressionFillDefault(source_root) {
@@ -1473,7 +1686,9 @@ impl Config {
             prefer_no_std: self.imports_preferNoStd(source_root).to_owned(),
             prefer_prelude: self.imports_preferPrelude(source_root).to_owned(),
             prefer_absolute: self.imports_prefixExternPrelude(source_root).to_owned(),
+            prefer_reexports: self.imports_preferReexports(source_root).to_owned(),
             style_lints: self.diagnostics_styleLints_enable(source_root).to_owned(),
+            unused_pub_items: self.diagnostics_unusedPubItems_enable(source_root).to_owned(),
             term_search_fuel: self.assist_termSearch_fuel(source_root).to_owned() as u64,
             term_search_borrowck: self.assist_termSearch_borrowcheck(source_root).to_owned(),
         }
@@ -1529,6 +1744,7 @@ impl Config {
             max_trait_assoc_items_count: self.hover_show_traitAssocItems().to_owned(),
             max_fields_count: self.hover_show_fields().to_owned(),
             max_enum_variants_count: self.hover_show_enumVariants().to_owned(),
+            actions: self.hover_actions(),
         }
     }
 
@@ -1735,12 +1951,29 @@ impl Config {
         self.diagnostics_enable(None).to_owned()
     }
 
+    pub fn diagnostics_on_startup_deferred(&self) -> bool {
+        matches!(self.diagnostics_onStartup(None), DiagnosticsOnStartupDef::Deferred)
+    }
+
+    pub fn folding_ranges_comments(&self) -> bool {
+        *self.foldingRanges_comments_enable()
+    }
+
+    pub fn diagnostics_message_replacements(&self) -> &FxHashMap<String, String> {
+        self.diagnostics_messageReplacements(None)
+    }
+
     pub fn diagnostics_map(&self) -> DiagnosticsMapConfig {
         DiagnosticsMapConfig {
             remap_prefix: self.diagnostics_remapPrefix(None).clone(),
             warnings_as_info: self.diagnostics_warningsAsInfo(None).clone(),
             warnings_as_hint: self.diagnostics_warningsAsHint(None).clone(),
             check_ignore: self.check_ignore(None).clone(),
+            child_spans: match self.diagnostics_childSpans(None) {
+                ChildSpansDef::Hints => ChildSpans::Hints,
+                ChildSpansDef::Related => ChildSpans::Related,
+                ChildSpansDef::Ignore => ChildSpans::Ignore,
+            },
         }
     }
 
@@ -1768,6 +2001,10 @@ impl Config {
         self.lru_capacity(None).to_owned()
     }
 
+    pub fn lru_macro_expansion_capacity(&self) -> Option<u16> {
+        self.lru_macroExpansionCapacity(None).to_owned()
+    }
+
     pub fn lru_query_capacities_config(&self) -> Option<&FxHashMap<Box<str>, u16>> {
         self.lru_query_capacities(None).is_empty().not().then(|| self.lru_query_capacities(None))
     }
@@ -1798,9 +2035,17 @@ impl Config {
                 .iter()
                 .map(|it| self.root_path.join(it))
                 .collect(),
+            encoding: match self.files_encoding(None) {
+                FilesEncodingDef::Utf8 => FileEncoding::Utf8,
+                FilesEncodingDef::Latin1 => FileEncoding::Latin1,
+            },
         }
     }
 
+    pub fn files_open_documents_limit(&self) -> Option<NonZeroUsize> {
+        *self.files_openDocumentsLimit(None)
+    }
+
     pub fn notifications(&self) -> NotificationsConfig {
         NotificationsConfig {
             cargo_toml_not_found: self.notifications_cargoTomlNotFound().to_owned(),
@@ -1823,11 +2068,11 @@ impl Config {
                 RustLibSource::Path(self.root_path.join(rustc_src))
             }
         });
-        let sysroot = self.cargo_sysroot(None).as_ref().map(|sysroot| {
-            if sysroot == "discover" {
-                RustLibSource::Discover
-            } else {
-                RustLibSource::Path(self.root_path.join(sysroot))
+        let sysroot = self.cargo_sysroot(None).as_ref().and_then(|sysroot| {
+            match sysroot.as_str() {
+                "discover" => Some(RustLibSource::Discover),
+                "none" => None,
+                _ => Some(RustLibSource::Path(self.root_path.join(sysroot))),
             }
         });
         let sysroot_src =
@@ -1875,6 +2120,7 @@ impl Config {
                 InvocationLocation::Workspace => project_model::InvocationLocation::Workspace,
             },
             run_build_script_command: self.cargo_buildScripts_overrideCommand(None).clone(),
+            build_script_packages: self.cargo_buildScripts_packages(None).clone(),
             extra_args: self.cargo_extraArgs(None).clone(),
             extra_env: self.cargo_extraEnv(None).clone(),
             target_dir: self.target_dir_from_config(),
@@ -1891,6 +2137,7 @@ impl Config {
             Some(_) | None => RustfmtConfig::Rustfmt {
                 extra_args: self.rustfmt_extraArgs(source_root_id).clone(),
                 enable_range_formatting: *self.rustfmt_rangeFormatting_enable(source_root_id),
+                toolchain: self.rustfmt_toolchain(source_root_id).clone(),
             },
         }
     }
@@ -1899,10 +2146,25 @@ impl Config {
         *self.check_workspace(None)
     }
 
+    pub fn flycheck_default_members(&self) -> bool {
+        *self.check_defaultMembers(None)
+    }
+
+    pub fn document_colors_patterns(&self) -> &Vec<String> {
+        self.colors_patterns(None)
+    }
+
+    pub fn flycheck_only_changed_packages(&self) -> bool {
+        *self.check_onlyChangedPackages(None)
+    }
+
     pub(crate) fn cargo_test_options(&self) -> CargoOptions {
         CargoOptions {
             target_triples: self.cargo_target(None).clone().into_iter().collect(),
             all_targets: false,
+            tests: false,
+            benches: false,
+            examples: false,
             no_default_features: *self.cargo_noDefaultFeatures(None),
             all_features: matches!(self.cargo_features(None), CargoFeaturesDef::All),
             features: match self.cargo_features(None).clone() {
@@ -1912,6 +2174,8 @@ impl Config {
             extra_args: self.extra_args().clone(),
             extra_env: self.extra_env().clone(),
             target_dir: self.target_dir_from_config(),
+            // `cargo test` does not accept `--keep-going`.
+            keep_going: false,
         }
     }
 
@@ -1954,6 +2218,9 @@ impl Config {
                     all_targets: self
                         .check_allTargets(None)
                         .unwrap_or(*self.cargo_allTargets(None)),
+                    tests: *self.check_tests(None),
+                    benches: *self.check_benches(None),
+                    examples: *self.check_examples(None),
                     no_default_features: self
                         .check_noDefaultFeatures(None)
                         .unwrap_or(*self.cargo_noDefaultFeatures(None)),
@@ -1971,7 +2238,8 @@ impl Config {
                     },
                     extra_args: self.check_extra_args(),
                     extra_env: self.check_extra_env(),
-                    target_dir: self.target_dir_from_config(),
+                    target_dir: self.check_target_dir_from_config(),
+                    keep_going: *self.check_keepGoing(None),
                 },
                 ansi_color_output: self.color_diagnostic_output(),
             },
@@ -1979,14 +2247,14 @@ impl Config {
     }
 
     fn target_dir_from_config(&self) -> Option<Utf8PathBuf> {
-        self.cargo_targetDir(None).as_ref().and_then(|target_dir| match target_dir {
-            TargetDirectory::UseSubdirectory(true) => {
-                Some(Utf8PathBuf::from("target/rust-analyzer"))
-            }
-            TargetDirectory::UseSubdirectory(false) => None,
-            TargetDirectory::Directory(dir) if dir.is_relative() => Some(dir.clone()),
-            TargetDirectory::Directory(_) => None,
-        })
+        target_dir_from_setting(self.cargo_targetDir(None))
+    }
+
+    fn check_target_dir_from_config(&self) -> Option<Utf8PathBuf> {
+        match self.check_targetDir(None) {
+            Some(_) => target_dir_from_setting(self.check_targetDir(None)),
+            None => self.target_dir_from_config(),
+        }
     }
 
     pub fn check_on_save(&self) -> bool {
@@ -2005,6 +2273,10 @@ impl Config {
         }
     }
 
+    pub fn find_all_refs_count_limit(&self) -> Option<usize> {
+        *self.references_countLimit()
+    }
+
     pub fn find_all_refs_exclude_imports(&self) -> bool {
         *self.references_excludeImports()
     }
@@ -2040,6 +2312,14 @@ impl Config {
         }
     }
 
+    pub fn todo_comments(&self) -> TodoCommentsConfig {
+        TodoCommentsConfig {
+            markers: self.todoComments_markers().clone(),
+            scan_dependencies: *self.todoComments_scanDependencies(),
+            limit: *self.todoComments_limit(),
+        }
+    }
+
     pub fn workspace_symbol(&self) -> WorkspaceSymbolConfig {
         WorkspaceSymbolConfig {
             search_scope: match self.workspace_symbol_search_scope() {
@@ -2079,6 +2359,10 @@ impl Config {
         }
     }
 
+    pub fn request_timeout(&self) -> Option<Duration> {
+        (*self.requestTimeout(None)).map(Duration::from_millis)
+    }
+
     pub fn main_loop_num_threads(&self) -> usize {
         match self.numThreads() {
             Some(NumThreads::Concrete(0)) | None | Some(NumThreads::Physical) => {
@@ -2089,6 +2373,15 @@ impl Config {
         }
     }
 
+    pub fn diagnostics_num_threads(&self) -> usize {
+        match self.diagnostics_numThreads() {
+            None | Some(NumThreads::Concrete(0)) => (self.main_loop_num_threads() / 4).max(1),
+            &Some(NumThreads::Concrete(n)) => n,
+            Some(NumThreads::Physical) => num_cpus::get_physical(),
+            Some(NumThreads::Logical) => num_cpus::get(),
+        }
+    }
+
     pub fn typing_autoclose_angle(&self) -> bool {
         *self.typing_autoClosingAngleBrackets_enable()
     }
@@ -2293,6 +2586,15 @@ where
     se.serialize_str(path.as_str())
 }
 
+fn target_dir_from_setting(target_dir: &Option<TargetDirectory>) -> Option<Utf8PathBuf> {
+    target_dir.as_ref().and_then(|target_dir| match target_dir {
+        TargetDirectory::UseSubdirectory(true) => Some(Utf8PathBuf::from("target/rust-analyzer")),
+        TargetDirectory::UseSubdirectory(false) => None,
+        TargetDirectory::Directory(dir) if dir.is_relative() => Some(dir.clone()),
+        TargetDirectory::Directory(_) => None,
+    })
+}
+
 impl ManifestOrProjectJson {
     fn manifest(&self) -> Option<&Utf8Path> {
         match self {
@@ -2330,6 +2632,13 @@ pub(crate) enum CallableCompletionDef {
     None,
 }
 
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CompletionScopeDef {
+    Crate,
+    Workspace,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 enum CargoFeaturesDef {
@@ -2441,6 +2750,13 @@ enum FilesWatcherDef {
     Server,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum FilesEncodingDef {
+    Utf8,
+    Latin1,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 enum ImportPrefixDef {
@@ -2460,6 +2776,15 @@ enum WorkspaceSymbolSearchScopeDef {
     WorkspaceAndDependencies,
 }
 
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum ProcMacroDiagnosticSeverityDef {
+    Error,
+    Warning,
+    Hint,
+    Off,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 enum SignatureDetail {
@@ -2474,6 +2799,21 @@ enum WorkspaceSymbolSearchKindDef {
     AllSymbols,
 }
 
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum ChildSpansDef {
+    Hints,
+    Related,
+    Ignore,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum DiagnosticsOnStartupDef {
+    Eager,
+    Deferred,
+}
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 enum MemoryLayoutHoverRenderKindDef {
@@ -2544,27 +2884,36 @@ macro_rules! _impl_for_config_data {
                 $($doc)*
                 #[allow(non_snake_case)]
                 $vis fn $field(&self, source_root: Option<SourceRootId>) -> &$ty {
-                    let mut source_root = source_root.as_ref();
-                    while let Some(sr) = source_root {
-                        if let Some((file, _)) = self.ratoml_file.get(&sr) {
-                            match file {
-                                RatomlFile::Workspace(config) => {
-                                    if let Some(v) = config.local.$field.as_ref() {
-                                        return &v;
-                                    }
-                                },
-                                RatomlFile::Crate(config) => {
-                                    if let Some(value) = config.$field.as_ref() {
-                                        return value;
+                    let from_ratoml = || {
+                        let mut source_root = source_root.as_ref();
+                        while let Some(sr) = source_root {
+                            if let Some((file, _)) = self.ratoml_file.get(&sr) {
+                                match file {
+                                    RatomlFile::Workspace(config) => {
+                                        if let Some(v) = config.local.$field.as_ref() {
+                                            return Some(v);
+                                        }
+                                    },
+                                    RatomlFile::Crate(config) => {
+                                        if let Some(value) = config.$field.as_ref() {
+                                            return Some(value);
+                                        }
                                     }
                                 }
                             }
+                            source_root = self.source_root_parent_map.get(&sr);
                         }
-                        source_root = self.source_root_parent_map.get(&sr);
-                    }
+                        None
+                    };
+                    let from_client = || self.client_config.0.local.$field.as_ref();
 
-                    if let Some(v) = self.client_config.0.local.$field.as_ref() {
-                        return &v;
+                    let v = if *self.workspace_ratomlTakesPrecedence() {
+                        from_ratoml().or_else(from_client)
+                    } else {
+                        from_client().or_else(from_ratoml)
+                    };
+                    if let Some(v) = v {
+                        return v;
                     }
 
                     if let Some((user_config, _)) = self.user_config.as_ref() {
@@ -2576,6 +2925,33 @@ macro_rules! _impl_for_config_data {
                     &self.default_config.local.$field
                 }
             )*
+
+            fn effective_local_config(
+                &self,
+                sink: &mut serde_json::Map<String, serde_json::Value>,
+            ) {
+                $(
+                    {
+                        let (value, source) = if let Some(v) =
+                            self.client_config.0.local.$field.as_ref()
+                        {
+                            (serde_json::to_value(v), "client")
+                        } else if let Some(v) =
+                            self.user_config.as_ref().and_then(|(it, _)| it.local.$field.as_ref())
+                        {
+                            (serde_json::to_value(v), "user")
+                        } else {
+                            (serde_json::to_value(&self.default_config.local.$field), "default")
+                        };
+                        if let Ok(value) = value {
+                            sink.insert(
+                                stringify!($field).replace('_', "."),
+                                serde_json::json!({ "value": value, "source": source }),
+                            );
+                        }
+                    }
+                )*
+            }
         }
     };
     (global, $(
@@ -2588,19 +2964,28 @@ macro_rules! _impl_for_config_data {
                 $($doc)*
                 #[allow(non_snake_case)]
                 $vis fn $field(&self, source_root : Option<SourceRootId>) -> &$ty {
-                    let mut source_root = source_root.as_ref();
-                    while let Some(sr) = source_root {
-                        if let Some((RatomlFile::Workspace(config), _)) = self.ratoml_file.get(&sr) {
-                            if let Some(v) = config.global.$field.as_ref() {
-                                return &v;
+                    let from_ratoml = || {
+                        let mut source_root = source_root.as_ref();
+                        while let Some(sr) = source_root {
+                            if let Some((RatomlFile::Workspace(config), _)) = self.ratoml_file.get(&sr) {
+                                if let Some(v) = config.global.$field.as_ref() {
+                                    return Some(v);
+                                }
                             }
-                        }
 
-                        source_root = self.source_root_parent_map.get(&sr);
-                    }
+                            source_root = self.source_root_parent_map.get(&sr);
+                        }
+                        None
+                    };
+                    let from_client = || self.client_config.0.global.$field.as_ref();
 
-                    if let Some(v) = self.client_config.0.global.$field.as_ref() {
-                        return &v;
+                    let v = if *self.workspace_ratomlTakesPrecedence() {
+                        from_ratoml().or_else(from_client)
+                    } else {
+                        from_client().or_else(from_ratoml)
+                    };
+                    if let Some(v) = v {
+                        return v;
                     }
 
                     if let Some((user_config, _)) = self.user_config.as_ref() {
@@ -2613,6 +2998,33 @@ macro_rules! _impl_for_config_data {
                     &self.default_config.global.$field
                 }
             )*
+
+            fn effective_global_config(
+                &self,
+                sink: &mut serde_json::Map<String, serde_json::Value>,
+            ) {
+                $(
+                    {
+                        let (value, source) = if let Some(v) =
+                            self.client_config.0.global.$field.as_ref()
+                        {
+                            (serde_json::to_value(v), "client")
+                        } else if let Some(v) =
+                            self.user_config.as_ref().and_then(|(it, _)| it.global.$field.as_ref())
+                        {
+                            (serde_json::to_value(v), "user")
+                        } else {
+                            (serde_json::to_value(&self.default_config.global.$field), "default")
+                        };
+                        if let Ok(value) = value {
+                            sink.insert(
+                                stringify!($field).replace('_', "."),
+                                serde_json::json!({ "value": value, "source": source }),
+                            );
+                        }
+                    }
+                )*
+            }
         }
     };
     (client, $(
@@ -2632,6 +3044,28 @@ macro_rules! _impl_for_config_data {
                     &self.default_config.client.$field
                 }
             )*
+
+            fn effective_client_config(
+                &self,
+                sink: &mut serde_json::Map<String, serde_json::Value>,
+            ) {
+                $(
+                    {
+                        let (value, source) = match self.client_config.0.client.$field.as_ref() {
+                            Some(v) => (serde_json::to_value(v), "client"),
+                            None => {
+                                (serde_json::to_value(&self.default_config.client.$field), "default")
+                            }
+                        };
+                        if let Ok(value) = value {
+                            sink.insert(
+                                stringify!($field).replace('_', "."),
+                                serde_json::json!({ "value": value, "source": source }),
+                            );
+                        }
+                    }
+                )*
+            }
         }
     };
 }
@@ -2966,11 +3400,19 @@ fn field_props(field: &str, ty: &str, doc: &[&str], default: &str) -> serde_json
             "type": ["null", "integer"],
             "minimum": 0,
         },
+        "Option<NonZeroUsize>" => set! {
+            "type": ["null", "integer"],
+            "minimum": 1,
+        },
         "Option<u16>" => set! {
             "type": ["null", "integer"],
             "minimum": 0,
             "maximum": 65535,
         },
+        "Option<u64>" => set! {
+            "type": ["null", "integer"],
+            "minimum": 0,
+        },
         "Option<String>" => set! {
             "type": ["null", "string"],
         },
@@ -3020,6 +3462,16 @@ fn field_props(field: &str, ty: &str, doc: &[&str], default: &str) -> serde_json
             "type": "array",
             "items": { "type": ["string", "object"] },
         },
+        "ProcMacroDiagnosticSeverityDef" => set! {
+            "type": "string",
+            "enum": ["error", "warning", "hint", "off"],
+            "enumDescriptions": [
+                "Report these diagnostics as errors.",
+                "Report these diagnostics as warnings.",
+                "Report these diagnostics as hints.",
+                "Do not report these diagnostics at all."
+            ],
+        },
         "WorkspaceSymbolSearchScopeDef" => set! {
             "type": "string",
             "enum": ["workspace", "workspace_and_dependencies"],
@@ -3036,6 +3488,23 @@ fn field_props(field: &str, ty: &str, doc: &[&str], default: &str) -> serde_json
                 "Search for all symbols kinds."
             ],
         },
+        "DiagnosticsOnStartupDef" => set! {
+            "type": "string",
+            "enum": ["eager", "deferred"],
+            "enumDescriptions": [
+                "Refresh diagnostics as soon as the workspace has loaded.",
+                "Wait until a document is opened or modified after the workspace has loaded."
+            ],
+        },
+        "ChildSpansDef" => set! {
+            "type": "string",
+            "enum": ["hints", "related", "ignore"],
+            "enumDescriptions": [
+                "Attach child spans to the primary diagnostic as related information and additionally publish them as separate hint-level diagnostics.",
+                "Only attach child spans to the primary diagnostic as related information.",
+                "Drop child spans entirely."
+            ],
+        },
         "LifetimeElisionDef" => set! {
             "type": "string",
             "enum": [
@@ -3164,6 +3633,14 @@ fn field_props(field: &str, ty: &str, doc: &[&str], default: &str) -> serde_json
                 "Do no snippet completions for callables."
             ]
         },
+        "CompletionScopeDef" => set! {
+            "type": "string",
+            "enum": ["crate", "workspace"],
+            "enumDescriptions": [
+                "Search only the current crate and the sysroot crates.",
+                "Search the current crate and all of its dependencies."
+            ]
+        },
         "SignatureDetail" => set! {
             "type": "string",
             "enum": ["full", "parameters"],
@@ -3180,6 +3657,14 @@ fn field_props(field: &str, ty: &str, doc: &[&str], default: &str) -> serde_json
                 "Use server-side file watching",
             ],
         },
+        "FilesEncodingDef" => set! {
+            "type": "string",
+            "enum": ["utf8", "latin1"],
+            "enumDescriptions": [
+                "Only load files that are valid UTF-8",
+                "Decode files that are not valid UTF-8 as ISO-8859-1",
+            ],
+        },
         "AnnotationLocation" => set! {
             "type": "string",
             "enum": ["above_name", "above_whole_item"],
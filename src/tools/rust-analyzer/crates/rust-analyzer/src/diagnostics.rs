@@ -21,6 +21,21 @@ pub struct DiagnosticsMapConfig {
     pub warnings_as_info: Vec<String>,
     pub warnings_as_hint: Vec<String>,
     pub check_ignore: FxHashSet<String>,
+    pub child_spans: ChildSpans,
+}
+
+/// How the child spans of a cargo diagnostic (notes and helps with their own spans) are
+/// rendered. Children carrying a quickfix are kept as separate diagnostics in all modes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChildSpans {
+    /// Attach to the primary diagnostic as `relatedInformation` and additionally publish
+    /// separate hint-level diagnostics, which show up inline in most editors.
+    #[default]
+    Hints,
+    /// Only attach to the primary diagnostic as `relatedInformation`.
+    Related,
+    /// Drop child spans entirely.
+    Ignore,
 }
 
 pub(crate) type DiagnosticsGeneration = usize;
@@ -64,6 +79,14 @@ impl DiagnosticCollection {
             .extend(self.check.values_mut().flat_map(|it| it.drain().map(|(key, _value)| key)))
     }
 
+    /// Marks every file with retained native diagnostics as changed, forcing a
+    /// re-publish. Used when the set of disabled diagnostic codes changes, as
+    /// that is applied to the retained entries at publish time.
+    pub(crate) fn refresh_native(&mut self) {
+        self.changes.extend(self.native_syntax.keys().copied());
+        self.changes.extend(self.native_semantic.keys().copied());
+    }
+
     pub(crate) fn clear_native_for(&mut self, file_id: FileId) {
         self.native_syntax.remove(&file_id);
         self.native_semantic.remove(&file_id);
@@ -127,12 +150,23 @@ impl DiagnosticCollection {
         }
     }
 
-    pub(crate) fn diagnostics_for(
-        &self,
+    pub(crate) fn diagnostics_for<'a>(
+        &'a self,
         file_id: FileId,
-    ) -> impl Iterator<Item = &lsp_types::Diagnostic> {
-        let native_syntax = self.native_syntax.get(&file_id).into_iter().flat_map(|(_, d)| d);
-        let native_semantic = self.native_semantic.get(&file_id).into_iter().flat_map(|(_, d)| d);
+        disabled_native: &'a FxHashSet<String>,
+    ) -> impl Iterator<Item = &'a lsp_types::Diagnostic> {
+        // Natively computed diagnostics may predate a config change that
+        // disabled their code, so the disabled set is applied again when
+        // publishing. Check diagnostics are the compiler's own and are never
+        // filtered by it.
+        let enabled = move |d: &&lsp_types::Diagnostic| match &d.code {
+            Some(lsp_types::NumberOrString::String(code)) => !disabled_native.contains(code),
+            _ => true,
+        };
+        let native_syntax =
+            self.native_syntax.get(&file_id).into_iter().flat_map(|(_, d)| d).filter(enabled);
+        let native_semantic =
+            self.native_semantic.get(&file_id).into_iter().flat_map(|(_, d)| d).filter(enabled);
         let check = self.check.values().filter_map(move |it| it.get(&file_id)).flatten();
         native_syntax.chain(native_semantic).chain(check)
     }
@@ -183,7 +217,12 @@ pub(crate) fn fetch_native_diagnostics(
             message: d.message,
             related_information: None,
             tags: d.unused.then(|| vec![lsp_types::DiagnosticTag::UNNECESSARY]),
-            data: None,
+            // Fix availability is cheap to compute here as the fixes are
+            // unresolved; clients can use it to only show a lightbulb where a
+            // code action request will actually produce a quick-fix.
+            data: Some(serde_json::json!({
+                "hasFix": !d.fixes.as_deref().unwrap_or_default().is_empty(),
+            })),
         };
 
     // the diagnostics produced may point to different files not requested by the concrete request,
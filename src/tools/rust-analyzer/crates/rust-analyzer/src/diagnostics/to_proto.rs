@@ -1,6 +1,8 @@
 //! This module provides the functionality needed to convert diagnostics from
 //! `cargo check` json format to the LSP diagnostic format.
 
+use std::borrow::Cow;
+
 use crate::flycheck::{Applicability, DiagnosticLevel, DiagnosticSpan};
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
@@ -12,7 +14,7 @@ use crate::{
     lsp::to_proto::url_from_abs_path, lsp_ext,
 };
 
-use super::{DiagnosticsMapConfig, Fix};
+use super::{ChildSpans, DiagnosticsMapConfig, Fix};
 
 /// Determines the LSP severity from a diagnostic
 fn diagnostic_severity(
@@ -178,6 +180,35 @@ enum MappedRustChildDiagnostic {
     MessageLine(String),
 }
 
+/// Removes ANSI escape sequences that tools running with forced colors can
+/// leak into the diagnostic text.
+fn strip_ansi_codes(input: &str) -> Cow<'_, str> {
+    if !input.contains('\u{1b}') {
+        return Cow::Borrowed(input);
+    }
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'[') {
+            // A CSI sequence runs until a byte in the `@`..=`~` range.
+            chars.next();
+            for c in chars.by_ref() {
+                if ('\u{40}'..='\u{7e}').contains(&c) {
+                    break;
+                }
+            }
+        } else {
+            // Other escape sequences consist of a single character.
+            chars.next();
+        }
+    }
+    Cow::Owned(out)
+}
+
 fn map_rust_child_diagnostic(
     config: &DiagnosticsMapConfig,
     workspace_root: &AbsPath,
@@ -188,7 +219,7 @@ fn map_rust_child_diagnostic(
     if spans.is_empty() {
         // `rustc` uses these spanless children as a way to print multi-line
         // messages
-        return MappedRustChildDiagnostic::MessageLine(rd.message.clone());
+        return MappedRustChildDiagnostic::MessageLine(strip_ansi_codes(&rd.message).into_owned());
     }
 
     let mut edit_map: FxHashMap<lsp_types::Url, Vec<lsp_types::TextEdit>> = FxHashMap::default();
@@ -219,7 +250,7 @@ fn map_rust_child_diagnostic(
 
     // rustc renders suggestion diagnostics by appending the suggested replacement, so do the same
     // here, otherwise the diagnostic text is missing useful information.
-    let mut message = rd.message.clone();
+    let mut message = strip_ansi_codes(&rd.message).into_owned();
     if !suggested_replacements.is_empty() {
         message.push_str(": ");
         let suggestions =
@@ -324,7 +355,7 @@ pub(crate) fn map_rust_diagnostic_to_lsp(
         }
     }
 
-    let mut message = rd.message.clone();
+    let mut message = strip_ansi_codes(&rd.message).into_owned();
     for child in &rd.children {
         let child = map_rust_child_diagnostic(config, workspace_root, child, snap);
         match child {
@@ -367,6 +398,16 @@ pub(crate) fn map_rust_diagnostic_to_lsp(
         _ => None,
     };
 
+    // The rendered text is only supposed to be colored when the client opted
+    // into `--message-format=json-diagnostic-rendered-ansi`; strip any escape
+    // codes that leaked in otherwise.
+    let rendered = match &rd.rendered {
+        Some(rendered) if !snap.config.color_diagnostic_output() => {
+            Some(strip_ansi_codes(rendered).into_owned())
+        }
+        _ => rd.rendered.clone(),
+    };
+
     primary_spans
         .iter()
         .flat_map(|primary_span| {
@@ -430,7 +471,7 @@ pub(crate) fn map_rust_diagnostic_to_lsp(
                     message: message.clone(),
                     related_information: Some(information_for_additional_diagnostic),
                     tags: if tags.is_empty() { None } else { Some(tags.clone()) },
-                    data: Some(serde_json::json!({ "rendered": rd.rendered })),
+                    data: Some(serde_json::json!({ "rendered": rendered.clone() })),
                 };
                 diagnostics.push(MappedRustDiagnostic {
                     url: secondary_location.uri,
@@ -450,10 +491,16 @@ pub(crate) fn map_rust_diagnostic_to_lsp(
                     source: Some(source.clone()),
                     message,
                     related_information: {
+                        let attach_subs = config.child_spans != ChildSpans::Ignore;
                         let info = related_info_macro_calls
                             .iter()
                             .cloned()
-                            .chain(subdiagnostics.iter().map(|sub| sub.related.clone()))
+                            .chain(
+                                subdiagnostics
+                                    .iter()
+                                    .filter(|_| attach_subs)
+                                    .map(|sub| sub.related.clone()),
+                            )
                             .collect::<Vec<_>>();
                         if info.is_empty() {
                             None
@@ -462,7 +509,7 @@ pub(crate) fn map_rust_diagnostic_to_lsp(
                         }
                     },
                     tags: if tags.is_empty() { None } else { Some(tags.clone()) },
-                    data: Some(serde_json::json!({ "rendered": rd.rendered })),
+                    data: Some(serde_json::json!({ "rendered": rendered.clone() })),
                 },
                 fix: None,
             });
@@ -470,11 +517,15 @@ pub(crate) fn map_rust_diagnostic_to_lsp(
             // Emit hint-level diagnostics for all `related_information` entries such as "help"s.
             // This is useful because they will show up in the user's editor, unlike
             // `related_information`, which just produces hard-to-read links, at least in VS Code.
+            // In the `related` and `ignore` modes only children carrying a quickfix are kept,
+            // as dropping them would make the fix inaccessible.
             let back_ref = lsp_types::DiagnosticRelatedInformation {
                 location: primary_location,
                 message: "original diagnostic".to_owned(),
             };
-            for sub in &subdiagnostics {
+            for sub in subdiagnostics.iter().filter(|sub| {
+                config.child_spans == ChildSpans::Hints || sub.suggested_fix.is_some()
+            }) {
                 diagnostics.push(MappedRustDiagnostic {
                     url: sub.related.location.uri.clone(),
                     fix: sub.suggested_fix.clone(),
@@ -686,6 +737,90 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(not(windows))]
+    fn rustc_unused_variable_child_spans_ignore() {
+        check_with_config(
+            DiagnosticsMapConfig {
+                child_spans: ChildSpans::Ignore,
+                ..DiagnosticsMapConfig::default()
+            },
+            r##"{
+    "message": "unused variable: `foo`",
+    "code": {
+        "code": "unused_variables",
+        "explanation": null
+    },
+    "level": "warning",
+    "spans": [
+        {
+            "file_name": "driver/subcommand/repl.rs",
+            "byte_start": 9228,
+            "byte_end": 9231,
+            "line_start": 291,
+            "line_end": 291,
+            "column_start": 9,
+            "column_end": 12,
+            "is_primary": true,
+            "text": [
+                {
+                    "text": "    let foo = 42;",
+                    "highlight_start": 9,
+                    "highlight_end": 12
+                }
+            ],
+            "label": null,
+            "suggested_replacement": null,
+            "suggestion_applicability": null,
+            "expansion": null
+        }
+    ],
+    "children": [
+        {
+            "message": "#[warn(unused_variables)] on by default",
+            "code": null,
+            "level": "note",
+            "spans": [],
+            "children": [],
+            "rendered": null
+        },
+        {
+            "message": "consider prefixing with an underscore",
+            "code": null,
+            "level": "help",
+            "spans": [
+                {
+                    "file_name": "driver/subcommand/repl.rs",
+                    "byte_start": 9228,
+                    "byte_end": 9231,
+                    "line_start": 291,
+                    "line_end": 291,
+                    "column_start": 9,
+                    "column_end": 12,
+                    "is_primary": true,
+                    "text": [
+                        {
+                            "text": "    let foo = 42;",
+                            "highlight_start": 9,
+                            "highlight_end": 12
+                        }
+                    ],
+                    "label": null,
+                    "suggested_replacement": "_foo",
+                    "suggestion_applicability": "MachineApplicable",
+                    "expansion": null
+                }
+            ],
+            "children": [],
+            "rendered": null
+        }
+    ],
+    "rendered": "warning: unused variable: `foo`\n   --> driver/subcommand/repl.rs:291:9\n    |\n291 |     let foo = 42;\n    |         ^^^ help: consider prefixing with an underscore: `_foo`\n    |\n    = note: #[warn(unused_variables)] on by default\n\n"
+    }"##,
+            expect_file!["./test_data/rustc_unused_variable_child_spans_ignore.txt"],
+        );
+    }
+
     #[test]
     #[cfg(not(windows))]
     fn rustc_unused_variable_as_info() {
@@ -854,6 +989,51 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(not(windows))]
+    fn rustc_remap_prefix_is_applied_to_spans() {
+        check_with_config(
+            DiagnosticsMapConfig {
+                remap_prefix: std::iter::once(("/build".to_owned(), "/test".to_owned())).collect(),
+                ..DiagnosticsMapConfig::default()
+            },
+            r##"{
+    "message": "unused variable: `foo`",
+    "code": {
+        "code": "unused_variables",
+        "explanation": null
+    },
+    "level": "warning",
+    "spans": [
+        {
+            "file_name": "/build/driver/subcommand/repl.rs",
+            "byte_start": 9228,
+            "byte_end": 9231,
+            "line_start": 291,
+            "line_end": 291,
+            "column_start": 9,
+            "column_end": 12,
+            "is_primary": true,
+            "text": [
+                {
+                    "text": "    let foo = 42;",
+                    "highlight_start": 9,
+                    "highlight_end": 12
+                }
+            ],
+            "label": null,
+            "suggested_replacement": null,
+            "suggestion_applicability": null,
+            "expansion": null
+        }
+    ],
+    "children": [],
+    "rendered": "warning: unused variable: `foo`\n   --> /build/driver/subcommand/repl.rs:291:9\n    |\n291 |     let foo = 42;\n    |         ^^^\n    |\n    = note: #[warn(unused_variables)] on by default\n\n"
+    }"##,
+            expect_file!["./test_data/rustc_remap_prefix_is_applied_to_spans.txt"],
+        );
+    }
+
     #[test]
     fn rustc_wrong_number_of_parameters() {
         check(
@@ -1092,6 +1272,250 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(not(windows))]
+    fn clippy_pass_by_ref_child_spans_related() {
+        check_with_config(
+            DiagnosticsMapConfig {
+                child_spans: ChildSpans::Related,
+                ..DiagnosticsMapConfig::default()
+            },
+            r##"{
+    "message": "this argument is passed by reference, but would be more efficient if passed by value",
+    "code": {
+        "code": "clippy::trivially_copy_pass_by_ref",
+        "explanation": null
+    },
+    "level": "warning",
+    "spans": [
+        {
+            "file_name": "compiler/mir/tagset.rs",
+            "byte_start": 941,
+            "byte_end": 946,
+            "line_start": 42,
+            "line_end": 42,
+            "column_start": 24,
+            "column_end": 29,
+            "is_primary": true,
+            "text": [
+                {
+                    "text": "    pub fn is_disjoint(&self, other: Self) -> bool {",
+                    "highlight_start": 24,
+                    "highlight_end": 29
+                }
+            ],
+            "label": null,
+            "suggested_replacement": null,
+            "suggestion_applicability": null,
+            "expansion": null
+        }
+    ],
+    "children": [
+        {
+            "message": "lint level defined here",
+            "code": null,
+            "level": "note",
+            "spans": [
+                {
+                    "file_name": "compiler/lib.rs",
+                    "byte_start": 8,
+                    "byte_end": 19,
+                    "line_start": 1,
+                    "line_end": 1,
+                    "column_start": 9,
+                    "column_end": 20,
+                    "is_primary": true,
+                    "text": [
+                        {
+                            "text": "#![warn(clippy::all)]",
+                            "highlight_start": 9,
+                            "highlight_end": 20
+                        }
+                    ],
+                    "label": null,
+                    "suggested_replacement": null,
+                    "suggestion_applicability": null,
+                    "expansion": null
+                }
+            ],
+            "children": [],
+            "rendered": null
+        },
+        {
+            "message": "#[warn(clippy::trivially_copy_pass_by_ref)] implied by #[warn(clippy::all)]",
+            "code": null,
+            "level": "note",
+            "spans": [],
+            "children": [],
+            "rendered": null
+        },
+        {
+            "message": "for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#trivially_copy_pass_by_ref",
+            "code": null,
+            "level": "help",
+            "spans": [],
+            "children": [],
+            "rendered": null
+        },
+        {
+            "message": "consider passing by value instead",
+            "code": null,
+            "level": "help",
+            "spans": [
+                {
+                    "file_name": "compiler/mir/tagset.rs",
+                    "byte_start": 941,
+                    "byte_end": 946,
+                    "line_start": 42,
+                    "line_end": 42,
+                    "column_start": 24,
+                    "column_end": 29,
+                    "is_primary": true,
+                    "text": [
+                        {
+                            "text": "    pub fn is_disjoint(&self, other: Self) -> bool {",
+                            "highlight_start": 24,
+                            "highlight_end": 29
+                        }
+                    ],
+                    "label": null,
+                    "suggested_replacement": "self",
+                    "suggestion_applicability": "Unspecified",
+                    "expansion": null
+                }
+            ],
+            "children": [],
+            "rendered": null
+        }
+    ],
+    "rendered": "warning: this argument is passed by reference, but would be more efficient if passed by value\n  --> compiler/mir/tagset.rs:42:24\n   |\n42 |     pub fn is_disjoint(&self, other: Self) -> bool {\n   |                        ^^^^^ help: consider passing by value instead: `self`\n   |\nnote: lint level defined here\n  --> compiler/lib.rs:1:9\n   |\n1  | #![warn(clippy::all)]\n   |         ^^^^^^^^^^^\n   = note: #[warn(clippy::trivially_copy_pass_by_ref)] implied by #[warn(clippy::all)]\n   = help: for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#trivially_copy_pass_by_ref\n\n"
+    }"##,
+            expect_file!["./test_data/clippy_pass_by_ref_child_spans_related.txt"],
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn clippy_pass_by_ref_child_spans_ignore() {
+        check_with_config(
+            DiagnosticsMapConfig {
+                child_spans: ChildSpans::Ignore,
+                ..DiagnosticsMapConfig::default()
+            },
+            r##"{
+    "message": "this argument is passed by reference, but would be more efficient if passed by value",
+    "code": {
+        "code": "clippy::trivially_copy_pass_by_ref",
+        "explanation": null
+    },
+    "level": "warning",
+    "spans": [
+        {
+            "file_name": "compiler/mir/tagset.rs",
+            "byte_start": 941,
+            "byte_end": 946,
+            "line_start": 42,
+            "line_end": 42,
+            "column_start": 24,
+            "column_end": 29,
+            "is_primary": true,
+            "text": [
+                {
+                    "text": "    pub fn is_disjoint(&self, other: Self) -> bool {",
+                    "highlight_start": 24,
+                    "highlight_end": 29
+                }
+            ],
+            "label": null,
+            "suggested_replacement": null,
+            "suggestion_applicability": null,
+            "expansion": null
+        }
+    ],
+    "children": [
+        {
+            "message": "lint level defined here",
+            "code": null,
+            "level": "note",
+            "spans": [
+                {
+                    "file_name": "compiler/lib.rs",
+                    "byte_start": 8,
+                    "byte_end": 19,
+                    "line_start": 1,
+                    "line_end": 1,
+                    "column_start": 9,
+                    "column_end": 20,
+                    "is_primary": true,
+                    "text": [
+                        {
+                            "text": "#![warn(clippy::all)]",
+                            "highlight_start": 9,
+                            "highlight_end": 20
+                        }
+                    ],
+                    "label": null,
+                    "suggested_replacement": null,
+                    "suggestion_applicability": null,
+                    "expansion": null
+                }
+            ],
+            "children": [],
+            "rendered": null
+        },
+        {
+            "message": "#[warn(clippy::trivially_copy_pass_by_ref)] implied by #[warn(clippy::all)]",
+            "code": null,
+            "level": "note",
+            "spans": [],
+            "children": [],
+            "rendered": null
+        },
+        {
+            "message": "for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#trivially_copy_pass_by_ref",
+            "code": null,
+            "level": "help",
+            "spans": [],
+            "children": [],
+            "rendered": null
+        },
+        {
+            "message": "consider passing by value instead",
+            "code": null,
+            "level": "help",
+            "spans": [
+                {
+                    "file_name": "compiler/mir/tagset.rs",
+                    "byte_start": 941,
+                    "byte_end": 946,
+                    "line_start": 42,
+                    "line_end": 42,
+                    "column_start": 24,
+                    "column_end": 29,
+                    "is_primary": true,
+                    "text": [
+                        {
+                            "text": "    pub fn is_disjoint(&self, other: Self) -> bool {",
+                            "highlight_start": 24,
+                            "highlight_end": 29
+                        }
+                    ],
+                    "label": null,
+                    "suggested_replacement": "self",
+                    "suggestion_applicability": "Unspecified",
+                    "expansion": null
+                }
+            ],
+            "children": [],
+            "rendered": null
+        }
+    ],
+    "rendered": "warning: this argument is passed by reference, but would be more efficient if passed by value\n  --> compiler/mir/tagset.rs:42:24\n   |\n42 |     pub fn is_disjoint(&self, other: Self) -> bool {\n   |                        ^^^^^ help: consider passing by value instead: `self`\n   |\nnote: lint level defined here\n  --> compiler/lib.rs:1:9\n   |\n1  | #![warn(clippy::all)]\n   |         ^^^^^^^^^^^\n   = note: #[warn(clippy::trivially_copy_pass_by_ref)] implied by #[warn(clippy::all)]\n   = help: for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#trivially_copy_pass_by_ref\n\n"
+    }"##,
+            expect_file!["./test_data/clippy_pass_by_ref_child_spans_ignore.txt"],
+        );
+    }
+
     #[test]
     fn rustc_range_map_lsp_position() {
         check(
@@ -1859,4 +2283,53 @@ mod tests {
             expect_file!["./test_data/reasonable_line_numbers_from_empty_file.txt"],
         );
     }
+
+    #[test]
+    fn strips_ansi_codes_from_diagnostic_text() {
+        check(
+            r##"{
+                "message": "unused variable: \u001b[1m`foo`\u001b[0m",
+                "code": {
+                    "code": "unused_variables",
+                    "explanation": null
+                },
+                "level": "warning",
+                "spans": [
+                    {
+                        "file_name": "driver/subcommand/repl.rs",
+                        "byte_start": 9228,
+                        "byte_end": 9231,
+                        "line_start": 291,
+                        "line_end": 291,
+                        "column_start": 9,
+                        "column_end": 12,
+                        "is_primary": true,
+                        "text": [
+                            {
+                                "text": "    let foo = 42;",
+                                "highlight_start": 9,
+                                "highlight_end": 12
+                            }
+                        ],
+                        "label": "unused variable",
+                        "suggested_replacement": null,
+                        "suggestion_applicability": null,
+                        "expansion": null
+                    }
+                ],
+                "children": [
+                    {
+                        "message": "\u001b[33mconsider prefixing with an underscore\u001b[0m",
+                        "code": null,
+                        "level": "help",
+                        "spans": [],
+                        "children": [],
+                        "rendered": null
+                    }
+                ],
+                "rendered": "\u001b[1m\u001b[33mwarning\u001b[0m: unused variable: `foo`\n"
+            }"##,
+            expect_file!["./test_data/strips_ansi_codes_from_diagnostic_text.txt"],
+        );
+    }
 }
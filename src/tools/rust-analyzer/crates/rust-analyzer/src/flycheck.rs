@@ -5,7 +5,7 @@ use std::{fmt, io, process::Command, time::Duration};
 
 use crossbeam_channel::{select_biased, unbounded, Receiver, Sender};
 use paths::{AbsPath, AbsPathBuf, Utf8PathBuf};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde::Deserialize;
 
 pub(crate) use cargo_metadata::diagnostic::{
@@ -33,12 +33,16 @@ pub(crate) enum InvocationLocation {
 pub(crate) struct CargoOptions {
     pub(crate) target_triples: Vec<String>,
     pub(crate) all_targets: bool,
+    pub(crate) tests: bool,
+    pub(crate) benches: bool,
+    pub(crate) examples: bool,
     pub(crate) no_default_features: bool,
     pub(crate) all_features: bool,
     pub(crate) features: Vec<String>,
     pub(crate) extra_args: Vec<String>,
     pub(crate) extra_env: FxHashMap<String, String>,
     pub(crate) target_dir: Option<Utf8PathBuf>,
+    pub(crate) keep_going: bool,
 }
 
 impl CargoOptions {
@@ -48,6 +52,16 @@ impl CargoOptions {
         }
         if self.all_targets {
             cmd.arg("--all-targets");
+        } else {
+            if self.tests {
+                cmd.arg("--tests");
+            }
+            if self.benches {
+                cmd.arg("--benches");
+            }
+            if self.examples {
+                cmd.arg("--examples");
+            }
         }
         if self.all_features {
             cmd.arg("--all-features");
@@ -61,7 +75,14 @@ impl CargoOptions {
             }
         }
         if let Some(target_dir) = &self.target_dir {
-            cmd.arg("--target-dir").arg(target_dir);
+            // A `CARGO_TARGET_DIR` from `extraEnv` takes precedence over the
+            // target dir configured in rust-analyzer's own settings.
+            if !self.extra_env.contains_key("CARGO_TARGET_DIR") {
+                cmd.arg("--target-dir").arg(target_dir);
+            }
+        }
+        if self.keep_going {
+            cmd.arg("--keep-going");
         }
         cmd.envs(&self.extra_env);
     }
@@ -114,9 +135,19 @@ impl FlycheckHandle {
         sysroot_root: Option<AbsPathBuf>,
         workspace_root: AbsPathBuf,
         manifest_path: Option<AbsPathBuf>,
+        default_members: Option<Vec<String>>,
+        member_manifests: FxHashSet<String>,
     ) -> FlycheckHandle {
-        let actor =
-            FlycheckActor::new(id, sender, config, sysroot_root, workspace_root, manifest_path);
+        let actor = FlycheckActor::new(
+            id,
+            sender,
+            config,
+            sysroot_root,
+            workspace_root,
+            manifest_path,
+            default_members,
+            member_manifests,
+        );
         let (sender, receiver) = unbounded::<StateChange>();
         let thread = stdx::thread::Builder::new(stdx::thread::ThreadIntent::Worker)
             .name("Flycheck".to_owned())
@@ -127,13 +158,16 @@ impl FlycheckHandle {
 
     /// Schedule a re-start of the cargo check worker to do a workspace wide check.
     pub(crate) fn restart_workspace(&self, saved_file: Option<AbsPathBuf>) {
-        self.sender.send(StateChange::Restart { package: None, saved_file }).unwrap();
+        self.sender.send(StateChange::Restart { packages: None, saved_file }).unwrap();
     }
 
-    /// Schedule a re-start of the cargo check worker to do a package wide check.
-    pub(crate) fn restart_for_package(&self, package: String) {
+    /// Schedule a re-start of the cargo check worker to check only the given packages.
+    ///
+    /// Packages accumulate across scoped restarts until a check run completes, so saves
+    /// in different packages that race with an in-flight check are not lost.
+    pub(crate) fn restart_for_packages(&self, packages: Vec<String>) {
         self.sender
-            .send(StateChange::Restart { package: Some(package), saved_file: None })
+            .send(StateChange::Restart { packages: Some(packages), saved_file: None })
             .unwrap();
     }
 
@@ -184,14 +218,16 @@ impl fmt::Debug for FlycheckMessage {
 #[derive(Debug)]
 pub(crate) enum Progress {
     DidStart,
-    DidCheckCrate(String),
+    /// A crate was checked, together with the number of workspace packages
+    /// compiled so far out of the total, when the run is workspace wide.
+    DidCheckCrate(String, Option<(usize, usize)>),
     DidFinish(io::Result<()>),
     DidCancel,
     DidFailToRestart(String),
 }
 
 enum StateChange {
-    Restart { package: Option<String>, saved_file: Option<AbsPathBuf> },
+    Restart { packages: Option<Vec<String>>, saved_file: Option<AbsPathBuf> },
     Cancel,
 }
 
@@ -206,6 +242,18 @@ struct FlycheckActor {
     /// or the project root of the project.
     root: AbsPathBuf,
     sysroot_root: Option<AbsPathBuf>,
+    /// The workspace's `default-members`, if any. A workspace wide check is
+    /// scoped to these packages, matching what `cargo check` does when run
+    /// from the workspace root.
+    default_members: Option<Vec<String>>,
+    /// Manifest paths of the workspace's member packages, used to tell
+    /// workspace packages apart from dependencies when counting check
+    /// progress.
+    member_manifests: FxHashSet<String>,
+    /// Manifest paths of the members compiled so far in the current run.
+    /// `None` while the run is scoped to explicit packages, where a count
+    /// against the member total would mislead.
+    checked_manifests: Option<FxHashSet<String>>,
     /// CargoHandle exists to wrap around the communication needed to be able to
     /// run `cargo check` without blocking. Currently the Rust standard library
     /// doesn't provide a way to read sub-process output without blocking, so we
@@ -214,6 +262,9 @@ struct FlycheckActor {
     command_handle: Option<CommandHandle<CargoCheckMessage>>,
     /// The receiver side of the channel mentioned above.
     command_receiver: Option<Receiver<CargoCheckMessage>>,
+    /// Packages requested by scoped restarts that have not been covered by a finished
+    /// check run yet.
+    pending_packages: FxHashSet<String>,
 
     status: FlycheckStatus,
 }
@@ -240,6 +291,8 @@ impl FlycheckActor {
         sysroot_root: Option<AbsPathBuf>,
         workspace_root: AbsPathBuf,
         manifest_path: Option<AbsPathBuf>,
+        default_members: Option<Vec<String>>,
+        member_manifests: FxHashSet<String>,
     ) -> FlycheckActor {
         tracing::info!(%id, ?workspace_root, "Spawning flycheck");
         FlycheckActor {
@@ -249,8 +302,12 @@ impl FlycheckActor {
             sysroot_root,
             root: workspace_root,
             manifest_path,
+            default_members,
+            member_manifests,
+            checked_manifests: None,
             command_handle: None,
             command_receiver: None,
+            pending_packages: FxHashSet::default(),
             status: FlycheckStatus::Finished,
         }
     }
@@ -278,7 +335,7 @@ impl FlycheckActor {
                     tracing::debug!(flycheck_id = self.id, "flycheck cancelled");
                     self.cancel_check_process();
                 }
-                Event::RequestStateChange(StateChange::Restart { package, saved_file }) => {
+                Event::RequestStateChange(StateChange::Restart { packages, saved_file }) => {
                     // Cancel the previously spawned process
                     self.cancel_check_process();
                     while let Ok(restart) = inbox.recv_timeout(Duration::from_millis(50)) {
@@ -288,8 +345,24 @@ impl FlycheckActor {
                         }
                     }
 
+                    let packages = match packages {
+                        Some(packages) => {
+                            self.pending_packages.extend(packages);
+                            let mut packages =
+                                self.pending_packages.iter().cloned().collect::<Vec<_>>();
+                            packages.sort_unstable();
+                            Some(packages)
+                        }
+                        // A workspace wide check covers everything, so pending scoped
+                        // requests are subsumed by it.
+                        None => {
+                            self.pending_packages.clear();
+                            None
+                        }
+                    };
+
                     let command =
-                        match self.check_command(package.as_deref(), saved_file.as_deref()) {
+                        match self.check_command(packages.as_deref(), saved_file.as_deref()) {
                             Some(c) => c,
                             None => continue,
                         };
@@ -302,6 +375,7 @@ impl FlycheckActor {
                             tracing::debug!(command = formatted_command, "did restart flycheck");
                             self.command_handle = Some(command_handle);
                             self.command_receiver = Some(receiver);
+                            self.checked_manifests = packages.is_none().then(FxHashSet::default);
                             self.report_progress(Progress::DidStart);
                             self.status = FlycheckStatus::Started;
                         }
@@ -332,6 +406,9 @@ impl FlycheckActor {
                     if self.status == FlycheckStatus::Started {
                         self.send(FlycheckMessage::ClearDiagnostics { id: self.id });
                     }
+                    // The run that just finished covered all accumulated packages.
+                    self.pending_packages.clear();
+                    self.checked_manifests = None;
                     self.report_progress(Progress::DidFinish(res));
                     self.status = FlycheckStatus::Finished;
                 }
@@ -342,7 +419,16 @@ impl FlycheckActor {
                             artifact = msg.target.name,
                             "artifact received"
                         );
-                        self.report_progress(Progress::DidCheckCrate(msg.target.name));
+                        let packages = match &mut self.checked_manifests {
+                            Some(checked) if !self.member_manifests.is_empty() => {
+                                if self.member_manifests.contains(msg.manifest_path.as_str()) {
+                                    checked.insert(msg.manifest_path.to_string());
+                                }
+                                Some((checked.len(), self.member_manifests.len()))
+                            }
+                            _ => None,
+                        };
+                        self.report_progress(Progress::DidCheckCrate(msg.target.name, packages));
                     }
 
                     CargoCheckMessage::Diagnostic(msg) => {
@@ -376,6 +462,7 @@ impl FlycheckActor {
             );
             command_handle.cancel();
             self.command_receiver.take();
+            self.checked_manifests = None;
             self.report_progress(Progress::DidCancel);
             self.status = FlycheckStatus::Finished;
         }
@@ -386,7 +473,7 @@ impl FlycheckActor {
     /// return None.
     fn check_command(
         &self,
-        package: Option<&str>,
+        packages: Option<&[String]>,
         saved_file: Option<&AbsPath>,
     ) -> Option<Command> {
         match &self.config {
@@ -398,9 +485,15 @@ impl FlycheckActor {
                 cmd.arg(command);
                 cmd.current_dir(&self.root);
 
-                match package {
-                    Some(pkg) => cmd.arg("-p").arg(pkg),
-                    None => cmd.arg("--workspace"),
+                match packages.or(self.default_members.as_deref()) {
+                    Some(packages) => {
+                        for package in packages {
+                            cmd.arg("-p").arg(package);
+                        }
+                    }
+                    None => {
+                        cmd.arg("--workspace");
+                    }
                 };
 
                 cmd.arg(if *ansi_color_output {
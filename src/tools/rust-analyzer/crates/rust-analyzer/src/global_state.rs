@@ -5,9 +5,12 @@
 
 use std::{ops::Not as _, time::Instant};
 
+use cfg::CfgAtom;
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use hir::ChangeWithProcMacros;
-use ide::{Analysis, AnalysisHost, Cancellable, FileId, SourceRootId};
+use ide::{
+    Analysis, AnalysisHost, Cancellable, FileId, FilePosition, FileRange, SourceRootId, TextSize,
+};
 use ide_db::base_db::{CrateId, ProcMacroPaths, SourceDatabase, SourceRootDatabase};
 use itertools::Itertools;
 use load_cargo::SourceRootConfig;
@@ -20,20 +23,21 @@ use parking_lot::{
 use proc_macro_api::ProcMacroServer;
 use project_model::{ManifestPath, ProjectWorkspace, ProjectWorkspaceKind, WorkspaceBuildScripts};
 use rustc_hash::{FxHashMap, FxHashSet};
+use syntax::{algo, ast, AstNode};
 use tracing::{span, trace, Level};
 use triomphe::Arc;
 use vfs::{AbsPathBuf, AnchoredPathBuf, ChangeKind, Vfs, VfsPath};
 
 use crate::{
-    config::{Config, ConfigChange, ConfigErrors, RatomlFileKind},
+    config::{Config, ConfigChange, ConfigErrors, FileEncoding, RatomlFileKind},
     diagnostics::{CheckFixes, DiagnosticCollection},
     discover,
     flycheck::{FlycheckHandle, FlycheckMessage},
     line_index::{LineEndings, LineIndex},
-    lsp::{from_proto, to_proto::url_from_abs_path},
+    lsp::{from_proto, to_proto, to_proto::url_from_abs_path},
     lsp_ext,
     main_loop::Task,
-    mem_docs::MemDocs,
+    mem_docs::{DocumentData, MemDocs},
     op_queue::{Cause, OpQueue},
     reload,
     target_spec::{CargoTargetSpec, ProjectJsonTargetSpec, TargetSpec},
@@ -69,30 +73,75 @@ pub(crate) struct GlobalState {
 
     pub(crate) task_pool: Handle<TaskPool<Task>, Receiver<Task>>,
     pub(crate) fmt_pool: Handle<TaskPool<Task>, Receiver<Task>>,
+    pub(crate) diagnostics_pool: Handle<TaskPool<Task>, Receiver<Task>>,
 
     pub(crate) config: Arc<Config>,
     pub(crate) config_errors: Option<ConfigErrors>,
     pub(crate) analysis_host: AnalysisHost,
     pub(crate) diagnostics: DiagnosticCollection,
+    /// Compiled `diagnostics_messageReplacements` patterns, rebuilt whenever
+    /// the configuration changes.
+    pub(crate) diagnostics_message_replacements: Arc<Vec<(regex::Regex, String)>>,
     pub(crate) mem_docs: MemDocs,
+    /// The document the user is currently looking at, as reported by the
+    /// `rust-analyzer/focusedDocument` notification or, failing that, inferred
+    /// from the last opened or edited document. Background work such as
+    /// diagnostics is scheduled for it first.
+    pub(crate) focused_document: Option<vfs::VfsPath>,
     pub(crate) source_root_config: SourceRootConfig,
     /// A mapping that maps a local source root's `SourceRootId` to it parent's `SourceRootId`, if it has one.
     pub(crate) local_roots_parent_map: Arc<FxHashMap<SourceRootId, SourceRootId>>,
     pub(crate) semantic_tokens_cache: Arc<Mutex<FxHashMap<Url, SemanticTokens>>>,
+    /// Labels of recently accepted completions, most recent last, feeding the
+    /// recency scoring weight of subsequent completion requests.
+    pub(crate) recent_completions: Arc<Mutex<Vec<String>>>,
+    /// Session-scoped cfg overrides set via `rust-analyzer/setCfgOverrides`,
+    /// keyed by crate display name with `None` applying to all crates. They
+    /// are layered over the configured cfgs when the workspace is loaded.
+    pub(crate) cfg_overrides: FxHashMap<Option<String>, Vec<CfgAtom>>,
 
     // status
     pub(crate) shutdown_requested: bool,
     pub(crate) last_reported_status: Option<lsp_ext::ServerStatusParams>,
+    /// Dispatch time of the pending request we last warned about, so each
+    /// stuck request triggers the warning only once.
+    pub(crate) pending_request_warned: Option<Instant>,
+    /// Whether the task pool backlog has grown past the shedding threshold, in
+    /// which case non-essential background work is deferred until it drains.
+    pub(crate) task_pool_saturated: bool,
+    /// Set when a diagnostics refresh was shed due to a saturated task pool,
+    /// so it can be rerun once the backlog drains.
+    pub(crate) deferred_diagnostics_refresh: bool,
+    /// Set when `diagnostics.onStartup` is `deferred`; suppresses diagnostics
+    /// refreshes until the user opens or edits a document.
+    pub(crate) startup_diagnostics_deferred: bool,
+    /// Opt-in sink every incoming request is echoed to, for debugging against
+    /// real editors. Enabled by pointing the `RA_ECHO_REQUESTS` env var at a
+    /// file path.
+    pub(crate) request_echo_sink: Option<Arc<Mutex<std::fs::File>>>,
 
     // proc macros
     pub(crate) proc_macro_clients: Arc<[anyhow::Result<ProcMacroServer>]>,
     pub(crate) build_deps_changed: bool,
+    /// Files that currently have failed macro expansion diagnostics. They are
+    /// re-touched when new proc-macros are loaded, so a restarted proc-macro
+    /// server retries the expansions instead of serving the cached failure.
+    pub(crate) proc_macro_failed_files: FxHashSet<FileId>,
 
     // Flycheck
     pub(crate) flycheck: Arc<[FlycheckHandle]>,
     pub(crate) flycheck_sender: Sender<FlycheckMessage>,
     pub(crate) flycheck_receiver: Receiver<FlycheckMessage>,
     pub(crate) last_flycheck_error: Option<String>,
+    /// Error from the startup `cargo --version`/`rustc --version` probe, if
+    /// any. Surfaced in the server status so that a missing or broken
+    /// toolchain shows up as an actionable message instead of everything
+    /// silently not working.
+    pub(crate) toolchain_self_check_error: Option<String>,
+    /// Proc-macro dylibs the server refused to load because they were built
+    /// by a different toolchain, with the expected and found versions.
+    /// Expansion stays disabled for them until the next proc-macro reload.
+    pub(crate) proc_macro_abi_mismatches: Vec<String>,
 
     // Test explorer
     pub(crate) test_run_session: Option<Vec<CargoTestHandle>>,
@@ -141,6 +190,9 @@ pub(crate) struct GlobalState {
     pub(crate) workspaces: Arc<Vec<ProjectWorkspace>>,
     pub(crate) crate_graph_file_dependencies: FxHashSet<vfs::VfsPath>,
     pub(crate) detached_files: FxHashSet<ManifestPath>,
+    /// Manifests we have published workspace loading error diagnostics for, so
+    /// they can be cleared once the workspace loads again.
+    pub(crate) workspace_fetch_diagnostics_sent: FxHashSet<Url>,
 
     // op queues
     pub(crate) fetch_workspaces_queue:
@@ -170,6 +222,7 @@ pub(crate) struct GlobalStateSnapshot {
     pub(crate) check_fixes: CheckFixes,
     mem_docs: MemDocs,
     pub(crate) semantic_tokens_cache: Arc<Mutex<FxHashMap<Url, SemanticTokens>>>,
+    pub(crate) recent_completions: Arc<Mutex<Vec<String>>>,
     vfs: Arc<RwLock<(vfs::Vfs, IntMap<FileId, LineEndings>)>>,
     pub(crate) workspaces: Arc<Vec<ProjectWorkspace>>,
     // used to signal semantic highlighting to fall back to syntax based highlighting until
@@ -177,6 +230,22 @@ pub(crate) struct GlobalStateSnapshot {
     // FIXME: Can we derive this from somewhere else?
     pub(crate) proc_macros_loaded: bool,
     pub(crate) flycheck: Arc<[FlycheckHandle]>,
+    pub(crate) pending_request_stats: PendingRequestStats,
+    /// Number of tasks queued in the task pool when the snapshot was taken.
+    pub(crate) task_pool_backlog: usize,
+    /// Counters reported by the vfs loader when the snapshot was taken.
+    pub(crate) vfs_loader_stats: vfs::loader::Stats,
+    /// The status last reported to the client, `None` until the first report.
+    pub(crate) server_status: Option<lsp_ext::ServerStatusParams>,
+}
+
+/// Statistics about the incoming requests that have been dispatched but not
+/// answered yet, surfaced through `rust-analyzer/analyzerStatus`.
+#[derive(Clone)]
+pub(crate) struct PendingRequestStats {
+    pub(crate) count: usize,
+    /// Method name and dispatch time of the oldest in-flight request.
+    pub(crate) oldest: Option<(String, Instant)>,
 }
 
 impl std::panic::UnwindSafe for GlobalStateSnapshot {}
@@ -200,6 +269,13 @@ impl GlobalState {
             let handle = TaskPool::new_with_threads(sender, 1);
             Handle { handle, receiver }
         };
+        // Diagnostics get their own pool so that a workspace-wide recompute
+        // never occupies the threads interactive requests are served on.
+        let diagnostics_pool = {
+            let (sender, receiver) = unbounded();
+            let handle = TaskPool::new_with_threads(sender, config.diagnostics_num_threads());
+            Handle { handle, receiver }
+        };
 
         let task_queue = {
             let (sender, receiver) = unbounded();
@@ -207,6 +283,9 @@ impl GlobalState {
         };
 
         let mut analysis_host = AnalysisHost::new(config.lru_parse_query_capacity());
+        if let Some(capacity) = config.lru_macro_expansion_capacity() {
+            analysis_host.update_macro_expansion_lru_capacity(capacity);
+        }
         if let Some(capacities) = config.lru_query_capacities_config() {
             analysis_host.update_lru_capacities(capacities);
         }
@@ -220,14 +299,32 @@ impl GlobalState {
             req_queue: ReqQueue::default(),
             task_pool,
             fmt_pool,
+            diagnostics_pool,
             loader,
             config: Arc::new(config.clone()),
             analysis_host,
             diagnostics: Default::default(),
+            diagnostics_message_replacements: Default::default(),
             mem_docs: MemDocs::default(),
+            focused_document: None,
             semantic_tokens_cache: Arc::new(Default::default()),
+            recent_completions: Arc::new(Default::default()),
+            cfg_overrides: FxHashMap::default(),
             shutdown_requested: false,
             last_reported_status: None,
+            pending_request_warned: None,
+            task_pool_saturated: false,
+            deferred_diagnostics_refresh: false,
+            startup_diagnostics_deferred: config.diagnostics_on_startup_deferred(),
+            request_echo_sink: std::env::var("RA_ECHO_REQUESTS").ok().and_then(|path| {
+                match std::fs::File::create(&path) {
+                    Ok(file) => Some(Arc::new(Mutex::new(file))),
+                    Err(err) => {
+                        tracing::error!("failed to create request echo file {path}: {err}");
+                        None
+                    }
+                }
+            }),
             source_root_config: SourceRootConfig::default(),
             local_roots_parent_map: Arc::new(FxHashMap::default()),
             config_errors: Default::default(),
@@ -235,11 +332,14 @@ impl GlobalState {
             proc_macro_clients: Arc::from_iter([]),
 
             build_deps_changed: false,
+            proc_macro_failed_files: FxHashSet::default(),
 
             flycheck: Arc::from_iter([]),
             flycheck_sender,
             flycheck_receiver,
             last_flycheck_error: None,
+            toolchain_self_check_error: None,
+            proc_macro_abi_mismatches: Vec::new(),
 
             test_run_session: None,
             test_run_sender,
@@ -259,6 +359,7 @@ impl GlobalState {
             workspaces: Arc::from(Vec::new()),
             crate_graph_file_dependencies: FxHashSet::default(),
             detached_files: FxHashSet::default(),
+            workspace_fetch_diagnostics_sent: FxHashSet::default(),
             fetch_workspaces_queue: OpQueue::default(),
             fetch_build_data_queue: OpQueue::default(),
             fetch_proc_macros_queue: OpQueue::default(),
@@ -300,6 +401,7 @@ impl GlobalState {
             let mut has_structure_changes = false;
             let mut bytes = vec![];
             let mut modified_rust_files = vec![];
+            let encoding = self.config.files().encoding;
             for file in changed_files.into_values() {
                 let vfs_path = vfs.file_path(file.file_id);
                 if let Some(("rust-analyzer", Some("toml"))) = vfs_path.name_and_extension() {
@@ -308,7 +410,7 @@ impl GlobalState {
                 }
 
                 if let Some(path) = vfs_path.as_path() {
-                    has_structure_changes |= file.is_created_or_deleted();
+                    has_structure_changes |= file.changes_structure();
 
                     if file.is_modified() && path.extension() == Some("rs") {
                         modified_rust_files.push(file.file_id);
@@ -323,14 +425,15 @@ impl GlobalState {
                         .unwrap_or_default();
 
                     let path = path.to_path_buf();
-                    if file.is_created_or_deleted() {
+                    if file.changes_structure() {
                         workspace_structure_change.get_or_insert((path, false)).1 |=
                             self.crate_graph_file_dependencies.contains(vfs_path);
                     } else if reload::should_refresh_for_change(
                         &path,
                         file.kind(),
                         &additional_files,
-                    ) {
+                    ) || self.is_workspace_env_file(&path)
+                    {
                         trace!(?path, kind = ?file.kind(), "refreshing for a change");
                         workspace_structure_change.get_or_insert((path.clone(), false));
                     }
@@ -341,9 +444,15 @@ impl GlobalState {
                     self.diagnostics.clear_native_for(file.file_id);
                 }
 
+                if let vfs::Change::Rename = file.change {
+                    // The content is unchanged, so leave the file text alone;
+                    // the rebuilt source roots pick up the new path.
+                    continue;
+                }
+
                 let text =
                     if let vfs::Change::Create(v, _) | vfs::Change::Modify(v, _) = file.change {
-                        String::from_utf8(v).ok().map(|text| {
+                        decode_text(v, encoding).map(|text| {
                             // FIXME: Consider doing normalization in the `vfs` instead? That allows
                             // getting rid of some locking
                             let (text, line_endings) = LineEndings::normalize(text);
@@ -488,6 +597,16 @@ impl GlobalState {
         true
     }
 
+    /// Like [`GlobalState::snapshot`], but returns `None` when there are unapplied vfs
+    /// changes queued, i.e. when the snapshot would be stale and the first query against
+    /// it is bound to be cancelled. Use this as a cheap pre-check to skip doomed work.
+    pub(crate) fn try_snapshot(&self) -> Option<GlobalStateSnapshot> {
+        if self.vfs.read().0.has_changes() {
+            return None;
+        }
+        Some(self.snapshot())
+    }
+
     pub(crate) fn snapshot(&self) -> GlobalStateSnapshot {
         GlobalStateSnapshot {
             config: Arc::clone(&self.config),
@@ -497,12 +616,45 @@ impl GlobalState {
             check_fixes: Arc::clone(&self.diagnostics.check_fixes),
             mem_docs: self.mem_docs.clone(),
             semantic_tokens_cache: Arc::clone(&self.semantic_tokens_cache),
+            recent_completions: Arc::clone(&self.recent_completions),
             proc_macros_loaded: !self.config.expand_proc_macros()
                 || *self.fetch_proc_macros_queue.last_op_result(),
             flycheck: self.flycheck.clone(),
+            pending_request_stats: PendingRequestStats {
+                count: self.req_queue.incoming.iter().count(),
+                oldest: self.oldest_pending_request(),
+            },
+            task_pool_backlog: self.task_pool.handle.len(),
+            vfs_loader_stats: self.loader.handle.stats(),
+            server_status: self.last_reported_status.clone(),
         }
     }
 
+    /// Returns the method name and dispatch time of the oldest incoming
+    /// request that has not been answered yet.
+    pub(crate) fn oldest_pending_request(&self) -> Option<(String, Instant)> {
+        self.req_queue
+            .incoming
+            .iter()
+            .map(|(_, (method, start))| (method.clone(), *start))
+            .min_by_key(|&(_, start)| start)
+    }
+
+    /// Returns the id and method name of every incoming request that has been
+    /// pending for at least `timeout`.
+    pub(crate) fn requests_pending_for(
+        &self,
+        timeout: std::time::Duration,
+    ) -> Vec<(lsp_server::RequestId, String)> {
+        let now = Instant::now();
+        self.req_queue
+            .incoming
+            .iter()
+            .filter(|(_, (_, start))| now.saturating_duration_since(*start) >= timeout)
+            .map(|(id, (method, _))| (id.clone(), method.clone()))
+            .collect()
+    }
+
     pub(crate) fn send_request<R: lsp_types::request::Request>(
         &mut self,
         params: R::Params,
@@ -540,7 +692,7 @@ impl GlobalState {
     }
 
     pub(crate) fn respond(&mut self, response: lsp_server::Response) {
-        if let Some((method, start)) = self.req_queue.incoming.complete(response.id.clone()) {
+        if let Some((method, start)) = self.req_queue.incoming.complete(&response.id) {
             if let Some(err) = &response.error {
                 if err.message.starts_with("server panicked") {
                     self.poke_rust_analyzer_developer(format!("{}, check the log", err.message))
@@ -559,6 +711,16 @@ impl GlobalState {
         }
     }
 
+    /// Cancels every pending incoming request, responding to each with an LSP cancelled
+    /// error, and requests cancellation of all in-flight salsa queries.
+    pub(crate) fn cancel_all(&mut self) {
+        let pending = self.req_queue.incoming.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>();
+        for request_id in pending {
+            self.cancel(request_id);
+        }
+        self.analysis_host.request_cancellation();
+    }
+
     pub(crate) fn is_completed(&self, request: &lsp_server::Request) -> bool {
         self.req_queue.incoming.is_completed(&request.id)
     }
@@ -577,6 +739,7 @@ impl GlobalState {
         // We put this on a separate thread to avoid blocking the main thread with serialization work
         self.task_pool.handle.spawn_with_sender(stdx::thread::ThreadIntent::Worker, {
             let sender = self.sender.clone();
+            let message_replacements = self.diagnostics_message_replacements.clone();
             move |_| {
                 // VSCode assumes diagnostic messages to be non-empty strings, so we need to patch
                 // empty diagnostics. Neither the docs of VSCode nor the LSP spec say whether
@@ -593,10 +756,24 @@ impl GlobalState {
                     }
                 };
 
+                // Opt-in rewrite of the messages before they go out; ranges and
+                // codes are deliberately left untouched.
+                let rewrite = |message: &mut String| {
+                    for (pattern, replacement) in message_replacements.iter() {
+                        if let std::borrow::Cow::Owned(rewritten) =
+                            pattern.replace_all(message, replacement.as_str())
+                        {
+                            *message = rewritten;
+                        }
+                    }
+                };
+
                 for d in &mut diagnostics {
+                    rewrite(&mut d.message);
                     patch_empty(&mut d.message);
                     if let Some(dri) = &mut d.related_information {
                         for dri in dri {
+                            rewrite(&mut dri.message);
                             patch_empty(&mut dri.message);
                         }
                     }
@@ -647,6 +824,57 @@ impl GlobalStateSnapshot {
         Some(self.mem_docs.get(&path)?.version)
     }
 
+    pub(crate) fn open_documents(&self) -> impl Iterator<Item = (&VfsPath, &DocumentData)> {
+        self.mem_docs.iter().filter_map(|path| Some((path, self.mem_docs.get(path)?)))
+    }
+
+    /// Returns the rendered markdown documentation of the item referenced at
+    /// the given position, a docs-only subset of hover for doc-preview
+    /// consumers.
+    #[allow(dead_code)]
+    pub(crate) fn docs_for_position(&self, file_id: FileId, offset: TextSize) -> Option<String> {
+        self.analysis.docs_for_position(FilePosition { file_id, offset }).ok().flatten()
+    }
+
+    /// Returns each unresolved `use` path in the file together with whether an
+    /// importable item of the same name exists elsewhere, for bulk autoimport
+    /// or removal tooling.
+    #[allow(dead_code)]
+    pub(crate) fn unresolved_imports(&self, file_id: FileId) -> Vec<ide::UnresolvedImport> {
+        self.analysis.unresolved_imports(file_id).unwrap_or_default()
+    }
+
+    /// Remembers that the client resolved -- and so most likely accepted -- the
+    /// completion with the given label, feeding the recency scoring weight of
+    /// subsequent completion requests.
+    pub(crate) fn note_accepted_completion(&self, label: &str) {
+        const CAP: usize = 32;
+        let mut recent = self.recent_completions.lock();
+        recent.retain(|it| it != label);
+        recent.push(label.to_owned());
+        if recent.len() > CAP {
+            recent.remove(0);
+        }
+    }
+
+    /// Returns the location of the innermost `impl` block containing the given
+    /// position, for "go to containing impl" style breadcrumb navigation.
+    /// `None` when the position is not inside any impl.
+    #[allow(dead_code)]
+    pub(crate) fn enclosing_impl(
+        &self,
+        file_id: FileId,
+        offset: TextSize,
+    ) -> Option<lsp_types::Location> {
+        let file = self.analysis.parse(file_id).ok()?;
+        // `find_node_at_offset` walks outwards from the token at the offset,
+        // so the first impl it yields is the innermost one, which is what we
+        // want for e.g. an impl nested inside a function body.
+        let impl_ = algo::find_node_at_offset::<ast::Impl>(file.syntax(), offset)?;
+        let frange = FileRange { file_id, range: impl_.syntax().text_range() };
+        to_proto::location(self, frange).ok()
+    }
+
     pub(crate) fn anchored_path(&self, path: &AnchoredPathBuf) -> Url {
         let mut base = self.vfs_read().file_path(path.anchor).clone();
         base.pop();
@@ -711,6 +939,28 @@ impl GlobalStateSnapshot {
     pub(crate) fn file_exists(&self, file_id: FileId) -> bool {
         self.vfs.read().0.exists(file_id)
     }
+
+    /// Returns the crates that transitively depend on the given crate, i.e. everything that
+    /// rebuilds when it changes. The queried crate itself is not part of the result.
+    pub(crate) fn reverse_dependents(&self, crate_id: CrateId) -> Cancellable<Vec<CrateId>> {
+        let rev_deps = self.analysis.transitive_rev_deps(crate_id)?;
+        Ok(rev_deps.into_iter().filter(|&it| it != crate_id).collect())
+    }
+}
+
+/// Decodes loaded file contents into UTF-8, applying the configured fallback
+/// encoding when the bytes are not valid UTF-8. The database only ever sees
+/// UTF-8; clients applying returned edits are responsible for writing the file
+/// back in its original encoding.
+fn decode_text(bytes: Vec<u8>, encoding: FileEncoding) -> Option<String> {
+    match String::from_utf8(bytes) {
+        Ok(text) => Some(text),
+        Err(err) if encoding == FileEncoding::Latin1 => {
+            // ISO-8859-1 maps each byte to the code point of the same value.
+            Some(err.into_bytes().iter().map(|&b| b as char).collect())
+        }
+        Err(_) => None,
+    }
 }
 
 pub(crate) fn file_id_to_url(vfs: &vfs::Vfs, id: FileId) -> Url {
@@ -28,8 +28,9 @@ use crate::{
 /// Some requests modify the state, and are run on the main thread to get
 /// `&mut` (`on_sync_mut`).
 ///
-/// Read-only requests are wrapped into `catch_unwind` -- they don't modify the
-/// state, so it's OK to recover from their failures.
+/// All handlers are wrapped into `catch_unwind` -- a panic fails only the
+/// request at hand with an internal error, while the server keeps serving the
+/// requests that follow.
 pub(crate) struct RequestDispatcher<'a> {
     pub(crate) req: Option<lsp_server::Request>,
     pub(crate) global_state: &'a mut GlobalState,
@@ -37,8 +38,8 @@ pub(crate) struct RequestDispatcher<'a> {
 
 impl RequestDispatcher<'_> {
     /// Dispatches the request onto the current thread, given full access to
-    /// mutable global state. Unlike all other methods here, this one isn't
-    /// guarded by `catch_unwind`, so, please, don't make bugs :-)
+    /// mutable global state. A panicking handler may leave that state partially
+    /// mutated, which we accept in exchange for keeping the server alive.
     pub(crate) fn on_sync_mut<R>(
         &mut self,
         f: fn(&mut GlobalState, R::Params) -> anyhow::Result<R::Result>,
@@ -55,13 +56,19 @@ impl RequestDispatcher<'_> {
         let _guard =
             tracing::info_span!("request", method = ?req.method, "request_id" = ?req.id).entered();
         tracing::debug!(?params);
-        let result = {
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
             let _pctx = stdx::panic_context::enter(panic_context);
             f(self.global_state, params)
+        }));
+        let response = match thread_result_to_response::<R>(req.id.clone(), result) {
+            Ok(response) => response,
+            Err(_cancelled) => lsp_server::Response::new_err(
+                req.id,
+                lsp_server::ErrorCode::ContentModified as i32,
+                "content modified".to_owned(),
+            ),
         };
-        if let Ok(response) = result_to_response::<R>(req.id, result) {
-            self.global_state.respond(response);
-        }
+        self.global_state.respond(response);
 
         self
     }
@@ -197,7 +204,17 @@ impl RequestDispatcher<'_> {
             tracing::info_span!("request", method = ?req.method, "request_id" = ?req.id).entered();
         tracing::debug!(?params);
 
-        let world = self.global_state.snapshot();
+        let world = match self.global_state.try_snapshot() {
+            Some(it) => it,
+            // The snapshot would be stale and the first query against it is bound to be
+            // cancelled, so don't bother spawning the work and retry the request once the
+            // pending changes have been applied.
+            None if ALLOW_RETRYING => {
+                self.global_state.task_pool.handle.spawn(intent, move || Task::Retry(req));
+                return self;
+            }
+            None => self.global_state.snapshot(),
+        };
         if MAIN_POOL {
             &mut self.global_state.task_pool.handle
         } else {
@@ -260,6 +277,14 @@ where
     match result {
         Ok(result) => result_to_response::<R>(id, result),
         Err(panic) => {
+            // Salsa unwinds to cancel a query against a stale revision. That's
+            // not a handler bug, so route it through the cancellation path
+            // instead of reporting an internal error.
+            let panic = match panic.downcast::<Cancelled>() {
+                Ok(cancelled) => return Err(*cancelled),
+                Err(panic) => panic,
+            };
+
             let panic_message = panic
                 .downcast_ref::<String>()
                 .map(String::as_str)
@@ -270,6 +295,10 @@ where
                 message.push_str(": ");
                 message.push_str(panic_message)
             };
+            match stdx::panic_context::take_backtrace() {
+                Some(backtrace) => tracing::error!("{message}\n{backtrace}"),
+                None => tracing::error!("{message}"),
+            }
 
             Ok(lsp_server::Response::new_err(
                 id,
@@ -57,6 +57,12 @@ pub(crate) fn handle_did_open_text_document(
 ) -> anyhow::Result<()> {
     let _p = tracing::info_span!("handle_did_open_text_document").entered();
 
+    // A file opened after the workspace has loaded counts as the first
+    // interaction; files restored by the editor during startup do not.
+    if state.is_quiescent() {
+        state.startup_diagnostics_deferred = false;
+    }
+
     if let Ok(path) = from_proto::vfs_path(&params.text_document.uri) {
         let already_exists = state
             .mem_docs
@@ -72,7 +78,21 @@ pub(crate) fn handle_did_open_text_document(
             tracing::error!("duplicate DidOpenTextDocument: {}", path);
         }
 
+        if let Some(limit) = state.config.files_open_documents_limit() {
+            let open_documents = state.mem_docs.len();
+            if open_documents > limit.get() {
+                // A soft cap only: the overlay is kept so nothing breaks, but a
+                // misbehaving client is made visible in the logs.
+                tracing::warn!(
+                    "Open document limit exceeded ({open_documents} > {limit}): newest overlay is {path}"
+                );
+            }
+        }
+
         tracing::info!("New file content set {:?}", params.text_document.text);
+        // Opening a document is the best focus hint we get from clients that
+        // don't send `rust-analyzer/focusedDocument`.
+        state.focused_document = Some(path.clone());
         state.vfs.write().0.set_file_contents(path, Some(params.text_document.text.into_bytes()));
         if state.config.discover_workspace_config().is_some() {
             tracing::debug!("queuing task");
@@ -91,6 +111,8 @@ pub(crate) fn handle_did_change_text_document(
 ) -> anyhow::Result<()> {
     let _p = tracing::info_span!("handle_did_change_text_document").entered();
 
+    state.startup_diagnostics_deferred = false;
+
     if let Ok(path) = from_proto::vfs_path(&params.text_document.uri) {
         let Some(DocumentData { version, data }) = state.mem_docs.get_mut(&path) else {
             tracing::error!(?path, "unexpected DidChangeTextDocument");
@@ -99,6 +121,7 @@ pub(crate) fn handle_did_change_text_document(
         // The version passed in DidChangeTextDocument is the version after all edits are applied
         // so we should apply it before the vfs is notified.
         *version = params.text_document.version;
+        state.focused_document = Some(path.clone());
 
         let new_contents = apply_document_changes(
             state.config.negotiated_encoding(),
@@ -160,7 +183,9 @@ pub(crate) fn handle_did_save_text_document(
 
             // FIXME: We should move this check into a QueuedTask and do semantic resolution of
             // the files. There is only so much we can tell syntactically from the path.
-            if reload::should_refresh_for_change(path, ChangeKind::Modify, additional_files) {
+            if reload::should_refresh_for_change(path, ChangeKind::Modify, additional_files)
+                || state.is_workspace_env_file(path)
+            {
                 state.fetch_workspaces_queue.request_op(
                     format!("workspace vfs file change saved {path}"),
                     FetchWorkspaceRequest {
@@ -312,20 +337,32 @@ fn run_flycheck(state: &mut GlobalState, vfs_path: VfsPath) -> bool {
                 .collect::<ide::Cancellable<_>>()?;
             let crate_root_paths: Vec<_> = crate_root_paths.iter().map(Deref::deref).collect();
 
-            // Find all workspaces that have at least one target containing the saved file
+            // Find all workspaces that have at least one target containing the saved file,
+            // together with the affected packages: the ones containing the saved file and
+            // their reverse dependencies.
             let workspace_ids = world.workspaces.iter().enumerate().filter_map(|(idx, ws)| {
-                let package = match &ws.kind {
+                let packages = match &ws.kind {
                     project_model::ProjectWorkspaceKind::Cargo { cargo, .. }
                     | project_model::ProjectWorkspaceKind::DetachedFile {
                         cargo: Some((cargo, _)),
                         ..
-                    } => cargo.packages().find_map(|pkg| {
-                        let has_target_with_root = cargo[pkg]
-                            .targets
-                            .iter()
-                            .any(|&it| crate_root_paths.contains(&cargo[it].root.as_path()));
-                        has_target_with_root.then(|| cargo[pkg].name.clone())
-                    }),
+                    } => {
+                        let packages: Vec<_> = cargo
+                            .packages()
+                            .filter(|&pkg| {
+                                cargo[pkg]
+                                    .targets
+                                    .iter()
+                                    .any(|&it| crate_root_paths.contains(&cargo[it].root.as_path()))
+                            })
+                            .map(|pkg| cargo[pkg].name.clone())
+                            .collect();
+                        if packages.is_empty() {
+                            None
+                        } else {
+                            Some(packages)
+                        }
+                    }
                     project_model::ProjectWorkspaceKind::Json(project) => {
                         if !project.crates().any(|(_, krate)| {
                             crate_root_paths.contains(&krate.root_module.as_path())
@@ -336,18 +373,20 @@ fn run_flycheck(state: &mut GlobalState, vfs_path: VfsPath) -> bool {
                     }
                     project_model::ProjectWorkspaceKind::DetachedFile { .. } => return None,
                 };
-                Some((idx, package))
+                Some((idx, packages))
             });
 
             let saved_file = vfs_path.as_path().map(|p| p.to_owned());
 
             // Find and trigger corresponding flychecks
+            let scoped =
+                !world.config.flycheck_workspace() || world.config.flycheck_only_changed_packages();
             for flycheck in world.flycheck.iter() {
-                for (id, package) in workspace_ids.clone() {
+                for (id, packages) in workspace_ids.clone() {
                     if id == flycheck.id() {
                         updated = true;
-                        match package.filter(|_| !world.config.flycheck_workspace()) {
-                            Some(package) => flycheck.restart_for_package(package),
+                        match packages.filter(|_| scoped) {
+                            Some(packages) => flycheck.restart_for_packages(packages),
                             None => flycheck.restart_workspace(saved_file.clone()),
                         }
                         continue;
@@ -373,6 +412,12 @@ fn run_flycheck(state: &mut GlobalState, vfs_path: VfsPath) -> bool {
     }
 }
 
+pub(crate) fn handle_cancel_all(state: &mut GlobalState, _: ()) -> anyhow::Result<()> {
+    let _p = tracing::info_span!("handle_cancel_all").entered();
+    state.cancel_all();
+    Ok(())
+}
+
 pub(crate) fn handle_cancel_flycheck(state: &mut GlobalState, _: ()) -> anyhow::Result<()> {
     let _p = tracing::info_span!("handle_cancel_flycheck").entered();
     state.flycheck.iter().for_each(|flycheck| flycheck.cancel());
@@ -404,6 +449,41 @@ pub(crate) fn handle_run_flycheck(
     Ok(())
 }
 
+pub(crate) fn handle_rebuild_diagnostics(state: &mut GlobalState, _: ()) -> anyhow::Result<()> {
+    let _p = tracing::info_span!("handle_rebuild_diagnostics").entered();
+    // Drop the native diagnostics of all open files so that a recompute from a
+    // fresh snapshot picks up config changes that happened since they were
+    // published.
+    let file_ids: Vec<_> = {
+        let vfs = &state.vfs.read().0;
+        state.mem_docs.iter().filter_map(|path| vfs.file_id(path)).collect()
+    };
+    for file_id in file_ids {
+        state.diagnostics.clear_native_for(file_id);
+    }
+    if state.config.publish_diagnostics() {
+        state.update_diagnostics();
+    }
+    // Check diagnostics are rebuilt by the restarted flycheck runs.
+    state.diagnostics.clear_check_all();
+    for flycheck in state.flycheck.iter() {
+        flycheck.restart_workspace(None);
+    }
+    Ok(())
+}
+
+pub(crate) fn handle_focused_document(
+    state: &mut GlobalState,
+    params: lsp_ext::FocusedDocumentParams,
+) -> anyhow::Result<()> {
+    let _p = tracing::info_span!("handle_focused_document").entered();
+    state.focused_document = match params.text_document {
+        Some(text_document) => from_proto::vfs_path(&text_document.uri).ok(),
+        None => None,
+    };
+    Ok(())
+}
+
 pub(crate) fn handle_abort_run_test(state: &mut GlobalState, _: ()) -> anyhow::Result<()> {
     if state.test_run_session.take().is_some() {
         state.send_notification::<lsp_ext::EndRunTest>(());
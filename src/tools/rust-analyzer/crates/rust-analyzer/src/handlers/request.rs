@@ -10,17 +10,18 @@ use std::{
 use anyhow::Context;
 
 use ide::{
-    AnnotationConfig, AssistKind, AssistResolveStrategy, Cancellable, FilePosition, FileRange,
-    HoverAction, HoverGotoTypeData, InlayFieldsToResolve, Query, RangeInfo, ReferenceCategory,
-    Runnable, RunnableKind, SingleResolve, SourceChange, TextEdit,
+    AnnotationConfig, AssistKind, AssistResolveStrategy, Cancellable, CompletionScope,
+    FilePosition, FileRange, FoldKind, HoverAction, HoverGotoTypeData, InlayFieldsToResolve, Query,
+    RangeInfo, ReferenceCategory, Runnable, RunnableKind, SingleResolve, SourceChange, TextEdit,
 };
-use ide_db::SymbolKind;
+use ide_db::{FxHashMap, FxHashSet, FxIndexSet, SymbolKind};
 use itertools::Itertools;
 use lsp_server::ErrorCode;
 use lsp_types::{
     CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
     CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams,
-    CodeLens, CompletionItem, FoldingRange, FoldingRangeParams, HoverContents, InlayHint,
+    CodeLens, Color, ColorInformation, ColorPresentation, ColorPresentationParams, CompletionItem,
+    DocumentColorParams, FoldingRange, FoldingRangeParams, HoverContents, InlayHint,
     InlayHintParams, Location, LocationLink, Position, PrepareRenameResponse, Range, RenameParams,
     ResourceOp, ResourceOperationKind, SemanticTokensDeltaParams, SemanticTokensFullDeltaResult,
     SemanticTokensParams, SemanticTokensRangeParams, SemanticTokensRangeResult,
@@ -36,9 +37,9 @@ use vfs::{AbsPath, AbsPathBuf, FileId, VfsPath};
 
 use crate::{
     config::{Config, RustfmtConfig, WorkspaceSymbolConfig},
-    global_state::{FetchWorkspaceRequest, GlobalState, GlobalStateSnapshot},
+    global_state::{FetchWorkspaceRequest, GlobalState, GlobalStateSnapshot, PendingRequestStats},
     hack_recover_crate_name,
-    line_index::LineEndings,
+    line_index::{LineEndings, LineIndex},
     lsp::{
         ext::InternalTestingFetchConfigParams,
         from_proto, to_proto,
@@ -62,6 +63,48 @@ pub(crate) fn handle_workspace_reload(state: &mut GlobalState, _: ()) -> anyhow:
     Ok(())
 }
 
+pub(crate) fn handle_set_cfg_overrides(
+    state: &mut GlobalState,
+    params: lsp_ext::SetCfgOverridesParams,
+) -> anyhow::Result<()> {
+    let cfgs = params
+        .cfgs
+        .iter()
+        .map(|it| project_model::parse_cfg(it))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(invalid_params_error)?;
+
+    if cfgs.is_empty() {
+        state.cfg_overrides.remove(&params.krate);
+    } else {
+        state.cfg_overrides.insert(params.krate, cfgs);
+    }
+
+    let req = FetchWorkspaceRequest { path: None, force_crate_graph_reload: false };
+    state.fetch_workspaces_queue.request_op("cfg overrides changed".to_owned(), req);
+    Ok(())
+}
+
+pub(crate) fn handle_clear_cfg_overrides(state: &mut GlobalState, _: ()) -> anyhow::Result<()> {
+    if state.cfg_overrides.is_empty() {
+        return Ok(());
+    }
+    state.cfg_overrides.clear();
+
+    let req = FetchWorkspaceRequest { path: None, force_crate_graph_reload: false };
+    state.fetch_workspaces_queue.request_op("cfg overrides cleared".to_owned(), req);
+    Ok(())
+}
+
+pub(crate) fn handle_shuffle_crate_graph(state: &mut GlobalState, _: ()) -> anyhow::Result<()> {
+    state.analysis_host.shuffle_crate_graph();
+    // Rerun a sample query over the new graph; its results must not depend on the
+    // numeric crate ids, only on the graph's shape.
+    let stats = state.analysis_host.analysis().crate_graph_stats()?;
+    tracing::info!("crate graph stats after shuffle: {stats:?}");
+    Ok(())
+}
+
 pub(crate) fn handle_proc_macros_rebuild(state: &mut GlobalState, _: ()) -> anyhow::Result<()> {
     state.proc_macro_clients = Arc::from_iter([]);
     state.build_deps_changed = false;
@@ -73,9 +116,33 @@ pub(crate) fn handle_proc_macros_rebuild(state: &mut GlobalState, _: ()) -> anyh
 pub(crate) fn handle_analyzer_status(
     snap: GlobalStateSnapshot,
     params: lsp_ext::AnalyzerStatusParams,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<lsp_ext::AnalyzerStatusResult> {
     let _p = tracing::info_span!("handle_analyzer_status").entered();
 
+    let (health, reasons) = match &snap.server_status {
+        // The initial workspace fetch has not reported back yet.
+        None => (lsp_ext::AnalyzerStatusHealth::Loading, Vec::new()),
+        Some(status) => {
+            let health = match status.health {
+                lsp_ext::Health::Error => lsp_ext::AnalyzerStatusHealth::Error,
+                _ if !status.quiescent => lsp_ext::AnalyzerStatusHealth::Loading,
+                lsp_ext::Health::Warning => lsp_ext::AnalyzerStatusHealth::Degraded,
+                lsp_ext::Health::Ok => lsp_ext::AnalyzerStatusHealth::Healthy,
+            };
+            // The status message consists of one paragraph per problem.
+            let reasons = status
+                .message
+                .as_deref()
+                .unwrap_or_default()
+                .split("\n\n")
+                .map(str::trim)
+                .filter(|it| !it.is_empty())
+                .map(str::to_owned)
+                .collect();
+            (health, reasons)
+        }
+    };
+
     let mut buf = String::new();
 
     let mut file_id = None;
@@ -103,6 +170,22 @@ pub(crate) fn handle_analyzer_status(
             "Workspace root folders: {:?}",
             snap.workspaces.iter().map(|ws| ws.manifest_or_root()).collect::<Vec<&AbsPath>>()
         );
+
+        for ws in snap.workspaces.iter() {
+            let ProjectWorkspaceKind::Cargo { cargo, .. } = &ws.kind else { continue };
+            format_to!(buf, "\nActive features per package in {}:\n", cargo.workspace_root());
+            for package in cargo.packages() {
+                let package = &cargo[package];
+                if !package.is_member {
+                    continue;
+                }
+                if package.active_features.is_empty() {
+                    format_to!(buf, "  {}: (none)\n", package.name);
+                } else {
+                    format_to!(buf, "  {}: {}\n", package.name, package.active_features.join(", "));
+                }
+            }
+        }
     }
     buf.push_str("\nAnalysis:\n");
     buf.push_str(
@@ -112,13 +195,125 @@ pub(crate) fn handle_analyzer_status(
             .unwrap_or_else(|_| "Analysis retrieval was cancelled".to_owned()),
     );
 
+    format_to!(buf, "\nOpen documents: {}\n", snap.open_documents().count());
+
+    buf.push_str("\nRequests:\n");
+    let PendingRequestStats { count, oldest } = &snap.pending_request_stats;
+    match oldest {
+        Some((method, start)) => format_to!(
+            buf,
+            "{count} in flight, oldest `{method}` pending for {:.2?}\n",
+            start.elapsed()
+        ),
+        None => format_to!(buf, "{count} in flight\n"),
+    }
+    format_to!(buf, "{} background tasks queued\n", snap.task_pool_backlog);
+
+    buf.push_str("\nFile watcher:\n");
+    let vfs::loader::Stats { events_received, files_scanned, watch_registrations } =
+        snap.vfs_loader_stats;
+    format_to!(
+        buf,
+        "{events_received} events received, {files_scanned} files scanned, {watch_registrations} watch registrations\n"
+    );
+    if watch_registrations > 0 && events_received == 0 {
+        buf.push_str(
+            "No events have been received; if files were changed on disk, the file watcher may not be working\n",
+        );
+    }
+
     buf.push_str("\nVersion: \n");
     format_to!(buf, "{}", crate::version());
 
     buf.push_str("\nConfiguration: \n");
     format_to!(buf, "{:?}", snap.config);
 
-    Ok(buf)
+    Ok(lsp_ext::AnalyzerStatusResult { health, reasons, status: buf })
+}
+
+pub(crate) fn handle_effective_config(
+    snap: GlobalStateSnapshot,
+    _: (),
+) -> anyhow::Result<serde_json::Value> {
+    let _p = tracing::info_span!("handle_effective_config").entered();
+    Ok(snap.config.effective_config())
+}
+
+pub(crate) fn handle_capture_state(
+    snap: GlobalStateSnapshot,
+    _: (),
+) -> anyhow::Result<lsp_ext::CaptureStateResult> {
+    let _p = tracing::info_span!("handle_capture_state").entered();
+
+    let open_files = snap
+        .open_documents()
+        .map(|(path, doc)| lsp_ext::CapturedFile {
+            path: path.to_string(),
+            contents: String::from_utf8_lossy(&doc.data).into_owned(),
+        })
+        .collect();
+
+    let workspaces = snap
+        .workspaces
+        .iter()
+        .map(|ws| lsp_ext::CapturedWorkspace {
+            root: ws.manifest_or_root().to_string(),
+            packages: ws.n_packages(),
+            toolchain: ws.toolchain.as_ref().map(ToString::to_string),
+            cfg: ws.rustc_cfg.iter().map(ToString::to_string).collect(),
+        })
+        .collect();
+
+    Ok(lsp_ext::CaptureStateResult {
+        version: crate::version().to_string(),
+        open_files,
+        workspaces,
+    })
+}
+
+pub(crate) fn handle_load_crate(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::LoadCrateParams,
+) -> anyhow::Result<()> {
+    let _p = tracing::info_span!("handle_load_crate").entered();
+
+    let crates = match (params.name, params.text_document) {
+        (Some(name), _) => snap.analysis.crates_with_name(&name)?,
+        (None, Some(tdi)) => {
+            let file_id = from_proto::file_id(&snap, &tdi.uri)?;
+            snap.analysis.crates_for(file_id)?
+        }
+        (None, None) => {
+            return Err(anyhow::format_err!("expected a crate name or a text document"))
+        }
+    };
+    if crates.is_empty() {
+        return Err(anyhow::format_err!("no matching crate in the crate graph"));
+    }
+    for krate in crates {
+        snap.analysis.load_crate(krate)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn handle_mem_docs(
+    snap: GlobalStateSnapshot,
+    _: (),
+) -> anyhow::Result<lsp_ext::MemDocsResult> {
+    let _p = tracing::info_span!("handle_mem_docs").entered();
+
+    let documents = snap
+        .open_documents()
+        .map(|(path, doc)| {
+            let differs_from_disk = match path.as_path() {
+                Some(path) => std::fs::read(path).map_or(true, |on_disk| on_disk != doc.data),
+                None => true,
+            };
+            lsp_ext::MemDoc { path: path.to_string(), version: doc.version, differs_from_disk }
+        })
+        .collect();
+
+    Ok(lsp_ext::MemDocsResult { documents })
 }
 
 pub(crate) fn handle_memory_usage(state: &mut GlobalState, _: ()) -> anyhow::Result<String> {
@@ -146,6 +341,18 @@ pub(crate) fn handle_syntax_tree(
     Ok(res)
 }
 
+pub(crate) fn handle_type_of(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::TypeOfParams,
+) -> anyhow::Result<String> {
+    let _p = tracing::info_span!("handle_type_of").entered();
+    let frange = from_proto::file_range(&snap, &params.text_document, params.range)?;
+    match snap.analysis.type_of_range(frange)? {
+        Some(it) => Ok(it),
+        None => Err(anyhow::format_err!("selection does not cover a typed expression")),
+    }
+}
+
 pub(crate) fn handle_view_hir(
     snap: GlobalStateSnapshot,
     params: lsp_types::TextDocumentPositionParams,
@@ -194,6 +401,106 @@ pub(crate) fn handle_view_item_tree(
     Ok(res)
 }
 
+pub(crate) fn handle_view_crate_context(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::TextDocumentIdentifier,
+) -> anyhow::Result<String> {
+    let _p = tracing::info_span!("handle_view_crate_context").entered();
+    let file_id = from_proto::file_id(&snap, &params.uri)?;
+    let crates = snap.analysis.crates_for(file_id)?;
+    let mut res = String::new();
+    for (idx, krate) in crates.iter().enumerate() {
+        let name = snap.analysis.crate_name(*krate)?.unwrap_or_else(|| "<unnamed>".to_owned());
+        let root = snap.analysis.crate_root(*krate)?;
+        let root = snap.file_id_to_url(root);
+        let marker = if idx == 0 { " (current)" } else { "" };
+        format_to!(res, "{name} ({root}){marker}\n");
+    }
+    if res.is_empty() {
+        res = "file does not belong to any crate\n".to_owned();
+    }
+    Ok(res)
+}
+
+pub(crate) fn handle_validate_project_json(
+    _snap: GlobalStateSnapshot,
+    params: lsp_ext::ValidateProjectJsonParams,
+) -> anyhow::Result<lsp_ext::ValidateProjectJsonResult> {
+    let _p = tracing::info_span!("handle_validate_project_json").entered();
+    let path = from_proto::abs_path(&params.text_document.uri)?;
+    let text = std::fs::read_to_string(&path).with_context(|| format!("failed to read {path}"))?;
+    let problems = match serde_json::from_str::<project_model::ProjectJsonData>(&text) {
+        Ok(data) => {
+            let base = path.parent().context("project json file has no parent directory")?;
+            data.validate(base)
+                .into_iter()
+                .map(|message| lsp_ext::ProjectJsonProblem { message, position: None })
+                .collect()
+        }
+        Err(e) => {
+            // serde positions are 1-based.
+            let position = Position {
+                line: e.line().saturating_sub(1) as u32,
+                character: e.column().saturating_sub(1) as u32,
+            };
+            vec![lsp_ext::ProjectJsonProblem { message: e.to_string(), position: Some(position) }]
+        }
+    };
+    Ok(lsp_ext::ValidateProjectJsonResult { problems })
+}
+
+pub(crate) fn handle_export_project_json(
+    snap: GlobalStateSnapshot,
+    _: (),
+) -> anyhow::Result<serde_json::Value> {
+    let _p = tracing::info_span!("handle_export_project_json").entered();
+    let crate_graph = snap.analysis.crate_graph()?;
+    // `rust-project.json` refers to crates by their index in the `crates`
+    // array, which is the iteration order of the graph here.
+    let crate_indices: FxHashMap<_, _> =
+        crate_graph.iter().enumerate().map(|(idx, crate_id)| (crate_id, idx)).collect();
+    let crates = crate_graph
+        .iter()
+        .map(|crate_id| {
+            let data = &crate_graph[crate_id];
+            let deps = data
+                .dependencies
+                .iter()
+                .filter_map(|dep| {
+                    let krate = crate_indices.get(&dep.crate_id)?;
+                    Some(serde_json::json!({ "name": dep.name.to_string(), "crate": krate }))
+                })
+                .collect::<Vec<_>>();
+            let cfg =
+                data.cfg_options.as_ref().into_iter().map(ToString::to_string).collect::<Vec<_>>();
+            let env: Vec<(String, String)> = data.env.clone().into();
+            serde_json::json!({
+                "display_name": data.display_name.as_ref().map(ToString::to_string),
+                "root_module": snap.file_id_to_file_path(data.root_file_id).to_string(),
+                "edition": data.edition.to_string(),
+                "version": data.version,
+                "deps": deps,
+                "cfg": cfg,
+                "env": env
+                    .into_iter()
+                    .map(|(k, v)| (k, serde_json::Value::from(v)))
+                    .collect::<serde_json::Map<_, _>>(),
+                "is_workspace_member": data.origin.is_local(),
+                "is_proc_macro": data.is_proc_macro,
+            })
+        })
+        .collect::<Vec<_>>();
+    Ok(serde_json::json!({ "crates": crates }))
+}
+
+pub(crate) fn handle_list_assists(
+    _snap: GlobalStateSnapshot,
+    _: (),
+) -> anyhow::Result<Vec<String>> {
+    let _p = tracing::info_span!("handle_list_assists").entered();
+    Ok(ide::ASSIST_IDS.iter().map(|&it| it.to_owned()).collect())
+}
+
 // cargo test requires the real package name which might contain hyphens but
 // the test identifier passed to this function is the namespace form where hyphens
 // are replaced with underscores so we have to reverse this and find the real package name
@@ -310,6 +617,26 @@ pub(crate) fn handle_view_crate_graph(
     Ok(dot)
 }
 
+pub(crate) fn handle_crate_graph_stats(
+    snap: GlobalStateSnapshot,
+    _: (),
+) -> anyhow::Result<lsp_ext::CrateGraphStatsResult> {
+    let _p = tracing::info_span!("handle_crate_graph_stats").entered();
+    let stats = snap.analysis.crate_graph_stats()?;
+    Ok(lsp_ext::CrateGraphStatsResult {
+        crates: stats.crates,
+        edges: stats.edges,
+        source_roots: stats.source_roots,
+        files: stats.files,
+        workspaces: snap.workspaces.len(),
+        largest_crates: stats
+            .largest_crates
+            .into_iter()
+            .map(|(name, files)| lsp_ext::CrateSize { name, files })
+            .collect(),
+    })
+}
+
 pub(crate) fn handle_expand_macro(
     snap: GlobalStateSnapshot,
     params: lsp_ext::ExpandMacroParams,
@@ -323,6 +650,46 @@ pub(crate) fn handle_expand_macro(
     Ok(res.map(|it| lsp_ext::ExpandedMacro { name: it.name, expansion: it.expansion }))
 }
 
+pub(crate) fn handle_goto_expansion(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::ExpandMacroParams,
+) -> anyhow::Result<Option<lsp_ext::GotoExpansionResult>> {
+    let _p = tracing::info_span!("handle_goto_expansion").entered();
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let offset = from_proto::offset(&line_index, params.position)?;
+
+    let res = snap.analysis.expand_macro_step(FilePosition { file_id, offset })?;
+    Ok(res.map(|it| {
+        // The ranges point into the expansion text, so they need a line index built from
+        // that text rather than from a file in the vfs.
+        let expansion_line_index = LineIndex {
+            index: Arc::new(ide::LineIndex::new(&it.expansion)),
+            endings: LineEndings::Unix,
+            encoding: snap.config.caps().negotiated_encoding(),
+        };
+        let expandable_ranges = it
+            .expandable_ranges
+            .into_iter()
+            .map(|range| to_proto::range(&expansion_line_index, range))
+            .collect();
+        lsp_ext::GotoExpansionResult { name: it.name, expansion: it.expansion, expandable_ranges }
+    }))
+}
+
+pub(crate) fn handle_eval_cfg(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::EvalCfgParams,
+) -> anyhow::Result<Option<lsp_ext::EvalCfgResult>> {
+    let _p = tracing::info_span!("handle_eval_cfg").entered();
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let offset = from_proto::offset(&line_index, params.position)?;
+
+    let res = snap.analysis.eval_cfg(FilePosition { file_id, offset })?;
+    Ok(res.map(|it| lsp_ext::EvalCfgResult { enabled: it.enabled, decided_by: it.decided_by }))
+}
+
 pub(crate) fn handle_selection_range(
     snap: GlobalStateSnapshot,
     params: lsp_types::SelectionRangeParams,
@@ -657,18 +1024,17 @@ pub(crate) fn handle_will_rename_files(
 ) -> anyhow::Result<Option<lsp_types::WorkspaceEdit>> {
     let _p = tracing::info_span!("handle_will_rename_files").entered();
 
-    let source_changes: Vec<SourceChange> = params
-        .files
-        .into_iter()
-        .filter_map(|file_rename| {
+    let mut source_changes: Vec<SourceChange> = Vec::new();
+    for file_rename in params.files {
+        let Some((file_id, new_name, new_parent_file_id)) = (|| {
             let from = Url::parse(&file_rename.old_uri).ok()?;
             let to = Url::parse(&file_rename.new_uri).ok()?;
 
             let from_path = from.to_file_path().ok()?;
             let to_path = to.to_file_path().ok()?;
 
-            // Limit to single-level moves for now.
             match (from_path.parent(), to_path.parent()) {
+                // A rename within the same directory.
                 (Some(p1), Some(p2)) if p1 == p2 => {
                     if from_path.is_dir() {
                         // add '/' to end of url -- from `file://path/to/folder` to `file://path/to/folder/`
@@ -681,6 +1047,7 @@ pub(crate) fn handle_will_rename_files(
                         Some((
                             snap.url_to_file_id(&imitate_from_url).ok()?,
                             new_file_name.to_owned(),
+                            None,
                         ))
                     } else {
                         let old_name = from_path.file_stem()?.to_str()?;
@@ -688,19 +1055,54 @@ pub(crate) fn handle_will_rename_files(
                         match (old_name, new_name) {
                             ("mod", _) => None,
                             (_, "mod") => None,
-                            _ => Some((snap.url_to_file_id(&from).ok()?, new_name.to_owned())),
+                            _ => {
+                                Some((snap.url_to_file_id(&from).ok()?, new_name.to_owned(), None))
+                            }
                         }
                     }
                 }
+                // A move into a different directory: the module is re-parented
+                // under the module owning the target directory.
+                (Some(_), Some(p2)) if !from_path.is_dir() => {
+                    let old_name = from_path.file_stem()?.to_str()?;
+                    let new_name = to_path.file_stem()?.to_str()?;
+                    if old_name == "mod" || new_name == "mod" {
+                        return None;
+                    }
+                    let new_parent_file_id = [
+                        p2.join("mod.rs"),
+                        p2.with_extension("rs"),
+                        p2.join("lib.rs"),
+                        p2.join("main.rs"),
+                    ]
+                    .into_iter()
+                    .find_map(|candidate| {
+                        let url = Url::from_file_path(candidate).ok()?;
+                        snap.url_to_file_id(&url).ok()
+                    })?;
+                    Some((
+                        snap.url_to_file_id(&from).ok()?,
+                        new_name.to_owned(),
+                        Some(new_parent_file_id),
+                    ))
+                }
                 _ => None,
             }
-        })
-        .filter_map(|(file_id, new_name)| {
-            snap.analysis.will_rename_file(file_id, &new_name).ok()?
-        })
-        .collect();
+        })() else {
+            continue;
+        };
+        let change = match new_parent_file_id {
+            Some(new_parent_file_id) => {
+                snap.analysis.will_move_file(file_id, new_parent_file_id, &new_name)?
+            }
+            None => snap.analysis.will_rename_file(file_id, &new_name)?,
+        };
+        if let Some(change) = change {
+            source_changes.push(change.map_err(to_proto::rename_error)?);
+        }
+    }
 
-    // Drop file system edits since we're just renaming things on the same level
+    // Drop file system edits since the client is doing the file move itself
     let mut source_changes = source_changes.into_iter();
     let mut source_change = source_changes.next().unwrap_or_default();
     source_change.file_system_edits.clear();
@@ -997,15 +1399,23 @@ pub(crate) fn handle_completion(
     let _p = tracing::info_span!("handle_completion").entered();
     let mut position = from_proto::file_position(&snap, text_document_position.clone())?;
     let line_index = snap.file_line_index(position.file_id)?;
+    let invoked = context
+        .as_ref()
+        .is_some_and(|ctx| ctx.trigger_kind == lsp_types::CompletionTriggerKind::INVOKED);
     let completion_trigger_character =
         context.and_then(|ctx| ctx.trigger_character).and_then(|s| s.chars().next());
 
     let source_root = snap.analysis.source_root_id(position.file_id)?;
-    let completion_config = &snap.config.completion(Some(source_root));
+    let mut completion_config = snap.config.completion(Some(source_root));
+    // A completion the user explicitly invoked searches the whole workspace,
+    // even when the scope is restricted for completions triggered by typing.
+    if invoked {
+        completion_config.scope = CompletionScope::Workspace;
+    }
     // FIXME: We should fix up the position when retrying the cancelled request instead
     position.offset = position.offset.min(line_index.index.len());
     let items = match snap.analysis.completions(
-        completion_config,
+        &completion_config,
         position,
         completion_trigger_character,
     )? {
@@ -1013,11 +1423,14 @@ pub(crate) fn handle_completion(
         Some(items) => items,
     };
 
+    let recent_completions = snap.recent_completions.lock().clone();
     let items = to_proto::completion_items(
         &snap.config,
         &line_index,
         snap.file_version(position.file_id),
         text_document_position,
+        completion_trigger_character,
+        &recent_completions,
         items,
     );
 
@@ -1038,6 +1451,11 @@ pub(crate) fn handle_completion_resolve(
         .into());
     }
 
+    // Clients resolve a completion when the user selects it, which is the
+    // closest thing to an acceptance signal we get; remember it for the
+    // recency scoring weight.
+    snap.note_accepted_completion(&original_completion.label);
+
     let Some(data) = original_completion.data.take() else { return Ok(original_completion) };
 
     let resolve_data: lsp_ext::CompletionResolveData = serde_json::from_value(data)?;
@@ -1050,6 +1468,26 @@ pub(crate) fn handle_completion_resolve(
     };
     let source_root = snap.analysis.source_root_id(file_id)?;
 
+    // Documentation is deferred to resolve time when the client supports it;
+    // recompute the completion list and look the item up again by its index.
+    if let Some(completion_item_index) = resolve_data.completion_item_index {
+        if original_completion.documentation.is_none() {
+            let items = snap.analysis.completions(
+                &snap.config.completion(Some(source_root)),
+                FilePosition { file_id, offset },
+                resolve_data.trigger_character,
+            )?;
+            if let Some(item) = items.as_deref().and_then(|it| it.get(completion_item_index)) {
+                // The position may have been edited since the item was
+                // computed; only fill in docs that belong to the same item.
+                if original_completion.label.starts_with(item.label.as_str()) {
+                    original_completion.documentation =
+                        item.documentation.clone().map(to_proto::documentation);
+                }
+            }
+        }
+    }
+
     let additional_edits = snap
         .analysis
         .resolve_completion_edits(
@@ -1092,13 +1530,66 @@ pub(crate) fn handle_folding_range(
     let text = snap.analysis.file_text(file_id)?;
     let line_index = snap.file_line_index(file_id)?;
     let line_folding_only = snap.config.line_folding_only();
+    let fold_comments = snap.config.folding_ranges_comments();
     let res = folds
         .into_iter()
+        .filter(|it| fold_comments || it.kind != FoldKind::Comment)
         .map(|it| to_proto::folding_range(&text, &line_index, line_folding_only, it))
         .collect();
     Ok(Some(res))
 }
 
+pub(crate) fn handle_document_color(
+    snap: GlobalStateSnapshot,
+    params: DocumentColorParams,
+) -> anyhow::Result<Vec<ColorInformation>> {
+    let _p = tracing::info_span!("handle_document_color").entered();
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let colors = snap.analysis.document_colors(snap.config.document_colors_patterns(), file_id)?;
+    let res = colors
+        .into_iter()
+        .map(|it| ColorInformation {
+            range: to_proto::range(&line_index, it.range),
+            color: Color { red: it.red, green: it.green, blue: it.blue, alpha: it.alpha },
+        })
+        .collect();
+    Ok(res)
+}
+
+pub(crate) fn handle_color_presentation(
+    snap: GlobalStateSnapshot,
+    params: ColorPresentationParams,
+) -> anyhow::Result<Vec<ColorPresentation>> {
+    let _p = tracing::info_span!("handle_color_presentation").entered();
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let range = from_proto::text_range(&line_index, params.range)?;
+
+    let Color { red, green, blue, alpha } = params.color;
+    let to_byte = |it: f32| (it.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let label = if alpha < 1.0 {
+        format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            to_byte(red),
+            to_byte(green),
+            to_byte(blue),
+            to_byte(alpha)
+        )
+    } else {
+        format!("#{:02x}{:02x}{:02x}", to_byte(red), to_byte(green), to_byte(blue))
+    };
+
+    // Only hex color strings can be rewritten textually; the shape of color producing
+    // calls varies too much, so for those the label is informational only.
+    let text = snap.analysis.file_text(file_id)?;
+    let text_edit = text[range]
+        .starts_with('#')
+        .then(|| lsp_types::TextEdit { range: params.range, new_text: label.clone() });
+
+    Ok(vec![ColorPresentation { label, text_edit, additional_text_edits: None }])
+}
+
 pub(crate) fn handle_signature_help(
     snap: GlobalStateSnapshot,
     params: lsp_types::SignatureHelpParams,
@@ -1201,6 +1692,60 @@ pub(crate) fn handle_rename(
     Ok(Some(workspace_edit))
 }
 
+pub(crate) fn handle_reference_count(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::TextDocumentPositionParams,
+) -> anyhow::Result<Option<lsp_ext::ReferenceCountResult>> {
+    let _p = tracing::info_span!("handle_reference_count").entered();
+    let position = from_proto::file_position(&snap, params)?;
+
+    let exclude_imports = snap.config.find_all_refs_exclude_imports();
+    let exclude_tests = snap.config.find_all_refs_exclude_tests();
+    let limit = snap.config.find_all_refs_count_limit();
+
+    let Some(refs) = snap.analysis.find_all_refs(position, None)? else {
+        return Ok(None);
+    };
+
+    let mut seen = FxHashSet::default();
+    let mut capped = false;
+    let mut per_crate = FxHashMap::<String, usize>::default();
+    'count: for refs in refs {
+        for (file_id, refs) in refs.references {
+            let new = refs
+                .into_iter()
+                .filter(|&(_, category)| {
+                    (!exclude_imports || !category.contains(ReferenceCategory::IMPORT))
+                        && (!exclude_tests || !category.contains(ReferenceCategory::TEST))
+                })
+                .filter(|&(range, _)| seen.insert((file_id, range)))
+                .count();
+            if new == 0 {
+                continue;
+            }
+            let krate = match snap.analysis.crates_for(file_id)?.first() {
+                Some(&krate) => {
+                    snap.analysis.crate_name(krate)?.unwrap_or_else(|| "<unnamed>".to_owned())
+                }
+                None => "<unnamed>".to_owned(),
+            };
+            *per_crate.entry(krate).or_default() += new;
+            if limit.is_some_and(|limit| seen.len() >= limit) {
+                capped = true;
+                break 'count;
+            }
+        }
+    }
+
+    let total = seen.len();
+    let mut per_crate: Vec<_> = per_crate
+        .into_iter()
+        .map(|(name, count)| lsp_ext::CrateReferenceCount { name, count })
+        .collect();
+    per_crate.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    Ok(Some(lsp_ext::ReferenceCountResult { total, capped, per_crate }))
+}
+
 pub(crate) fn handle_references(
     snap: GlobalStateSnapshot,
     params: lsp_types::ReferenceParams,
@@ -1530,6 +2075,22 @@ pub(crate) fn handle_document_highlight(
     Ok(Some(res))
 }
 
+pub(crate) fn handle_linked_editing_range(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::LinkedEditingRangeParams,
+) -> anyhow::Result<Option<lsp_types::LinkedEditingRanges>> {
+    let _p = tracing::info_span!("handle_linked_editing_range").entered();
+    let position = from_proto::file_position(&snap, params.text_document_position_params)?;
+    let line_index = snap.file_line_index(position.file_id)?;
+
+    let ranges = match snap.analysis.linked_editing_ranges(position)? {
+        None => return Ok(None),
+        Some(ranges) => ranges,
+    };
+    let ranges = ranges.into_iter().map(|range| to_proto::range(&line_index, range)).collect();
+    Ok(Some(lsp_types::LinkedEditingRanges { ranges, word_pattern: None }))
+}
+
 pub(crate) fn handle_ssr(
     snap: GlobalStateSnapshot,
     params: lsp_ext::SsrParams,
@@ -1872,6 +2433,35 @@ pub(crate) fn handle_open_docs(
     }
 }
 
+pub(crate) fn handle_item_path(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::TextDocumentPositionParams,
+) -> anyhow::Result<Option<lsp_ext::ItemPathResult>> {
+    let _p = tracing::info_span!("handle_item_path").entered();
+    let position = from_proto::file_position(&snap, params)?;
+    let res = snap.analysis.item_path(position)?;
+    Ok(res.map(|it| lsp_ext::ItemPathResult { canonical: it.canonical, importable: it.importable }))
+}
+
+pub(crate) fn handle_todo_comments(
+    snap: GlobalStateSnapshot,
+    _: (),
+) -> anyhow::Result<Vec<lsp_ext::TodoComment>> {
+    let _p = tracing::info_span!("handle_todo_comments").entered();
+    let config = snap.config.todo_comments();
+    snap.analysis
+        .todo_comments(&config)?
+        .into_iter()
+        .map(|it| {
+            Ok(lsp_ext::TodoComment {
+                marker: it.marker,
+                text: it.text,
+                location: to_proto::location(&snap, it.range)?,
+            })
+        })
+        .collect()
+}
+
 pub(crate) fn handle_open_cargo_toml(
     snap: GlobalStateSnapshot,
     params: lsp_ext::OpenCargoTomlParams,
@@ -2115,11 +2705,15 @@ fn run_rustfmt(
     let line_index = snap.file_line_index(file_id)?;
     let sr = snap.analysis.source_root_id(file_id)?;
 
-    let mut command = match snap.config.rustfmt(Some(sr)) {
-        RustfmtConfig::Rustfmt { extra_args, enable_range_formatting } => {
-            // FIXME: Set RUSTUP_TOOLCHAIN
+    let rustfmt_config = snap.config.rustfmt(Some(sr));
+    let is_custom_command = matches!(rustfmt_config, RustfmtConfig::CustomCommand { .. });
+    let mut command = match rustfmt_config {
+        RustfmtConfig::Rustfmt { extra_args, enable_range_formatting, toolchain } => {
             let mut cmd = process::Command::new(toolchain::Tool::Rustfmt.path());
             cmd.envs(snap.config.extra_env());
+            if let Some(toolchain) = toolchain {
+                cmd.env("RUSTUP_TOOLCHAIN", toolchain);
+            }
             cmd.args(extra_args);
 
             if let Some(edition) = edition {
@@ -2217,6 +2811,18 @@ fn run_rustfmt(
     let captured_stderr = String::from_utf8(output.stderr).unwrap_or_default();
 
     if !output.status.success() {
+        if is_custom_command {
+            // A wrapper is not necessarily rustfmt, so we can't tell a parse error from a
+            // real failure by the exit code; surface its stderr as-is instead of formatting.
+            return Err(LspError::new(
+                -32900,
+                format!(
+                    "the configured rustfmt command exited with {}:\n{captured_stderr}",
+                    output.status,
+                ),
+            )
+            .into());
+        }
         let rustfmt_not_installed =
             captured_stderr.contains("not installed") || captured_stderr.contains("not available");
 
@@ -2288,6 +2894,36 @@ pub(crate) fn fetch_dependency_list(
     Ok(FetchDependencyListResult { crates: crate_infos })
 }
 
+/// The largest dependent set `handle_reverse_dependencies` reports. Core utility crates can have
+/// most of a big workspace as dependents, so anything beyond this is dropped and flagged via
+/// `truncated` instead of serializing thousands of entries.
+const REVERSE_DEPENDENCIES_CAP: usize = 1000;
+
+pub(crate) fn handle_reverse_dependencies(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::ReverseDependenciesParams,
+) -> anyhow::Result<lsp_ext::ReverseDependenciesResult> {
+    let _p = tracing::info_span!("handle_reverse_dependencies").entered();
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+
+    let mut dependents = FxIndexSet::default();
+    for krate in snap.analysis.crates_for(file_id)? {
+        dependents.extend(snap.reverse_dependents(krate)?);
+    }
+
+    let truncated = dependents.len() > REVERSE_DEPENDENCIES_CAP;
+    let mut crates = Vec::with_capacity(dependents.len().min(REVERSE_DEPENDENCIES_CAP));
+    for krate in dependents.into_iter().take(REVERSE_DEPENDENCIES_CAP) {
+        let root_file_id = snap.analysis.crate_root(krate)?;
+        crates.push(CrateInfoResult {
+            name: snap.analysis.crate_name(krate)?,
+            version: snap.analysis.crate_version(krate)?,
+            path: snap.file_id_to_url(root_file_id),
+        });
+    }
+    Ok(lsp_ext::ReverseDependenciesResult { crates, truncated })
+}
+
 pub(crate) fn internal_testing_fetch_config(
     state: GlobalStateSnapshot,
     params: InternalTestingFetchConfigParams,
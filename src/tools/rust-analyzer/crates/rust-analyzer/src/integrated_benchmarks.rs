@@ -12,7 +12,8 @@
 
 use hir::ChangeWithProcMacros;
 use ide::{
-    AnalysisHost, CallableSnippets, CompletionConfig, DiagnosticsConfig, FilePosition, TextSize,
+    AnalysisHost, CallableSnippets, CompletionConfig, CompletionScope, CompletionScoringWeights,
+    DiagnosticsConfig, FilePosition, TextSize,
 };
 use ide_db::{
     imports::insert_use::{ImportGranularity, InsertUseConfig},
@@ -148,6 +149,7 @@ fn integrated_completion_benchmark() {
         let config = CompletionConfig {
             enable_postfix_completions: true,
             enable_imports_on_the_fly: true,
+            autoimport_exclude: Vec::new(),
             enable_self_on_the_fly: true,
             enable_private_editable: true,
             enable_term_search: true,
@@ -165,8 +167,11 @@ fn integrated_completion_benchmark() {
             prefer_no_std: false,
             prefer_prelude: true,
             prefer_absolute: false,
+            prefer_reexports: false,
             snippets: Vec::new(),
             limit: None,
+            scope: CompletionScope::Workspace,
+            scoring: CompletionScoringWeights::UNWEIGHTED,
         };
         let position =
             FilePosition { file_id, offset: TextSize::try_from(completion_offset).unwrap() };
@@ -194,6 +199,7 @@ fn integrated_completion_benchmark() {
         let config = CompletionConfig {
             enable_postfix_completions: true,
             enable_imports_on_the_fly: true,
+            autoimport_exclude: Vec::new(),
             enable_self_on_the_fly: true,
             enable_private_editable: true,
             enable_term_search: true,
@@ -211,8 +217,11 @@ fn integrated_completion_benchmark() {
             prefer_no_std: false,
             prefer_prelude: true,
             prefer_absolute: false,
+            prefer_reexports: false,
             snippets: Vec::new(),
             limit: None,
+            scope: CompletionScope::Workspace,
+            scoring: CompletionScoringWeights::UNWEIGHTED,
         };
         let position =
             FilePosition { file_id, offset: TextSize::try_from(completion_offset).unwrap() };
@@ -238,6 +247,7 @@ fn integrated_completion_benchmark() {
         let config = CompletionConfig {
             enable_postfix_completions: true,
             enable_imports_on_the_fly: true,
+            autoimport_exclude: Vec::new(),
             enable_self_on_the_fly: true,
             enable_private_editable: true,
             enable_term_search: true,
@@ -255,8 +265,11 @@ fn integrated_completion_benchmark() {
             prefer_no_std: false,
             prefer_prelude: true,
             prefer_absolute: false,
+            prefer_reexports: false,
             snippets: Vec::new(),
             limit: None,
+            scope: CompletionScope::Workspace,
+            scoring: CompletionScoringWeights::UNWEIGHTED,
         };
         let position =
             FilePosition { file_id, offset: TextSize::try_from(completion_offset).unwrap() };
@@ -306,10 +319,12 @@ fn integrated_diagnostics_benchmark() {
         enabled: false,
         proc_macros_enabled: true,
         proc_attr_macros_enabled: true,
+        proc_macro_diagnostic_severity: ide::Severity::WeakWarning,
         disable_experimental: true,
         disabled: Default::default(),
         expr_fill_default: Default::default(),
         style_lints: false,
+        unused_pub_items: false,
         snippet_cap: SnippetCap::new(true),
         insert_use: InsertUseConfig {
             granularity: ImportGranularity::Crate,
@@ -321,6 +336,7 @@ fn integrated_diagnostics_benchmark() {
         prefer_no_std: false,
         prefer_prelude: false,
         prefer_absolute: false,
+        prefer_reexports: false,
         term_search_fuel: 400,
         term_search_borrowck: true,
     };
@@ -32,7 +32,7 @@ pub enum AnalyzerStatus {}
 
 impl Request for AnalyzerStatus {
     type Params = AnalyzerStatusParams;
-    type Result = String;
+    type Result = AnalyzerStatusResult;
     const METHOD: &'static str = "rust-analyzer/analyzerStatus";
 }
 
@@ -42,6 +42,100 @@ pub struct AnalyzerStatusParams {
     pub text_document: Option<TextDocumentIdentifier>,
 }
 
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzerStatusResult {
+    pub health: AnalyzerStatusHealth,
+    /// One entry for each problem degrading the health.
+    pub reasons: Vec<String>,
+    /// The freeform status text, previously the whole response.
+    pub status: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum AnalyzerStatusHealth {
+    Healthy,
+    Degraded,
+    #[default]
+    Loading,
+    Error,
+}
+
+pub enum CaptureState {}
+
+impl Request for CaptureState {
+    type Params = ();
+    type Result = CaptureStateResult;
+    const METHOD: &'static str = "rust-analyzer/captureState";
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureStateResult {
+    /// The server version the state was captured from.
+    pub version: String,
+    pub open_files: Vec<CapturedFile>,
+    pub workspaces: Vec<CapturedWorkspace>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedFile {
+    pub path: String,
+    pub contents: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedWorkspace {
+    pub root: String,
+    pub packages: usize,
+    pub toolchain: Option<String>,
+    pub cfg: Vec<String>,
+}
+
+pub enum LoadCrate {}
+
+impl Request for LoadCrate {
+    type Params = LoadCrateParams;
+    type Result = ();
+    const METHOD: &'static str = "rust-analyzer/loadCrate";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadCrateParams {
+    /// The display name of the crate to load.
+    pub name: Option<String>,
+    /// Alternatively, a file belonging to the crate.
+    pub text_document: Option<TextDocumentIdentifier>,
+}
+
+pub enum MemDocs {}
+
+impl Request for MemDocs {
+    type Params = ();
+    type Result = MemDocsResult;
+    const METHOD: &'static str = "rust-analyzer/memDocs";
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MemDocsResult {
+    pub documents: Vec<MemDoc>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MemDoc {
+    pub path: String,
+    pub version: i32,
+    /// Whether the in-memory contents differ from the file on disk. Also set
+    /// when the document does not exist on disk at all.
+    pub differs_from_disk: bool,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct CrateInfoResult {
@@ -67,6 +161,28 @@ pub struct FetchDependencyListResult {
     pub crates: Vec<CrateInfoResult>,
 }
 
+pub enum ReverseDependencies {}
+
+impl Request for ReverseDependencies {
+    type Params = ReverseDependenciesParams;
+    type Result = ReverseDependenciesResult;
+    const METHOD: &'static str = "rust-analyzer/reverseDependencies";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReverseDependenciesParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReverseDependenciesResult {
+    pub crates: Vec<CrateInfoResult>,
+    /// Set when the dependent set was capped to keep the response small.
+    pub truncated: bool,
+}
+
 pub enum MemoryUsage {}
 
 impl Request for MemoryUsage {
@@ -75,6 +191,22 @@ impl Request for MemoryUsage {
     const METHOD: &'static str = "rust-analyzer/memoryUsage";
 }
 
+pub enum EffectiveConfig {}
+
+impl Request for EffectiveConfig {
+    type Params = ();
+    type Result = serde_json::Value;
+    const METHOD: &'static str = "rust-analyzer/effectiveConfig";
+}
+
+pub enum ShuffleCrateGraph {}
+
+impl Request for ShuffleCrateGraph {
+    type Params = ();
+    type Result = ();
+    const METHOD: &'static str = "rust-analyzer/shuffleCrateGraph";
+}
+
 pub enum ReloadWorkspace {}
 
 impl Request for ReloadWorkspace {
@@ -83,6 +215,34 @@ impl Request for ReloadWorkspace {
     const METHOD: &'static str = "rust-analyzer/reloadWorkspace";
 }
 
+pub enum SetCfgOverrides {}
+
+impl Request for SetCfgOverrides {
+    type Params = SetCfgOverridesParams;
+    type Result = ();
+    const METHOD: &'static str = "rust-analyzer/setCfgOverrides";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCfgOverridesParams {
+    /// Display name of the crate the overrides apply to, or all crates when
+    /// absent.
+    #[serde(rename = "crate")]
+    pub krate: Option<String>,
+    /// `key` or `key="value"` cfg atoms to enable. An empty list resets the
+    /// overrides for the crate.
+    pub cfgs: Vec<String>,
+}
+
+pub enum ClearCfgOverrides {}
+
+impl Request for ClearCfgOverrides {
+    type Params = ();
+    type Result = ();
+    const METHOD: &'static str = "rust-analyzer/clearCfgOverrides";
+}
+
 pub enum RebuildProcMacros {}
 
 impl Request for RebuildProcMacros {
@@ -106,6 +266,21 @@ pub struct SyntaxTreeParams {
     pub range: Option<Range>,
 }
 
+pub enum TypeOf {}
+
+impl Request for TypeOf {
+    type Params = TypeOfParams;
+    type Result = String;
+    const METHOD: &'static str = "rust-analyzer/typeOf";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeOfParams {
+    pub text_document: TextDocumentIdentifier,
+    pub range: Range,
+}
+
 pub enum ViewHir {}
 
 impl Request for ViewHir {
@@ -153,6 +328,32 @@ impl Request for ViewCrateGraph {
     const METHOD: &'static str = "rust-analyzer/viewCrateGraph";
 }
 
+pub enum CrateGraphStats {}
+
+impl Request for CrateGraphStats {
+    type Params = ();
+    type Result = CrateGraphStatsResult;
+    const METHOD: &'static str = "rust-analyzer/crateGraphStats";
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CrateGraphStatsResult {
+    pub crates: usize,
+    pub edges: usize,
+    pub source_roots: usize,
+    pub files: usize,
+    pub workspaces: usize,
+    pub largest_crates: Vec<CrateSize>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CrateSize {
+    pub name: String,
+    pub files: usize,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ViewItemTreeParams {
@@ -167,6 +368,85 @@ impl Request for ViewItemTree {
     const METHOD: &'static str = "rust-analyzer/viewItemTree";
 }
 
+pub enum ViewCrateContext {}
+
+impl Request for ViewCrateContext {
+    type Params = lsp_types::TextDocumentIdentifier;
+    type Result = String;
+    const METHOD: &'static str = "rust-analyzer/viewCrateContext";
+}
+
+pub enum ReferenceCount {}
+
+impl Request for ReferenceCount {
+    type Params = lsp_types::TextDocumentPositionParams;
+    type Result = Option<ReferenceCountResult>;
+    const METHOD: &'static str = "rust-analyzer/referenceCount";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceCountResult {
+    /// The number of references found, a lower bound when `capped` is set.
+    pub total: usize,
+    /// Whether counting stopped at `rust-analyzer.references.countLimit`.
+    pub capped: bool,
+    /// The counts per referencing crate, largest first.
+    pub per_crate: Vec<CrateReferenceCount>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CrateReferenceCount {
+    pub name: String,
+    pub count: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateProjectJsonParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateProjectJsonResult {
+    pub problems: Vec<ProjectJsonProblem>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectJsonProblem {
+    pub message: String,
+    /// Position of the problem in the file, if known. Only syntax errors carry
+    /// a position.
+    pub position: Option<Position>,
+}
+
+pub enum ValidateProjectJson {}
+
+impl Request for ValidateProjectJson {
+    type Params = ValidateProjectJsonParams;
+    type Result = ValidateProjectJsonResult;
+    const METHOD: &'static str = "rust-analyzer/validateProjectJson";
+}
+
+pub enum ExportProjectJson {}
+
+impl Request for ExportProjectJson {
+    type Params = ();
+    type Result = serde_json::Value;
+    const METHOD: &'static str = "rust-analyzer/exportProjectJson";
+}
+
+pub enum ListAssists {}
+
+impl Request for ListAssists {
+    type Params = ();
+    type Result = Vec<String>;
+    const METHOD: &'static str = "rust-analyzer/listAssists";
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DiscoverTestParams {
@@ -299,6 +579,44 @@ pub struct ExpandedMacro {
     pub expansion: String,
 }
 
+pub enum GotoExpansion {}
+
+impl Request for GotoExpansion {
+    type Params = ExpandMacroParams;
+    type Result = Option<GotoExpansionResult>;
+    const METHOD: &'static str = "rust-analyzer/gotoExpansion";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GotoExpansionResult {
+    pub name: String,
+    pub expansion: String,
+    pub expandable_ranges: Vec<lsp_types::Range>,
+}
+
+pub enum EvalCfg {}
+
+impl Request for EvalCfg {
+    type Params = EvalCfgParams;
+    type Result = Option<EvalCfgResult>;
+    const METHOD: &'static str = "rust-analyzer/evalCfg";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalCfgParams {
+    pub text_document: TextDocumentIdentifier,
+    pub position: Position,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalCfgResult {
+    pub enabled: Option<bool>,
+    pub decided_by: Option<String>,
+}
+
 pub enum ViewRecursiveMemoryLayout {}
 
 impl Request for ViewRecursiveMemoryLayout {
@@ -326,6 +644,13 @@ pub struct MemoryLayoutNode {
     pub children_len: u64,
 }
 
+pub enum CancelAll {}
+
+impl Notification for CancelAll {
+    type Params = ();
+    const METHOD: &'static str = "rust-analyzer/cancelAll";
+}
+
 pub enum CancelFlycheck {}
 
 impl Notification for CancelFlycheck {
@@ -347,6 +672,13 @@ impl Notification for ClearFlycheck {
     const METHOD: &'static str = "rust-analyzer/clearFlycheck";
 }
 
+pub enum RebuildDiagnostics {}
+
+impl Notification for RebuildDiagnostics {
+    type Params = ();
+    const METHOD: &'static str = "rust-analyzer/rebuildDiagnostics";
+}
+
 pub enum OpenServerLogs {}
 
 impl Notification for OpenServerLogs {
@@ -354,6 +686,21 @@ impl Notification for OpenServerLogs {
     const METHOD: &'static str = "rust-analyzer/openServerLogs";
 }
 
+pub enum FocusedDocument {}
+
+impl Notification for FocusedDocument {
+    type Params = FocusedDocumentParams;
+    const METHOD: &'static str = "rust-analyzer/focusedDocument";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusedDocumentParams {
+    /// The document the user is currently looking at, or `None` if the focus
+    /// moved to something rust-analyzer doesn't track.
+    pub text_document: Option<TextDocumentIdentifier>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct RunFlycheckParams {
@@ -703,6 +1050,43 @@ pub struct ExternalDocsPair {
     pub local: Option<lsp_types::Url>,
 }
 
+pub enum ItemPath {}
+
+impl Request for ItemPath {
+    type Params = lsp_types::TextDocumentPositionParams;
+    type Result = Option<ItemPathResult>;
+    const METHOD: &'static str = "rust-analyzer/itemPath";
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemPathResult {
+    /// The path through the item's defining modules, e.g. `crate::foo::Bar::baz`.
+    pub canonical: String,
+    /// The shortest path under which the item (or its container, for associated
+    /// items) can be imported at the request position, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub importable: Option<String>,
+}
+
+pub enum TodoComments {}
+
+impl Request for TodoComments {
+    type Params = ();
+    type Result = Vec<TodoComment>;
+    const METHOD: &'static str = "rust-analyzer/todoComments";
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoComment {
+    /// The marker that matched, e.g. `TODO`.
+    pub marker: String,
+    /// The comment text from the marker to the end of its line.
+    pub text: String,
+    pub location: lsp_types::Location,
+}
+
 pub enum OpenCargoToml {}
 
 impl Request for OpenCargoToml {
@@ -812,6 +1196,12 @@ pub struct CompletionResolveData {
     pub position: lsp_types::TextDocumentPositionParams,
     pub imports: Vec<CompletionImport>,
     pub version: Option<i32>,
+    /// The trigger character of the original completion request, needed to
+    /// recompute the same completion list when resolving.
+    pub trigger_character: Option<char>,
+    /// Index of the item in the completion list computed server-side, set when
+    /// the `documentation` field is filled in lazily on resolve.
+    pub completion_item_index: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
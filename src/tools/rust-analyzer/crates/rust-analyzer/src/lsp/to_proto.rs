@@ -228,12 +228,32 @@ pub(crate) fn completion_items(
     line_index: &LineIndex,
     version: Option<i32>,
     tdpp: lsp_types::TextDocumentPositionParams,
+    completion_trigger_character: Option<char>,
+    recent_completions: &[String],
     items: Vec<CompletionItem>,
 ) -> Vec<lsp_types::CompletionItem> {
-    let max_relevance = items.iter().map(|it| it.relevance.score()).max().unwrap_or_default();
+    let scoring = config.completion(None).scoring;
+    let score_bump = |item: &CompletionItem| {
+        let recently_used = recent_completions.iter().any(|it| it.as_str() == item.label.as_str());
+        item.score_bump(&scoring, recently_used)
+    };
+    let max_relevance =
+        items.iter().map(|it| it.relevance.score() + score_bump(it)).max().unwrap_or_default();
     let mut res = Vec::with_capacity(items.len());
-    for item in items {
-        completion_item(&mut res, config, line_index, version, &tdpp, max_relevance, item);
+    for (index, item) in items.into_iter().enumerate() {
+        let score_bump = score_bump(&item);
+        completion_item(
+            &mut res,
+            config,
+            line_index,
+            version,
+            &tdpp,
+            max_relevance,
+            score_bump,
+            completion_trigger_character,
+            index,
+            item,
+        );
     }
 
     if let Some(limit) = config.completion(None).limit {
@@ -244,6 +264,7 @@ pub(crate) fn completion_items(
     res
 }
 
+#[allow(clippy::too_many_arguments)]
 fn completion_item(
     acc: &mut Vec<lsp_types::CompletionItem>,
     config: &Config,
@@ -251,6 +272,9 @@ fn completion_item(
     version: Option<i32>,
     tdpp: &lsp_types::TextDocumentPositionParams,
     max_relevance: u32,
+    score_bump: u32,
+    completion_trigger_character: Option<char>,
+    completion_item_index: usize,
     item: CompletionItem,
 ) {
     let insert_replace_support = config.insert_replace_support().then_some(tdpp.position);
@@ -295,6 +319,10 @@ fn completion_item(
         None
     };
 
+    // Defer the (potentially large) documentation to `completionItem/resolve`
+    // when the client is able to ask for it lazily.
+    let defer_docs = config.completion_item_docs_resolve() && item.documentation.is_some();
+
     let mut lsp_item = lsp_types::CompletionItem {
         label: item.label.to_string(),
         detail: item.detail,
@@ -302,7 +330,7 @@ fn completion_item(
         kind: Some(completion_item_kind(item.kind)),
         text_edit: Some(text_edit),
         additional_text_edits: Some(additional_text_edits),
-        documentation: item.documentation.map(documentation),
+        documentation: if defer_docs { None } else { item.documentation.map(documentation) },
         deprecated: Some(item.deprecated),
         tags,
         command,
@@ -310,7 +338,7 @@ fn completion_item(
         ..Default::default()
     };
 
-    if config.completion_label_details_support() {
+    if config.completion_label_details() {
         lsp_item.label_details = Some(lsp_types::CompletionItemLabelDetails {
             detail: item.label_detail.as_ref().map(ToString::to_string),
             description: lsp_item.detail.clone(),
@@ -319,21 +347,28 @@ fn completion_item(
         lsp_item.label.push_str(label_detail.as_str());
     }
 
-    set_score(&mut lsp_item, max_relevance, item.relevance);
+    set_score(&mut lsp_item, max_relevance, score_bump, item.relevance);
 
-    if config.completion(None).enable_imports_on_the_fly && !item.import_to_add.is_empty() {
-        let imports = item
-            .import_to_add
+    let imports = if config.completion(None).enable_imports_on_the_fly {
+        item.import_to_add
             .into_iter()
             .map(|(import_path, import_name)| lsp_ext::CompletionImport {
                 full_import_path: import_path,
                 imported_name: import_name,
             })
-            .collect::<Vec<_>>();
-        if !imports.is_empty() {
-            let data = lsp_ext::CompletionResolveData { position: tdpp.clone(), imports, version };
-            lsp_item.data = Some(to_value(data).unwrap());
-        }
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    if !imports.is_empty() || defer_docs {
+        let data = lsp_ext::CompletionResolveData {
+            position: tdpp.clone(),
+            imports,
+            version,
+            trigger_character: completion_trigger_character,
+            completion_item_index: defer_docs.then_some(completion_item_index),
+        };
+        lsp_item.data = Some(to_value(data).unwrap());
     }
 
     if let Some((label, indel, relevance)) = ref_match {
@@ -342,7 +377,7 @@ fn completion_item(
             .additional_text_edits
             .get_or_insert_with(Default::default)
             .push(self::text_edit(line_index, indel));
-        set_score(&mut lsp_item_with_ref, max_relevance, relevance);
+        set_score(&mut lsp_item_with_ref, max_relevance, score_bump, relevance);
         acc.push(lsp_item_with_ref);
     };
 
@@ -351,14 +386,16 @@ fn completion_item(
     fn set_score(
         res: &mut lsp_types::CompletionItem,
         max_relevance: u32,
+        score_bump: u32,
         relevance: CompletionRelevance,
     ) {
-        if relevance.is_relevant() && relevance.score() == max_relevance {
+        let score = relevance.score() + score_bump;
+        if relevance.is_relevant() && score == max_relevance {
             res.preselect = Some(true);
         }
         // The relevance needs to be inverted to come up with a sort score
         // because the client will sort ascending.
-        let sort_score = relevance.score() ^ 0xFF_FF_FF_FF;
+        let sort_score = score ^ 0xFF_FF_FF_FF;
         // Zero pad the string to ensure values can be properly sorted
         // by the client. Hex format is used because it is easier to
         // visually compare very large values, which the sort text
@@ -480,6 +517,8 @@ pub(crate) fn inlay_hint(
         resolve_hash.is_some(),
         inlay_hint.label,
     )?;
+    let (label, tooltip) =
+        truncate_inlay_hint_label(label, tooltip, snap.config.inlay_hints().max_length);
 
     let data = match resolve_hash {
         Some(hash) if something_to_resolve => Some(
@@ -587,6 +626,52 @@ fn inlay_hint_label(
     Ok((label, tooltip))
 }
 
+/// Truncates hint labels that exceed `max_length` characters, appending `…` and
+/// moving the full text into the hint tooltip so it stays discoverable on hover.
+fn truncate_inlay_hint_label(
+    label: lsp_types::InlayHintLabel,
+    tooltip: Option<lsp_types::InlayHintTooltip>,
+    max_length: Option<usize>,
+) -> (lsp_types::InlayHintLabel, Option<lsp_types::InlayHintTooltip>) {
+    let Some(max_length) = max_length else { return (label, tooltip) };
+
+    let truncate = |text: &str| {
+        let mut char_indices = text.char_indices();
+        // The `…` replaces the first character it cuts off, so the result never
+        // ends up longer than the input.
+        match char_indices.nth(max_length.saturating_sub(1)) {
+            Some((idx, _)) if char_indices.next().is_some() => {
+                Some(format!("{}…", &text[..idx]))
+            }
+            _ => None,
+        }
+    };
+
+    match &label {
+        lsp_types::InlayHintLabel::String(text) => match truncate(text) {
+            Some(truncated) => {
+                let tooltip = tooltip
+                    .or_else(|| Some(lsp_types::InlayHintTooltip::String(text.clone())));
+                (lsp_types::InlayHintLabel::String(truncated), tooltip)
+            }
+            None => (label, tooltip),
+        },
+        lsp_types::InlayHintLabel::LabelParts(parts) => {
+            let full_text = parts.iter().map(|part| &*part.value).collect::<String>();
+            match truncate(&full_text) {
+                Some(truncated) => {
+                    // Per-part locations no longer line up with the truncated text,
+                    // so collapse into a plain string label.
+                    let tooltip =
+                        tooltip.or(Some(lsp_types::InlayHintTooltip::String(full_text)));
+                    (lsp_types::InlayHintLabel::String(truncated), tooltip)
+                }
+                None => (label, tooltip),
+            }
+        }
+    }
+}
+
 static TOKEN_RESULT_COUNTER: AtomicU32 = AtomicU32::new(1);
 
 pub(crate) fn semantic_tokens(
@@ -329,6 +329,19 @@ mod tests {
         let text =
             apply_document_changes(encoding, &text, c![0, 1; 1, 0 => "ț\nc", 0, 2; 0, 2 => "c"]);
         assert_eq!(text, "ațc\ncb");
+
+        // Multi-range edits on a file with DOS line endings; the `\r` is part
+        // of the line, so offsets on lines after an edit must not drift.
+        let text = String::from("fn main() {}\r\nmod foo;\r\n");
+        let text = apply_document_changes(
+            encoding,
+            &text,
+            c![0, 0; 0, 0 => "/// Docs\r\n", 2, 4; 2, 7 => "bar"],
+        );
+        assert_eq!(text, "/// Docs\r\nfn main() {}\r\nmod bar;\r\n");
+        let text =
+            apply_document_changes(encoding, &text, c![1, 12; 2, 0 => "", 1, 3; 1, 7 => "spam"]);
+        assert_eq!(text, "/// Docs\r\nfn spam() {}mod bar;\r\n");
     }
 
     #[test]
@@ -3,7 +3,6 @@
 
 use std::{
     fmt,
-    ops::Div as _,
     time::{Duration, Instant},
 };
 
@@ -12,8 +11,11 @@ use crossbeam_channel::{select, Receiver};
 use ide_db::base_db::{SourceDatabase, SourceRootDatabase, VfsPath};
 use lsp_server::{Connection, Notification, Request};
 use lsp_types::{notification::Notification as _, TextDocumentIdentifier};
+use parking_lot::Mutex;
+use rustc_hash::{FxHashMap, FxHashSet};
 use stdx::thread::ThreadIntent;
 use tracing::{error, span, Level};
+use triomphe::Arc;
 use vfs::{loader::LoadingProgress, AbsPathBuf, FileId};
 
 use crate::{
@@ -29,6 +31,7 @@ use crate::{
         utils::{notification_is, Progress},
     },
     lsp_ext,
+    mem_docs::MemDocs,
     reload::{BuildDataProgress, ProcMacroProgress, ProjectWorkspaceProgress},
     test_runner::{CargoTestMessage, TestState},
 };
@@ -58,6 +61,33 @@ pub fn main_loop(config: Config, connection: Connection) -> anyhow::Result<()> {
     GlobalState::new(connection.sender, config).run(connection.receiver)
 }
 
+/// How long an incoming request may stay in flight before we warn the user
+/// that the server might be stuck.
+const PENDING_REQUEST_WARN_THRESHOLD: Duration = Duration::from_secs(300);
+
+/// How many tasks may pile up in the task pool before we consider it saturated
+/// and start shedding non-essential background work. To avoid oscillating at
+/// the boundary, shedding only stops once the backlog has drained to half of
+/// this.
+const TASK_POOL_SHED_THRESHOLD: usize = 256;
+
+/// Longest string the request echo log reproduces verbatim. Anything bigger is
+/// almost certainly document text and gets elided to keep the log readable.
+const REQUEST_ECHO_MAX_STRING: usize = 128;
+
+/// Replaces oversized strings in `value`, recursively, with a placeholder
+/// stating their length.
+fn redact_large_text(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(it) if it.len() > REQUEST_ECHO_MAX_STRING => {
+            *it = format!("<{} bytes redacted>", it.len());
+        }
+        serde_json::Value::Array(it) => it.iter_mut().for_each(redact_large_text),
+        serde_json::Value::Object(it) => it.values_mut().for_each(redact_large_text),
+        _ => (),
+    }
+}
+
 enum Event {
     Lsp(lsp_server::Message),
     Task(Task),
@@ -66,6 +96,8 @@ enum Event {
     Flycheck(FlycheckMessage),
     TestResult(CargoTestMessage),
     DiscoverProject(DiscoverProjectMessage),
+    /// An in-flight request has been pending for longer than `Config::requestTimeout`.
+    RequestTimeout,
 }
 
 impl fmt::Display for Event {
@@ -78,6 +110,7 @@ impl fmt::Display for Event {
             Event::QueuedTask(_) => write!(f, "Event::QueuedTask"),
             Event::TestResult(_) => write!(f, "Event::TestResult"),
             Event::DiscoverProject(_) => write!(f, "Event::DiscoverProject"),
+            Event::RequestTimeout => write!(f, "Event::RequestTimeout"),
         }
     }
 }
@@ -153,6 +186,7 @@ impl fmt::Debug for Event {
             Event::Flycheck(it) => fmt::Debug::fmt(it, f),
             Event::TestResult(it) => fmt::Debug::fmt(it, f),
             Event::DiscoverProject(it) => fmt::Debug::fmt(it, f),
+            Event::RequestTimeout => f.debug_struct("RequestTimeout").finish(),
         }
     }
 }
@@ -173,6 +207,9 @@ impl GlobalState {
         }
 
         if self.config.discover_workspace_config().is_none() {
+            // Loading the workspace is about to shell out to cargo, so verify
+            // up front that the toolchain is usable at all.
+            self.toolchain_self_check();
             self.fetch_workspaces_queue.request_op(
                 "startup".to_owned(),
                 FetchWorkspaceRequest { path: None, force_crate_graph_reload: false },
@@ -224,6 +261,16 @@ impl GlobalState {
                 scheme: None,
                 pattern: Some("**/Cargo.lock".into()),
             },
+            lsp_types::DocumentFilter {
+                language: None,
+                scheme: None,
+                pattern: Some("**/rust-toolchain".into()),
+            },
+            lsp_types::DocumentFilter {
+                language: None,
+                scheme: None,
+                pattern: Some("**/rust-toolchain.toml".into()),
+            },
         ];
         selectors.extend(additional_filters);
 
@@ -249,7 +296,19 @@ impl GlobalState {
         &self,
         inbox: &Receiver<lsp_server::Message>,
     ) -> Result<Option<Event>, crossbeam_channel::RecvError> {
+        // Wake up when the oldest in-flight request hits `Config::requestTimeout`, so it can
+        // be answered with an error instead of leaving the client waiting indefinitely.
+        let request_deadline = match (self.config.request_timeout(), self.oldest_pending_request())
+        {
+            (Some(timeout), Some((_, start))) => crossbeam_channel::after(
+                (start + timeout).saturating_duration_since(Instant::now()),
+            ),
+            _ => crossbeam_channel::never(),
+        };
         select! {
+            recv(request_deadline) -> _instant =>
+                return Ok(Some(Event::RequestTimeout)),
+
             recv(inbox) -> msg =>
                 return Ok(msg.ok().map(Event::Lsp)),
 
@@ -262,6 +321,9 @@ impl GlobalState {
             recv(self.fmt_pool.receiver) -> task =>
                 task.map(Event::Task),
 
+            recv(self.diagnostics_pool.receiver) -> task =>
+                task.map(Event::Task),
+
             recv(self.loader.receiver) -> task =>
                 task.map(Event::Vfs),
 
@@ -290,6 +352,18 @@ impl GlobalState {
             }
         }
 
+        // Interactive requests are never shed, only work we started speculatively.
+        let backlog = self.task_pool.handle.len();
+        if !self.task_pool_saturated && backlog >= TASK_POOL_SHED_THRESHOLD {
+            self.task_pool_saturated = true;
+            tracing::warn!(
+                "task pool backlog reached {backlog}, shedding background work until it drains"
+            );
+        } else if self.task_pool_saturated && backlog < TASK_POOL_SHED_THRESHOLD / 2 {
+            self.task_pool_saturated = false;
+            tracing::info!("task pool backlog drained to {backlog}, resuming background work");
+        }
+
         let was_quiescent = self.is_quiescent();
         match event {
             Event::Lsp(msg) => match msg {
@@ -390,6 +464,7 @@ impl GlobalState {
                     self.handle_discover_msg(message);
                 }
             }
+            Event::RequestTimeout => self.cancel_timed_out_requests(),
         }
         let event_handling_duration = loop_start.elapsed();
         let (state_changed, memdocs_added_or_removed) = if self.vfs_done {
@@ -434,8 +509,19 @@ impl GlobalState {
 
             let project_or_mem_docs_changed =
                 became_quiescent || state_changed || memdocs_added_or_removed;
-            if project_or_mem_docs_changed && self.config.publish_diagnostics() {
-                self.update_diagnostics();
+            // With `diagnostics.onStartup` set to `deferred`, the initial
+            // refresh waits for the user to interact with a document instead
+            // of firing as soon as the workspace has loaded.
+            if (project_or_mem_docs_changed || self.deferred_diagnostics_refresh)
+                && self.config.publish_diagnostics()
+                && !self.startup_diagnostics_deferred
+            {
+                if self.task_pool_saturated {
+                    self.deferred_diagnostics_refresh = true;
+                } else {
+                    self.deferred_diagnostics_refresh = false;
+                    self.update_diagnostics();
+                }
             }
             if project_or_mem_docs_changed && self.config.test_explorer() {
                 self.update_tests();
@@ -443,14 +529,18 @@ impl GlobalState {
         }
 
         if let Some(diagnostic_changes) = self.diagnostics.take_changes() {
+            let disabled_native = self.config.diagnostics(None).disabled;
             for file_id in diagnostic_changes {
                 let uri = file_id_to_url(&self.vfs.read().0, file_id);
                 let version = from_proto::vfs_path(&uri)
                     .ok()
                     .and_then(|path| self.mem_docs.get(&path).map(|it| it.version));
 
-                let diagnostics =
-                    self.diagnostics.diagnostics_for(file_id).cloned().collect::<Vec<_>>();
+                let diagnostics = self
+                    .diagnostics
+                    .diagnostics_for(file_id, &disabled_native)
+                    .cloned()
+                    .collect::<Vec<_>>();
                 self.publish_diagnostics(uri, version, diagnostics);
             }
         }
@@ -473,12 +563,34 @@ impl GlobalState {
             }
         }
 
-        if let Some((cause, ())) = self.prime_caches_queue.should_start_op() {
-            self.prime_caches(cause);
+        // Cache priming is the first work to be shed; the op stays queued and
+        // starts on a later turn once the backlog has drained.
+        if !self.task_pool_saturated {
+            if let Some((cause, ())) = self.prime_caches_queue.should_start_op() {
+                self.prime_caches(cause);
+            }
         }
 
         self.update_status_or_notify();
 
+        // Warn if a request has been in flight for a long time. This helps to tell
+        // "server is busy indexing" apart from "server is deadlocked" in bug reports.
+        if let Some((method, start)) = self.oldest_pending_request() {
+            let age = start.elapsed();
+            if age >= PENDING_REQUEST_WARN_THRESHOLD && self.pending_request_warned != Some(start) {
+                self.pending_request_warned = Some(start);
+                tracing::warn!("request `{method}` has been pending for {age:.2?}");
+                self.show_message(
+                    lsp_types::MessageType::WARNING,
+                    format!(
+                        "The `{method}` request has been pending for {age:.0?}. If the workspace \
+                         is not still loading, the server might be stuck."
+                    ),
+                    true,
+                );
+            }
+        }
+
         let loop_duration = loop_start.elapsed();
         if loop_duration > Duration::from_millis(100) && was_quiescent {
             tracing::warn!("overly long loop turn took {loop_duration:?} (event handling took {event_handling_duration:?}): {event_dbg_msg}");
@@ -508,12 +620,13 @@ impl GlobalState {
         });
     }
 
-    fn update_diagnostics(&mut self) {
+    pub(crate) fn update_diagnostics(&mut self) {
         let db = self.analysis_host.raw_database();
         let generation = self.diagnostics.next_generation();
         let subscriptions = {
             let vfs = &self.vfs.read().0;
-            self.mem_docs
+            let mut subscriptions = self
+                .mem_docs
                 .iter()
                 .map(|path| vfs.file_id(path).unwrap())
                 .filter(|&file_id| {
@@ -525,12 +638,21 @@ impl GlobalState {
                     // forever if we emitted them here.
                     !db.source_root(source_root).is_library
                 })
-                .collect::<std::sync::Arc<_>>()
+                .collect::<Vec<_>>();
+            // Diagnostics are computed chunk by chunk, so move the focused
+            // document to the front to have its results published first.
+            if let Some(focused) = self.focused_document.as_ref().and_then(|path| vfs.file_id(path))
+            {
+                if let Some(idx) = subscriptions.iter().position(|&it| it == focused) {
+                    subscriptions.swap(0, idx);
+                }
+            }
+            std::sync::Arc::<[_]>::from(subscriptions)
         };
         tracing::trace!("updating notifications for {:?}", subscriptions);
-        // Split up the work on multiple threads, but we don't wanna fill the entire task pool with
-        // diagnostic tasks, so we limit the number of tasks to a quarter of the total thread pool.
-        let max_tasks = self.config.main_loop_num_threads().div(4).max(1);
+        // Diagnostics run on a pool of their own, so saturating it does not
+        // delay interactive requests; split the work across all of its threads.
+        let max_tasks = self.config.diagnostics_num_threads();
         let chunk_length = subscriptions.len() / max_tasks;
         let remainder = subscriptions.len() % max_tasks;
 
@@ -542,10 +664,10 @@ impl GlobalState {
             if slice.is_empty() {
                 break;
             }
-            // Diagnostics are triggered by the user typing
-            // so we run them on a latency sensitive thread.
+            // The dedicated pool runs with worker intent: diagnostics are
+            // triggered by typing, but a pending completion matters more.
             let snapshot = self.snapshot();
-            self.task_pool.handle.spawn_with_sender(ThreadIntent::LatencySensitive, {
+            self.diagnostics_pool.handle.spawn_with_sender(ThreadIntent::Worker, {
                 let subscriptions = subscriptions.clone();
                 // Do not fetch semantic diagnostics (and populate query results) if we haven't even
                 // loaded the initial workspace yet.
@@ -666,6 +788,24 @@ impl GlobalState {
             Task::Retry(req) if !self.is_completed(&req) => self.on_request(req),
             Task::Retry(_) => (),
             Task::Diagnostics(kind) => {
+                // Keep track of the files whose macro expansions failed, so the
+                // expansions can be retried when new proc-macros are loaded.
+                if let DiagnosticsTaskKind::Semantic(_, diagnostics) = &kind {
+                    for (file_id, diagnostics) in diagnostics {
+                        let failed = diagnostics.iter().any(|d| {
+                            matches!(
+                                &d.code,
+                                Some(lsp_types::NumberOrString::String(code))
+                                    if code == "macro-error"
+                            )
+                        });
+                        if failed {
+                            self.proc_macro_failed_files.insert(*file_id);
+                        } else {
+                            self.proc_macro_failed_files.remove(file_id);
+                        }
+                    }
+                }
                 self.diagnostics.set_native_diagnostics(kind);
             }
             Task::PrimeCaches(progress) => match progress {
@@ -691,6 +831,7 @@ impl GlobalState {
                         if let Err(e) = self.fetch_workspace_error() {
                             error!("FetchWorkspaceError:\n{e}");
                         }
+                        self.publish_workspace_fetch_diagnostics();
                         self.wants_to_switch = Some("fetched workspace".to_owned());
                         (Progress::End, None)
                     }
@@ -747,8 +888,9 @@ impl GlobalState {
                 let (state, msg) = match progress {
                     ProcMacroProgress::Begin => (Some(Progress::Begin), None),
                     ProcMacroProgress::Report(msg) => (Some(Progress::Report), Some(msg)),
-                    ProcMacroProgress::End(proc_macro_load_result) => {
+                    ProcMacroProgress::End(proc_macro_load_result, abi_mismatches) => {
                         self.fetch_proc_macros_queue.op_completed(true);
+                        self.proc_macro_abi_mismatches = abi_mismatches;
                         self.set_proc_macros(proc_macro_load_result);
                         (Some(Progress::End), None)
                     }
@@ -772,6 +914,11 @@ impl GlobalState {
             vfs::loader::Message::Changed { files } | vfs::loader::Message::Loaded { files } => {
                 let _p = tracing::info_span!("GlobalState::handle_vfs_msg{changed/load}").entered();
                 let vfs = &mut self.vfs.write().0;
+                let files = if is_changed {
+                    translate_dir_renames(vfs, &self.mem_docs, files)
+                } else {
+                    files
+                };
                 for (path, contents) in files {
                     let path = VfsPath::from(path);
                     // if the file is in mem docs, it's managed by the client via notifications
@@ -951,12 +1098,19 @@ impl GlobalState {
             FlycheckMessage::ClearDiagnostics { id } => self.diagnostics.clear_check(id),
 
             FlycheckMessage::Progress { id, progress } => {
-                let (state, message) = match progress {
-                    flycheck::Progress::DidStart => (Progress::Begin, None),
-                    flycheck::Progress::DidCheckCrate(target) => (Progress::Report, Some(target)),
+                let (state, message, fraction) = match progress {
+                    flycheck::Progress::DidStart => (Progress::Begin, None, None),
+                    flycheck::Progress::DidCheckCrate(target, packages) => (
+                        Progress::Report,
+                        Some(match packages {
+                            Some((checked, total)) => format!("{checked}/{total} packages"),
+                            None => target,
+                        }),
+                        packages.map(|(checked, total)| checked as f64 / total as f64),
+                    ),
                     flycheck::Progress::DidCancel => {
                         self.last_flycheck_error = None;
-                        (Progress::End, None)
+                        (Progress::End, None, None)
                     }
                     flycheck::Progress::DidFailToRestart(err) => {
                         self.last_flycheck_error =
@@ -966,7 +1120,7 @@ impl GlobalState {
                     flycheck::Progress::DidFinish(result) => {
                         self.last_flycheck_error =
                             result.err().map(|err| format!("cargo check failed to start: {err}"));
-                        (Progress::End, None)
+                        (Progress::End, None, None)
                     }
                 };
 
@@ -981,7 +1135,7 @@ impl GlobalState {
                     &title,
                     state,
                     message,
-                    None,
+                    fraction,
                     Some(format!("rust-analyzer/flycheck/{id}")),
                 );
             }
@@ -993,9 +1147,50 @@ impl GlobalState {
         let _p =
             span!(Level::INFO, "GlobalState::on_new_request", req.method = ?req.method).entered();
         self.register_request(&req, request_received);
+        if let Some(sink) = self.request_echo_sink.clone() {
+            self.echo_request(&req, sink);
+        }
         self.on_request(req);
     }
 
+    /// Echoes `req` to the debug sink configured via `RA_ECHO_REQUESTS`.
+    ///
+    /// The redaction walk and serialization happen on the task pool, so the
+    /// main loop never blocks on the log file.
+    fn echo_request(&mut self, req: &Request, sink: Arc<Mutex<std::fs::File>>) {
+        let method = req.method.clone();
+        let mut params = req.params.clone();
+        self.task_pool.handle.spawn_with_sender(ThreadIntent::Worker, move |_| {
+            use std::io::Write as _;
+            redact_large_text(&mut params);
+            let line = serde_json::json!({ "method": method, "params": params });
+            let _ = writeln!(sink.lock(), "{line}");
+        });
+    }
+
+    /// Answers every in-flight request that has exceeded `Config::requestTimeout` with an
+    /// error and cooperatively cancels the analysis backing it.
+    ///
+    /// Cancellation goes through salsa, so it only takes effect at the next query boundary.
+    /// Other in-flight requests lose their snapshots too; they are either retried or
+    /// answered with `ContentModified`, the same way an incoming edit would bump them.
+    fn cancel_timed_out_requests(&mut self) {
+        let Some(timeout) = self.config.request_timeout() else { return };
+        let timed_out = self.requests_pending_for(timeout);
+        if timed_out.is_empty() {
+            return;
+        }
+        self.analysis_host.request_cancellation();
+        for (id, method) in timed_out {
+            tracing::warn!("request `{method}` timed out after {timeout:?}");
+            self.respond(lsp_server::Response::new_err(
+                id,
+                lsp_server::ErrorCode::RequestFailed as i32,
+                format!("request timed out after {timeout:?}"),
+            ));
+        }
+    }
+
     /// Handles a request.
     fn on_request(&mut self, req: Request) {
         let mut dispatcher = RequestDispatcher { req: Some(req), global_state: self };
@@ -1026,7 +1221,10 @@ impl GlobalState {
         dispatcher
             // Request handlers that must run on the main thread
             // because they mutate GlobalState:
+            .on_sync_mut::<lsp_ext::ShuffleCrateGraph>(handlers::handle_shuffle_crate_graph)
             .on_sync_mut::<lsp_ext::ReloadWorkspace>(handlers::handle_workspace_reload)
+            .on_sync_mut::<lsp_ext::SetCfgOverrides>(handlers::handle_set_cfg_overrides)
+            .on_sync_mut::<lsp_ext::ClearCfgOverrides>(handlers::handle_clear_cfg_overrides)
             .on_sync_mut::<lsp_ext::RebuildProcMacros>(handlers::handle_proc_macros_rebuild)
             .on_sync_mut::<lsp_ext::MemoryUsage>(handlers::handle_memory_usage)
             .on_sync_mut::<lsp_ext::RunTest>(handlers::handle_run_test)
@@ -1058,6 +1256,8 @@ impl GlobalState {
             // All other request handlers
             .on::<RETRY, lsp_request::DocumentSymbolRequest>(handlers::handle_document_symbol)
             .on::<RETRY, lsp_request::FoldingRangeRequest>(handlers::handle_folding_range)
+            .on::<RETRY, lsp_request::DocumentColor>(handlers::handle_document_color)
+            .on::<NO_RETRY, lsp_request::ColorPresentationRequest>(handlers::handle_color_presentation)
             .on::<NO_RETRY, lsp_request::SignatureHelpRequest>(handlers::handle_signature_help)
             .on::<RETRY, lsp_request::WillRenameFiles>(handlers::handle_will_rename_files)
             .on::<NO_RETRY, lsp_request::GotoDefinition>(handlers::handle_goto_definition)
@@ -1072,24 +1272,39 @@ impl GlobalState {
             .on::<NO_RETRY, lsp_request::Rename>(handlers::handle_rename)
             .on::<NO_RETRY, lsp_request::References>(handlers::handle_references)
             .on::<NO_RETRY, lsp_request::DocumentHighlightRequest>(handlers::handle_document_highlight)
+            .on::<NO_RETRY, lsp_request::LinkedEditingRange>(handlers::handle_linked_editing_range)
             .on::<NO_RETRY, lsp_request::CallHierarchyPrepare>(handlers::handle_call_hierarchy_prepare)
             .on::<NO_RETRY, lsp_request::CallHierarchyIncomingCalls>(handlers::handle_call_hierarchy_incoming)
             .on::<NO_RETRY, lsp_request::CallHierarchyOutgoingCalls>(handlers::handle_call_hierarchy_outgoing)
             // All other request handlers (lsp extension)
             .on::<RETRY, lsp_ext::FetchDependencyList>(handlers::fetch_dependency_list)
+            .on::<RETRY, lsp_ext::ReverseDependencies>(handlers::handle_reverse_dependencies)
             .on::<RETRY, lsp_ext::AnalyzerStatus>(handlers::handle_analyzer_status)
+            .on::<RETRY, lsp_ext::EffectiveConfig>(handlers::handle_effective_config)
+            .on::<RETRY, lsp_ext::CaptureState>(handlers::handle_capture_state)
+            .on::<RETRY, lsp_ext::MemDocs>(handlers::handle_mem_docs)
+            .on::<NO_RETRY, lsp_ext::LoadCrate>(handlers::handle_load_crate)
             .on::<RETRY, lsp_ext::ViewFileText>(handlers::handle_view_file_text)
             .on::<RETRY, lsp_ext::ViewCrateGraph>(handlers::handle_view_crate_graph)
+            .on::<RETRY, lsp_ext::CrateGraphStats>(handlers::handle_crate_graph_stats)
             .on::<RETRY, lsp_ext::ViewItemTree>(handlers::handle_view_item_tree)
+            .on::<RETRY, lsp_ext::ViewCrateContext>(handlers::handle_view_crate_context)
+            .on::<NO_RETRY, lsp_ext::ValidateProjectJson>(handlers::handle_validate_project_json)
+            .on::<RETRY, lsp_ext::ExportProjectJson>(handlers::handle_export_project_json)
+            .on::<RETRY, lsp_ext::ListAssists>(handlers::handle_list_assists)
             .on::<RETRY, lsp_ext::DiscoverTest>(handlers::handle_discover_test)
             .on::<RETRY, lsp_ext::WorkspaceSymbol>(handlers::handle_workspace_symbol)
+            .on::<NO_RETRY, lsp_ext::ReferenceCount>(handlers::handle_reference_count)
             .on::<NO_RETRY, lsp_ext::Ssr>(handlers::handle_ssr)
             .on::<NO_RETRY, lsp_ext::ViewRecursiveMemoryLayout>(handlers::handle_view_recursive_memory_layout)
             .on::<NO_RETRY, lsp_ext::SyntaxTree>(handlers::handle_syntax_tree)
+            .on::<NO_RETRY, lsp_ext::TypeOf>(handlers::handle_type_of)
             .on::<NO_RETRY, lsp_ext::ViewHir>(handlers::handle_view_hir)
             .on::<NO_RETRY, lsp_ext::ViewMir>(handlers::handle_view_mir)
             .on::<NO_RETRY, lsp_ext::InterpretFunction>(handlers::handle_interpret_function)
             .on::<NO_RETRY, lsp_ext::ExpandMacro>(handlers::handle_expand_macro)
+            .on::<NO_RETRY, lsp_ext::GotoExpansion>(handlers::handle_goto_expansion)
+            .on::<NO_RETRY, lsp_ext::EvalCfg>(handlers::handle_eval_cfg)
             .on::<NO_RETRY, lsp_ext::ParentModule>(handlers::handle_parent_module)
             .on::<NO_RETRY, lsp_ext::Runnables>(handlers::handle_runnables)
             .on::<NO_RETRY, lsp_ext::RelatedTests>(handlers::handle_related_tests)
@@ -1097,6 +1312,8 @@ impl GlobalState {
             .on_identity::<RETRY, lsp_ext::CodeActionResolveRequest, _>(handlers::handle_code_action_resolve)
             .on::<NO_RETRY, lsp_ext::HoverRequest>(handlers::handle_hover)
             .on::<NO_RETRY, lsp_ext::ExternalDocs>(handlers::handle_open_docs)
+            .on::<NO_RETRY, lsp_ext::ItemPath>(handlers::handle_item_path)
+            .on::<NO_RETRY, lsp_ext::TodoComments>(handlers::handle_todo_comments)
             .on::<NO_RETRY, lsp_ext::OpenCargoToml>(handlers::handle_open_cargo_toml)
             .on::<NO_RETRY, lsp_ext::MoveItem>(handlers::handle_move_item)
             //
@@ -1131,11 +1348,76 @@ impl GlobalState {
             .on_sync_mut::<notifs::DidChangeWatchedFiles>(
                 handlers::handle_did_change_watched_files,
             )?
+            .on_sync_mut::<lsp_ext::CancelAll>(handlers::handle_cancel_all)?
             .on_sync_mut::<lsp_ext::CancelFlycheck>(handlers::handle_cancel_flycheck)?
             .on_sync_mut::<lsp_ext::ClearFlycheck>(handlers::handle_clear_flycheck)?
             .on_sync_mut::<lsp_ext::RunFlycheck>(handlers::handle_run_flycheck)?
+            .on_sync_mut::<lsp_ext::RebuildDiagnostics>(handlers::handle_rebuild_diagnostics)?
             .on_sync_mut::<lsp_ext::AbortRunTest>(handlers::handle_abort_run_test)?
+            .on_sync_mut::<lsp_ext::FocusedDocument>(handlers::handle_focused_document)?
             .finish();
         Ok(())
     }
 }
+
+/// Recognizes a directory move in a batch of loader events.
+///
+/// A directory rename reaches us as a flood of seemingly unrelated deletions
+/// and creations. Where a deleted file's content hash matches that of a
+/// created file with the same file name, the pair is applied as an in-place
+/// [`vfs::Vfs::rename`], preserving the `FileId` so salsa keeps the derived
+/// data of moved-but-unchanged files. All other events are returned for
+/// regular processing.
+fn translate_dir_renames(
+    vfs: &mut vfs::Vfs,
+    mem_docs: &MemDocs,
+    files: Vec<(AbsPathBuf, Option<Vec<u8>>)>,
+) -> Vec<(AbsPathBuf, Option<Vec<u8>>)> {
+    let mut deletions: FxHashMap<(u64, Option<String>), Vec<VfsPath>> = FxHashMap::default();
+    for (path, contents) in &files {
+        if contents.is_some() {
+            continue;
+        }
+        let vfs_path = VfsPath::from(path.clone());
+        if mem_docs.contains(&vfs_path) {
+            continue;
+        }
+        if let Some(hash) = vfs.file_id(&vfs_path).and_then(|it| vfs.file_content_hash(it)) {
+            let key = (hash, path.file_name().map(ToOwned::to_owned));
+            deletions.entry(key).or_default().push(vfs_path);
+        }
+    }
+    if deletions.is_empty() {
+        return files;
+    }
+
+    let mut consumed = FxHashSet::default();
+    let mut res = Vec::with_capacity(files.len());
+    for (path, contents) in files {
+        if let Some(bytes) = &contents {
+            let vfs_path = VfsPath::from(path.clone());
+            if !mem_docs.contains(&vfs_path) && vfs.file_id(&vfs_path).is_none() {
+                let key = (
+                    stdx::hash_once::<rustc_hash::FxHasher>(&**bytes),
+                    path.file_name().map(ToOwned::to_owned),
+                );
+                if let Some(candidates) = deletions.get_mut(&key) {
+                    if let Some(old) = candidates.pop() {
+                        if vfs.rename(&old, vfs_path) {
+                            consumed.insert(old);
+                            continue;
+                        }
+                        candidates.push(old);
+                    }
+                }
+            }
+        }
+        res.push((path, contents));
+    }
+    // Drop the deletion halves of the renames; their files live on under the
+    // new paths.
+    res.retain(|(path, contents)| {
+        contents.is_some() || !consumed.contains(&VfsPath::from(path.clone()))
+    });
+    res
+}
@@ -50,6 +50,10 @@ impl MemDocs {
         self.mem_docs.keys()
     }
 
+    pub(crate) fn len(&self) -> usize {
+        self.mem_docs.len()
+    }
+
     pub(crate) fn take_changes(&mut self) -> bool {
         mem::replace(&mut self.added_or_removed, false)
     }
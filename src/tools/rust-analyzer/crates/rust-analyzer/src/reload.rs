@@ -17,8 +17,8 @@ use std::{iter, mem};
 
 use hir::{db::DefDatabase, ChangeWithProcMacros, ProcMacros, ProcMacrosBuilder};
 use ide_db::{
-    base_db::{salsa::Durability, CrateGraph, ProcMacroPaths, Version},
-    FxHashMap,
+    base_db::{salsa::Durability, CrateGraph, ProcMacroPaths, SourceDatabase, Version},
+    FxHashMap, FxHashSet,
 };
 use itertools::Itertools;
 use load_cargo::{load_proc_macro, ProjectFolders};
@@ -33,6 +33,7 @@ use crate::{
     config::{Config, FilesWatcher, LinkedProject},
     flycheck::{FlycheckConfig, FlycheckHandle},
     global_state::{FetchWorkspaceRequest, GlobalState},
+    lsp::to_proto::url_from_abs_path,
     lsp_ext,
     main_loop::{DiscoverProjectParam, Task},
     op_queue::Cause,
@@ -57,7 +58,7 @@ pub(crate) enum BuildDataProgress {
 pub(crate) enum ProcMacroProgress {
     Begin,
     Report(String),
-    End(ProcMacros),
+    End(ProcMacros, Vec<String>),
 }
 
 impl GlobalState {
@@ -71,11 +72,26 @@ impl GlobalState {
             && self.vfs_progress_config_version >= self.vfs_config_version
     }
 
+    /// Whether `path` is an env-file referenced by one of the loaded
+    /// `rust-project.json` workspaces. The cfgs and editions read from it are
+    /// part of the crate graph, so a change to it warrants a reload.
+    pub(crate) fn is_workspace_env_file(&self, path: &AbsPath) -> bool {
+        self.workspaces.iter().flat_map(|ws| ws.env_files()).any(|it| it == path)
+    }
+
     pub(crate) fn update_configuration(&mut self, config: Config) {
         let _p = tracing::info_span!("GlobalState::update_configuration").entered();
         let old_config = mem::replace(&mut self.config, Arc::new(config));
-        if self.config.lru_parse_query_capacity() != old_config.lru_parse_query_capacity() {
+        if self.config.lru_parse_query_capacity() != old_config.lru_parse_query_capacity()
+            || self.config.lru_macro_expansion_capacity()
+                != old_config.lru_macro_expansion_capacity()
+        {
+            // `update_lru_capacity` resets the macro expansion cache to its
+            // derived default, so the dedicated size is applied afterwards.
             self.analysis_host.update_lru_capacity(self.config.lru_parse_query_capacity());
+            if let Some(capacity) = self.config.lru_macro_expansion_capacity() {
+                self.analysis_host.update_macro_expansion_lru_capacity(capacity);
+            }
         }
         if self.config.lru_query_capacities_config() != old_config.lru_query_capacities_config() {
             self.analysis_host.update_lru_capacities(
@@ -83,14 +99,49 @@ impl GlobalState {
             );
         }
 
-        if self.config.linked_or_discovered_projects() != old_config.linked_or_discovered_projects()
-        {
+        if self.workspace_config_changed(&old_config) {
             let req = FetchWorkspaceRequest { path: None, force_crate_graph_reload: false };
-            self.fetch_workspaces_queue.request_op("discovered projects changed".to_owned(), req)
+            self.fetch_workspaces_queue
+                .request_op("workspace-affecting config changed".to_owned(), req)
         } else if self.config.flycheck() != old_config.flycheck() {
+            // The new flags may check fewer targets; drop the old diagnostics
+            // so ones for targets that are no longer checked don't linger.
+            self.diagnostics.clear_check_all();
             self.reload_flycheck();
         }
 
+        if !self.config.diagnostics_on_startup_deferred() {
+            self.startup_diagnostics_deferred = false;
+        }
+
+        if self.config.diagnostics(None).disabled != old_config.diagnostics(None).disabled {
+            // The disabled codes are applied to retained native diagnostics at
+            // publish time; re-publish so the change takes effect without
+            // waiting for the next recomputation.
+            self.diagnostics.refresh_native();
+        }
+
+        if self.config.diagnostics_message_replacements()
+            != old_config.diagnostics_message_replacements()
+        {
+            // Compile the patterns once here instead of on every publish.
+            self.diagnostics_message_replacements = Arc::new(
+                self.config
+                    .diagnostics_message_replacements()
+                    .iter()
+                    .filter_map(|(pattern, replacement)| match regex::Regex::new(pattern) {
+                        Ok(pattern) => Some((pattern, replacement.clone())),
+                        Err(e) => {
+                            tracing::error!(
+                                "invalid diagnostics.messageReplacements pattern `{pattern}`: {e}"
+                            );
+                            None
+                        }
+                    })
+                    .collect(),
+            );
+        }
+
         if self.analysis_host.raw_database().expand_proc_attr_macros()
             != self.config.expand_proc_attr_macros()
         {
@@ -101,6 +152,52 @@ impl GlobalState {
         }
     }
 
+    /// Checks that `cargo` and `rustc` can be spawned at all. Many "nothing
+    /// works" reports come down to the toolchain not being on `PATH` (or being
+    /// broken); this turns that silent failure into an explicit message.
+    pub(crate) fn toolchain_self_check(&mut self) {
+        let mut errors = String::new();
+        for tool in [toolchain::Tool::Cargo, toolchain::Tool::Rustc] {
+            let path = tool.path();
+            match std::process::Command::new(&path).arg("--version").output() {
+                Ok(output) if output.status.success() => {}
+                Ok(output) => {
+                    format_to!(errors, "`{path} --version` failed ({})", output.status);
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    let stderr = stderr.trim();
+                    if !stderr.is_empty() {
+                        format_to!(errors, ": {stderr}");
+                    }
+                    errors.push('\n');
+                }
+                Err(err) => format_to!(errors, "Failed to run `{path} --version`: {err}\n"),
+            }
+        }
+        self.toolchain_self_check_error = if errors.is_empty() {
+            None
+        } else {
+            let path_var = std::env::var("PATH").unwrap_or_default();
+            let message =
+                format!("Failed to verify the Rust toolchain:\n{errors}PATH is `{path_var}`");
+            self.show_message(lsp_types::MessageType::ERROR, message.clone(), false);
+            Some(message)
+        };
+    }
+
+    /// Whether swapping `old_config` for the current config requires re-running
+    /// project discovery and `cargo metadata`. These are exactly the keys that
+    /// [`GlobalState::fetch_workspaces`] reads: the set of linked or discovered
+    /// projects, the cargo configuration (features, target, sysroot, extra
+    /// environment, ...), the workspace discovery command and the file filters
+    /// that source roots are built from. Changes to any other key make do with
+    /// the cheap reactions in [`GlobalState::update_configuration`].
+    fn workspace_config_changed(&self, old_config: &Config) -> bool {
+        self.config.linked_or_discovered_projects() != old_config.linked_or_discovered_projects()
+            || self.config.cargo() != old_config.cargo()
+            || self.config.discover_workspace_config() != old_config.discover_workspace_config()
+            || self.config.files() != old_config.files()
+    }
+
     pub(crate) fn current_status(&self) -> lsp_ext::ServerStatusParams {
         let quiescent = self.is_quiescent();
         let mut status =
@@ -122,6 +219,12 @@ impl GlobalState {
                 "Proc-macros and/or build scripts have changed and need to be rebuilt.\n\n",
             );
         }
+        if !self.cfg_overrides.is_empty() {
+            status.health |= lsp_ext::Health::Warning;
+            message.push_str(
+                "Session cfg overrides are active, the crate graph does not reflect the real cfgs. Reset them with the `rust-analyzer/clearCfgOverrides` command.\n\n",
+            );
+        }
         if self.fetch_build_data_error().is_err() {
             status.health |= lsp_ext::Health::Warning;
             message.push_str("Failed to run build scripts of some packages.\n\n");
@@ -135,6 +238,21 @@ impl GlobalState {
             message.push_str(err);
             message.push('\n');
         }
+        if let Some(err) = &self.toolchain_self_check_error {
+            status.health |= lsp_ext::Health::Warning;
+            message.push_str(err);
+            message.push_str("\n\n");
+        }
+        if !self.proc_macro_abi_mismatches.is_empty() {
+            status.health |= lsp_ext::Health::Warning;
+            message.push_str(
+                "Expansion is disabled for proc-macros built by a different toolchain than the proc-macro server; rebuild the workspace with the server's toolchain:\n",
+            );
+            for mismatch in &self.proc_macro_abi_mismatches {
+                format_to!(message, "- {mismatch}\n");
+            }
+            message.push('\n');
+        }
 
         if self.config.linked_or_discovered_projects().is_empty()
             && self.config.detached_files().is_empty()
@@ -236,7 +354,18 @@ impl GlobalState {
                 .map(ManifestPath::try_from)
                 .filter_map(Result::ok)
                 .collect();
-            let cargo_config = self.config.cargo();
+            let mut cargo_config = self.config.cargo();
+            // Layer the session-scoped `rust-analyzer/setCfgOverrides` cfgs over
+            // the configured ones.
+            for (krate, cfgs) in &self.cfg_overrides {
+                let diff = match krate {
+                    Some(krate) => {
+                        cargo_config.cfg_overrides.selective.entry(krate.clone()).or_default()
+                    }
+                    None => &mut cargo_config.cfg_overrides.global,
+                };
+                diff.extend_enabled(cfgs.iter().cloned());
+            }
             let discover_command = self.config.discover_workspace_config().cloned();
             let is_quiescent = !(self.discover_workspace_queue.op_in_progress()
                 || self.vfs_progress_config_version < self.vfs_config_version
@@ -369,49 +498,71 @@ impl GlobalState {
             };
 
             let mut builder = ProcMacrosBuilder::default();
+            let mut abi_mismatches = Vec::new();
             let chain = proc_macro_clients
                 .iter()
                 .map(|res| res.as_ref().map_err(|e| e.to_string()))
                 .chain(iter::repeat_with(|| Err("proc-macro-srv is not running".into())));
             for (client, paths) in chain.zip(paths) {
-                paths
-                    .into_iter()
-                    .map(move |(crate_id, res)| {
-                        (
-                            crate_id,
-                            res.map_or_else(
-                                |e| Err((e, true)),
-                                |(crate_name, path)| {
-                                    progress(path.to_string());
-                                    client.as_ref().map_err(|it| (it.clone(), true)).and_then(
-                                        |client| {
-                                            load_proc_macro(
-                                                client,
-                                                &path,
-                                                ignored_proc_macros
-                                                    .iter()
-                                                    .find_map(|(name, macros)| {
-                                                        eq_ignore_underscore(name, &crate_name)
-                                                            .then_some(&**macros)
-                                                    })
-                                                    .unwrap_or_default(),
-                                            )
-                                        },
+                for (crate_id, res) in paths {
+                    let res = res.map_or_else(
+                        |e| Err((e, true)),
+                        |(crate_name, path)| {
+                            progress(path.to_string());
+                            let res = client.as_ref().map_err(|it| (it.clone(), true)).and_then(
+                                |client| {
+                                    load_proc_macro(
+                                        client,
+                                        &path,
+                                        ignored_proc_macros
+                                            .iter()
+                                            .find_map(|(name, macros)| {
+                                                eq_ignore_underscore(name, &crate_name)
+                                                    .then_some(&**macros)
+                                            })
+                                            .unwrap_or_default(),
                                     )
                                 },
-                            ),
-                        )
-                    })
-                    .for_each(|(krate, res)| builder.insert(krate, res));
+                            );
+                            if let Err((e, _)) = &res {
+                                if e.contains(proc_macro_api::ABI_MISMATCH_TAG) {
+                                    abi_mismatches.push(format!("`{path}`: {e}"));
+                                }
+                            }
+                            res
+                        },
+                    );
+                    builder.insert(crate_id, res);
+                }
             }
+            // A dylib can host several proc-macro crates; report it once.
+            abi_mismatches.sort();
+            abi_mismatches.dedup();
 
-            sender.send(Task::LoadProcMacros(ProcMacroProgress::End(builder.build()))).unwrap();
+            sender
+                .send(Task::LoadProcMacros(ProcMacroProgress::End(builder.build(), abi_mismatches)))
+                .unwrap();
         });
     }
 
     pub(crate) fn set_proc_macros(&mut self, proc_macros: ProcMacros) {
         let mut change = ChangeWithProcMacros::new();
         change.set_proc_macros(proc_macros);
+        // Re-touch the files whose expansions failed, so they are expanded
+        // against the fresh proc-macro server even if the set of proc-macros
+        // itself did not change, e.g. after the server was restarted.
+        let failed_files = mem::take(&mut self.proc_macro_failed_files);
+        if !failed_files.is_empty() {
+            info!("retrying failed macro expansions in {} file(s)", failed_files.len());
+            let db = self.analysis_host.raw_database();
+            let texts = failed_files
+                .into_iter()
+                .map(|file_id| (file_id, db.file_text(file_id).to_string()))
+                .collect::<Vec<_>>();
+            for (file_id, text) in texts {
+                change.change_file(file_id, Some(text));
+            }
+        }
         self.analysis_host.apply_change(change);
     }
 
@@ -502,7 +653,9 @@ impl GlobalState {
                                 [
                                     (base.clone(), "**/*.rs"),
                                     (base.clone(), "**/Cargo.{lock,toml}"),
-                                    (base, "**/rust-analyzer.toml"),
+                                    (base.clone(), "**/rust-analyzer.toml"),
+                                    (base.clone(), "**/rust-toolchain"),
+                                    (base, "**/rust-toolchain.toml"),
                                 ]
                             })
                         })
@@ -527,6 +680,8 @@ impl GlobalState {
                                     format!("{base}/**/*.rs"),
                                     format!("{base}/**/Cargo.{{toml,lock}}"),
                                     format!("{base}/**/rust-analyzer.toml"),
+                                    format!("{base}/**/rust-toolchain"),
+                                    format!("{base}/**/rust-toolchain.toml"),
                                 ]
                             })
                         })
@@ -541,6 +696,7 @@ impl GlobalState {
                 iter::once(self.config.user_config_path().as_path())
                     .chain(self.workspaces.iter().map(|ws| ws.manifest().map(ManifestPath::as_ref)))
                     .flatten()
+                    .chain(self.workspaces.iter().flat_map(|ws| ws.env_files()))
                     .map(|glob_pattern| lsp_types::FileSystemWatcher {
                         glob_pattern: lsp_types::GlobPattern::String(glob_pattern.to_string()),
                         kind: None,
@@ -722,6 +878,43 @@ impl GlobalState {
         Err(buf)
     }
 
+    /// Publishes workspace loading failures as diagnostics on the offending
+    /// manifest, so the editor underlines the error instead of only showing a
+    /// popup. Diagnostics of manifests that loaded fine again are cleared.
+    pub(super) fn publish_workspace_fetch_diagnostics(&mut self) {
+        let mut live = FxHashSet::default();
+
+        if let Some((last_op_result, _)) = self.fetch_workspaces_queue.last_op_result() {
+            let diagnostics = last_op_result
+                .iter()
+                .filter_map(|ws| ws.as_ref().err())
+                .filter_map(|err| {
+                    let message = format!("{err:#}");
+                    let manifest = manifest_path_in_error_message(&message)?;
+                    Some((url_from_abs_path(&manifest), message))
+                })
+                .collect::<Vec<_>>();
+            for (url, message) in diagnostics {
+                let diagnostic = lsp_types::Diagnostic {
+                    range: toml_error_range(&message),
+                    severity: Some(lsp_types::DiagnosticSeverity::ERROR),
+                    source: Some("rust-analyzer".to_owned()),
+                    message,
+                    ..Default::default()
+                };
+                self.publish_diagnostics(url.clone(), None, vec![diagnostic]);
+                live.insert(url);
+            }
+        }
+
+        for url in mem::take(&mut self.workspace_fetch_diagnostics_sent) {
+            if !live.contains(&url) {
+                self.publish_diagnostics(url, None, Vec::new());
+            }
+        }
+        self.workspace_fetch_diagnostics_sent = live;
+    }
+
     pub(super) fn fetch_build_data_error(&self) -> Result<(), String> {
         let mut buf = String::new();
 
@@ -763,6 +956,8 @@ impl GlobalState {
                 None,
                 self.config.root_path().clone(),
                 None,
+                None,
+                FxHashSet::default(),
             )],
             crate::flycheck::InvocationStrategy::PerWorkspace => {
                 self.workspaces
@@ -776,13 +971,33 @@ impl GlobalState {
                                 | ProjectWorkspaceKind::DetachedFile {
                                     cargo: Some((cargo, _)),
                                     ..
-                                } => (cargo.workspace_root(), Some(cargo.manifest_path())),
+                                } => (
+                                    cargo.workspace_root(),
+                                    Some(cargo.manifest_path()),
+                                    // A workspace wide check is scoped to the
+                                    // `default-members`, unless configured otherwise.
+                                    self.config
+                                        .flycheck_default_members()
+                                        .then(|| cargo.default_members())
+                                        .flatten()
+                                        .map(|members| {
+                                            members
+                                                .into_iter()
+                                                .map(|pkg| cargo.package_flag(&cargo[pkg]))
+                                                .collect()
+                                        }),
+                                    cargo
+                                        .packages()
+                                        .filter(|&pkg| cargo[pkg].is_member)
+                                        .map(|pkg| cargo[pkg].manifest.to_string())
+                                        .collect(),
+                                ),
                                 ProjectWorkspaceKind::Json(project) => {
                                     // Enable flychecks for json projects if a custom flycheck command was supplied
                                     // in the workspace configuration.
                                     match config {
                                         FlycheckConfig::CustomCommand { .. } => {
-                                            (project.path(), None)
+                                            (project.path(), None, None, FxHashSet::default())
                                         }
                                         _ => return None,
                                     }
@@ -790,18 +1005,36 @@ impl GlobalState {
                                 ProjectWorkspaceKind::DetachedFile { .. } => return None,
                             },
                             ws.sysroot.root().map(ToOwned::to_owned),
+                            ws.toolchain.clone(),
                         ))
                     })
-                    .map(|(id, (root, manifest_path), sysroot_root)| {
-                        FlycheckHandle::spawn(
+                    .map(
+                        |(
                             id,
-                            sender.clone(),
-                            config.clone(),
+                            (root, manifest_path, default_members, member_manifests),
                             sysroot_root,
-                            root.to_path_buf(),
-                            manifest_path.map(|it| it.to_path_buf()),
-                        )
-                    })
+                            toolchain,
+                        )| {
+                            let mut config = config.clone();
+                            if let FlycheckConfig::CargoCommand { options, .. } = &mut config {
+                                // `--keep-going` is only accepted by cargo 1.74+, fall
+                                // back to not passing it on older (or unknown) toolchains.
+                                options.keep_going &= toolchain.is_some_and(|version| {
+                                    (version.major, version.minor) >= (1, 74)
+                                });
+                            }
+                            FlycheckHandle::spawn(
+                                id,
+                                sender.clone(),
+                                config,
+                                sysroot_root,
+                                root.to_path_buf(),
+                                manifest_path.map(|it| it.to_path_buf()),
+                                default_members,
+                                member_manifests,
+                            )
+                        },
+                    )
                     .collect()
             }
         }
@@ -874,7 +1107,9 @@ pub(crate) fn should_refresh_for_change(
         None => return false,
     };
 
-    if let "Cargo.toml" | "Cargo.lock" = file_name {
+    // A toolchain file switches the active toolchain, so the sysroot and cfgs
+    // need to be re-probed.
+    if let "Cargo.toml" | "Cargo.lock" | "rust-toolchain" | "rust-toolchain.toml" = file_name {
         return true;
     }
 
@@ -915,6 +1150,31 @@ pub(crate) fn should_refresh_for_change(
     false
 }
 
+/// Extracts the path of the `Cargo.toml` a workspace loading error complains
+/// about. Both project-model and cargo itself embed the manifest path in their
+/// error messages, the latter wrapped in backticks.
+fn manifest_path_in_error_message(message: &str) -> Option<AbsPathBuf> {
+    message
+        .split(|c: char| c.is_whitespace() || c == '`')
+        .filter(|token| token.ends_with("Cargo.toml"))
+        .find_map(|token| AbsPathBuf::try_from(token.trim_end_matches(',')).ok())
+}
+
+/// Computes the range to attach a manifest diagnostic to, by looking for
+/// cargo's `TOML parse error at line {line}, column {column}` report. Falls
+/// back to the start of the file when there is no such location.
+fn toml_error_range(message: &str) -> lsp_types::Range {
+    let position = message.split_once(" at line ").and_then(|(_, it)| {
+        let (line, rest) = it.split_once(", column ")?;
+        let column: u32 = rest.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()?;
+        let line: u32 = line.parse().ok()?;
+        // Cargo reports one-based positions.
+        Some(lsp_types::Position::new(line.saturating_sub(1), column.saturating_sub(1)))
+    });
+    let start = position.unwrap_or_default();
+    lsp_types::Range::new(start, lsp_types::Position::new(start.line, start.character + 1))
+}
+
 /// Similar to [`str::eq_ignore_ascii_case`] but instead of ignoring
 /// case, we say that `-` and `_` are equal.
 fn eq_ignore_underscore(s1: &str, s2: &str) -> bool {
@@ -17,15 +17,17 @@ mod testdir;
 use std::{collections::HashMap, path::PathBuf, time::Instant};
 
 use lsp_types::{
-    notification::DidOpenTextDocument,
+    notification::{DidChangeTextDocument, DidOpenTextDocument},
     request::{
         CodeActionRequest, Completion, Formatting, GotoTypeDefinition, HoverRequest,
         InlayHintRequest, InlayHintResolveRequest, WillRenameFiles, WorkspaceSymbolRequest,
     },
-    CodeActionContext, CodeActionParams, CompletionParams, DidOpenTextDocumentParams,
-    DocumentFormattingParams, FileRename, FormattingOptions, GotoDefinitionParams, HoverParams,
-    InlayHint, InlayHintLabel, InlayHintParams, PartialResultParams, Position, Range,
-    RenameFilesParams, TextDocumentItem, TextDocumentPositionParams, WorkDoneProgressParams,
+    CodeActionContext, CodeActionParams, CompletionParams, DidChangeTextDocumentParams,
+    DidOpenTextDocumentParams, DocumentFormattingParams, FileRename, FormattingOptions,
+    GotoDefinitionParams, HoverParams, InlayHint, InlayHintLabel, InlayHintParams,
+    PartialResultParams, Position, Range, RenameFilesParams, TextDocumentContentChangeEvent,
+    TextDocumentItem, TextDocumentPositionParams, VersionedTextDocumentIdentifier,
+    WorkDoneProgressParams,
 };
 use rust_analyzer::lsp::ext::{OnEnter, Runnables, RunnablesParams};
 use serde_json::json;
@@ -341,6 +343,132 @@ fn main() {}
     );
 }
 
+#[test]
+fn test_runnables_doctests_and_cfg_test_modules() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /foo/Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /foo/src/lib.rs
+/// ```
+/// foo::foo();
+/// ```
+pub fn foo() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn spam() {}
+}
+"#,
+    )
+    .root("foo")
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    server.request::<Runnables>(
+        RunnablesParams { text_document: server.doc_id("foo/src/lib.rs"), position: None },
+        json!([
+          {
+            "args": {
+              // Cargo cannot mix `--doc` with other target flags.
+              "cargoArgs": ["test", "--doc", "--package", "foo"],
+              "executableArgs": ["foo", "--show-output"],
+              "overrideCargo": null,
+              "cwd": server.path().join("foo"),
+              "workspaceRoot": server.path().join("foo")
+            },
+            "kind": "cargo",
+            "label": "doctest foo",
+            "location": {
+              "targetRange": {
+                "end": { "character": 15, "line": 3 },
+                "start": { "character": 0, "line": 0 }
+              },
+              "targetSelectionRange": {
+                "end": { "character": 15, "line": 3 },
+                "start": { "character": 0, "line": 0 }
+              },
+              "targetUri": "file:///[..]/src/lib.rs"
+            }
+          },
+          {
+            "args": {
+              "cargoArgs": ["test", "--package", "foo", "--lib"],
+              "executableArgs": ["tests", "--show-output"],
+              "overrideCargo": null,
+              "cwd": server.path().join("foo"),
+              "workspaceRoot": server.path().join("foo")
+            },
+            "kind": "cargo",
+            "label": "test-mod tests",
+            "location": {
+              "targetRange": {
+                "end": { "character": 1, "line": 9 },
+                "start": { "character": 0, "line": 5 }
+              },
+              "targetSelectionRange": {
+                "end": { "character": 9, "line": 6 },
+                "start": { "character": 4, "line": 6 }
+              },
+              "targetUri": "file:///[..]/src/lib.rs"
+            }
+          },
+          {
+            "args": {
+              "cargoArgs": ["test", "--package", "foo", "--lib"],
+              "executableArgs": ["tests::spam", "--exact", "--show-output"],
+              "overrideCargo": null,
+              "cwd": server.path().join("foo"),
+              "workspaceRoot": server.path().join("foo")
+            },
+            "kind": "cargo",
+            "label": "test tests::spam",
+            "location": {
+              "targetRange": {
+                "end": { "character": 16, "line": 8 },
+                "start": { "character": 4, "line": 7 }
+              },
+              "targetSelectionRange": {
+                "end": { "character": 11, "line": 8 },
+                "start": { "character": 7, "line": 8 }
+              },
+              "targetUri": "file:///[..]/src/lib.rs"
+            }
+          },
+          {
+            "args": {
+              "cargoArgs": ["check", "--package", "foo", "--all-targets"],
+              "executableArgs": [],
+              "overrideCargo": null,
+              "cwd": server.path().join("foo"),
+              "workspaceRoot": server.path().join("foo")
+            },
+            "kind": "cargo",
+            "label": "cargo check -p foo --all-targets"
+          },
+          {
+            "args": {
+              "cargoArgs": ["test", "--package", "foo", "--all-targets"],
+              "executableArgs": [],
+              "overrideCargo": null,
+              "cwd": server.path().join("foo"),
+              "workspaceRoot": server.path().join("foo")
+            },
+            "kind": "cargo",
+            "label": "cargo test -p foo --all-targets"
+          }
+        ]),
+    );
+}
+
 // Each package in these workspaces should be run from its own root
 #[test]
 fn test_path_dependency_runnables() {
@@ -907,6 +1035,75 @@ version = \"0.0.0\"
     );
 }
 
+#[test]
+fn incremental_did_change_affects_dos_line_endings_document() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        "
+//- /Cargo.toml
+[package]
+name = \"foo\"
+version = \"0.0.0\"
+
+//- /src/main.rs
+fn main() {}
+",
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    server.notification::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: server.doc_id("src/main.rs").uri,
+            language_id: "rust".to_owned(),
+            version: 0,
+            text: "fn main() {}\r\n".to_owned(),
+        },
+    });
+    // Multiple range edits in one notification; each is relative to the
+    // result of applying the previous ones.
+    server.notification::<DidChangeTextDocument>(DidChangeTextDocumentParams {
+        text_document: VersionedTextDocumentIdentifier {
+            uri: server.doc_id("src/main.rs").uri,
+            version: 1,
+        },
+        content_changes: vec![
+            TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(0, 0), Position::new(0, 0))),
+                range_length: None,
+                text: "/// Some Docs\r\n".to_owned(),
+            },
+            TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(1, 3), Position::new(1, 7))),
+                range_length: None,
+                text: "spam".to_owned(),
+            },
+        ],
+    });
+
+    // The edited overlay must have made it into the analysis with its line
+    // endings and offsets intact: `onEnter` inside the doc comment only
+    // triggers if the comment survived both edits, and the `\r\n` in the
+    // response shows the endings were preserved through normalization.
+    server.request::<OnEnter>(
+        TextDocumentPositionParams {
+            text_document: server.doc_id("src/main.rs"),
+            position: Position { line: 0, character: 8 },
+        },
+        json!([{
+            "insertTextFormat": 2,
+            "newText": "\r\n/// $0",
+            "range": {
+            "end": { "line": 0, "character": 8 },
+            "start": { "line": 0, "character": 8 }
+            }
+        }]),
+    );
+}
+
 fn out_dirs_check_impl(root_contains_symlink: bool) {
     if skip_slow_tests() {
         return;
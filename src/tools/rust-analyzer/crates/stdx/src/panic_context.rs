@@ -2,7 +2,7 @@
 //!
 //! FIXME: upstream to <https://github.com/kriomant/panic-context> ?
 
-use std::{cell::RefCell, panic, sync::Once};
+use std::{backtrace::Backtrace, cell::RefCell, panic, sync::Once};
 
 pub fn enter(context: String) -> PanicContext {
     static ONCE: Once = Once::new();
@@ -23,6 +23,7 @@ impl PanicContext {
         let default_hook = panic::take_hook();
         #[allow(deprecated)]
         let hook = move |panic_info: &panic::PanicInfo<'_>| {
+            BACKTRACE.with(|bt| *bt.borrow_mut() = Some(Backtrace::force_capture()));
             with_ctx(|ctx| {
                 if !ctx.is_empty() {
                     eprintln!("Panic context:");
@@ -43,6 +44,19 @@ impl Drop for PanicContext {
     }
 }
 
+/// Returns the backtrace of the most recent panic on the current thread, if
+/// the hook installed by [`enter`] captured one.
+///
+/// Unwinds that bypass the panic hook, such as salsa cancellation, leave no
+/// backtrace behind.
+pub fn take_backtrace() -> Option<Backtrace> {
+    BACKTRACE.with(|bt| bt.borrow_mut().take())
+}
+
+thread_local! {
+    static BACKTRACE: RefCell<Option<Backtrace>> = const { RefCell::new(None) };
+}
+
 fn with_ctx(f: impl FnOnce(&mut Vec<String>)) {
     thread_local! {
         static CTX: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
@@ -8,9 +8,12 @@
 //! crates.io, and we can reduce this to trivial glue code.
 
 use std::{
-    fs,
+    fs, mem,
     path::{Component, Path},
-    sync::atomic::AtomicUsize,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 use crossbeam_channel::{select, unbounded, Receiver, Sender};
@@ -25,9 +28,19 @@ use walkdir::WalkDir;
 pub struct NotifyHandle {
     // Relative order of fields below is significant.
     sender: Sender<Message>,
+    stats: Arc<Counters>,
     _thread: stdx::thread::JoinHandle,
 }
 
+/// Shared counters behind [`loader::Handle::stats`], incremented by the
+/// [`NotifyActor`] thread.
+#[derive(Debug, Default)]
+struct Counters {
+    events_received: AtomicU64,
+    files_scanned: AtomicU64,
+    watch_registrations: AtomicU64,
+}
+
 #[derive(Debug)]
 enum Message {
     Config(loader::Config),
@@ -36,13 +49,14 @@ enum Message {
 
 impl loader::Handle for NotifyHandle {
     fn spawn(sender: loader::Sender) -> NotifyHandle {
-        let actor = NotifyActor::new(sender);
+        let stats = Arc::new(Counters::default());
+        let actor = NotifyActor::new(sender, Arc::clone(&stats));
         let (sender, receiver) = unbounded::<Message>();
         let thread = stdx::thread::Builder::new(stdx::thread::ThreadIntent::Worker)
             .name("VfsLoader".to_owned())
             .spawn(move || actor.run(receiver))
             .expect("failed to spawn thread");
-        NotifyHandle { sender, _thread: thread }
+        NotifyHandle { sender, stats, _thread: thread }
     }
 
     fn set_config(&mut self, config: loader::Config) {
@@ -56,12 +70,30 @@ impl loader::Handle for NotifyHandle {
     fn load_sync(&mut self, path: &AbsPath) -> Option<Vec<u8>> {
         read(path)
     }
+
+    fn stats(&self) -> loader::Stats {
+        loader::Stats {
+            events_received: self.stats.events_received.load(Ordering::Relaxed),
+            files_scanned: self.stats.files_scanned.load(Ordering::Relaxed),
+            watch_registrations: self.stats.watch_registrations.load(Ordering::Relaxed),
+        }
+    }
 }
 
 type NotifyEvent = notify::Result<notify::Event>;
 
+/// Number of files sent per [`loader::Message::Loaded`] message during the
+/// initial scan.
+///
+/// Reading a whole directory entry into a single message buffers the contents
+/// of every file in it at once, which for big monorepos means hundreds of
+/// megabytes of peak memory. Streaming the contents in bounded chunks lets the
+/// receiving end apply (and drop) them incrementally instead.
+const LOADED_CHUNK_SIZE: usize = 512;
+
 struct NotifyActor {
     sender: loader::Sender,
+    stats: Arc<Counters>,
     watched_file_entries: FxHashSet<AbsPathBuf>,
     watched_dir_entries: Vec<loader::Directories>,
     // Drop order is significant.
@@ -75,9 +107,10 @@ enum Event {
 }
 
 impl NotifyActor {
-    fn new(sender: loader::Sender) -> NotifyActor {
+    fn new(sender: loader::Sender, stats: Arc<Counters>) -> NotifyActor {
         NotifyActor {
             sender,
+            stats,
             watched_dir_entries: Vec::new(),
             watched_file_entries: FxHashSet::default(),
             watcher: None,
@@ -138,7 +171,7 @@ impl NotifyActor {
                             if do_watch {
                                 _ = entry_tx.send(entry.clone());
                             }
-                            let files = Self::load_entry(
+                            Self::load_entry(
                                 |f| _ = watch_tx.send(f.to_owned()),
                                 entry,
                                 do_watch,
@@ -152,8 +185,13 @@ impl NotifyActor {
                                         config_version,
                                     });
                                 },
+                                |files| {
+                                    self.stats
+                                        .files_scanned
+                                        .fetch_add(files.len() as u64, Ordering::Relaxed);
+                                    self.send(loader::Message::Loaded { files })
+                                },
                             );
-                            self.send(loader::Message::Loaded { files });
                             self.send(loader::Message::Progress {
                                 n_total,
                                 n_done: LoadingProgress::Progress(
@@ -195,6 +233,7 @@ impl NotifyActor {
                     }
                 },
                 Event::NotifyEvent(event) => {
+                    self.stats.events_received.fetch_add(1, Ordering::Relaxed);
                     if let Some(event) = log_notify_error(event) {
                         if let EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) =
                             event.kind
@@ -252,21 +291,26 @@ impl NotifyActor {
         entry: loader::Entry,
         do_watch: bool,
         send_message: impl Fn(AbsPathBuf),
-    ) -> Vec<(AbsPathBuf, Option<Vec<u8>>)> {
+        send_loaded: impl Fn(Vec<(AbsPathBuf, Option<Vec<u8>>)>),
+    ) {
+        let mut buf = Vec::new();
+        let push = |buf: &mut Vec<_>, file: AbsPathBuf| {
+            let contents = read(file.as_path());
+            buf.push((file, contents));
+            if buf.len() >= LOADED_CHUNK_SIZE {
+                send_loaded(mem::take(buf));
+            }
+        };
         match entry {
-            loader::Entry::Files(files) => files
-                .into_iter()
-                .map(|file| {
+            loader::Entry::Files(files) => {
+                for file in files {
                     if do_watch {
                         watch(file.as_ref());
                     }
-                    let contents = read(file.as_path());
-                    (file, contents)
-                })
-                .collect::<Vec<_>>(),
+                    push(&mut buf, file);
+                }
+            }
             loader::Entry::Directories(dirs) => {
-                let mut res = Vec::new();
-
                 for root in &dirs.include {
                     send_message(root.clone());
                     let walkdir =
@@ -308,19 +352,20 @@ impl NotifyActor {
                         Some(abs_path)
                     });
 
-                    res.extend(files.map(|file| {
-                        let contents = read(file.as_path());
-                        (file, contents)
-                    }));
+                    files.for_each(|file| push(&mut buf, file));
                 }
-                res
             }
         }
+        if !buf.is_empty() {
+            send_loaded(buf);
+        }
     }
 
     fn watch(&mut self, path: &Path) {
         if let Some((watcher, _)) = &mut self.watcher {
-            log_notify_error(watcher.watch(path, RecursiveMode::NonRecursive));
+            if log_notify_error(watcher.watch(path, RecursiveMode::NonRecursive)).is_some() {
+                self.stats.watch_registrations.fetch_add(1, Ordering::Relaxed);
+            }
         }
     }
 
@@ -133,11 +133,18 @@ impl ChangedFile {
         matches!(self.change, Change::Modify(_, _))
     }
 
+    /// Returns `true` if the change affects the existence or path of the file,
+    /// so file sets need to be rebuilt.
+    pub fn changes_structure(&self) -> bool {
+        matches!(self.change, Change::Create(_, _) | Change::Delete | Change::Rename)
+    }
+
     pub fn kind(&self) -> ChangeKind {
         match self.change {
             Change::Create(_, _) => ChangeKind::Create,
             Change::Modify(_, _) => ChangeKind::Modify,
             Change::Delete => ChangeKind::Delete,
+            Change::Rename => ChangeKind::Rename,
         }
     }
 }
@@ -151,6 +158,8 @@ pub enum Change {
     Modify(Vec<u8>, u64),
     /// The file was deleted
     Delete,
+    /// The file was moved to a new path, its content unchanged
+    Rename,
 }
 
 /// Kind of [file change](ChangedFile).
@@ -162,6 +171,8 @@ pub enum ChangeKind {
     Modify,
     /// The file was deleted
     Delete,
+    /// The file was moved to a new path, its content unchanged
+    Rename,
 }
 
 impl Vfs {
@@ -222,6 +233,8 @@ impl Vfs {
             self.data[file_id.0 as usize] = match change_kind {
                 &Change::Create(_, hash) | &Change::Modify(_, hash) => FileState::Exists(hash),
                 Change::Delete => FileState::Deleted,
+                // renames don't change the content hash
+                Change::Rename => return,
             };
         };
 
@@ -255,6 +268,13 @@ impl Vfs {
                     }
                     // shouldn't occur, but keep the Create
                     (prev @ Modify(_, _), new @ Create(_, _)) => *prev = new,
+                    // a rename followed by a content change acts as a
+                    // (re-)creation at the new path
+                    (change @ Rename, Create(new, new_hash) | Modify(new, new_hash)) => {
+                        *change = Create(new, new_hash);
+                    }
+                    // `set_file_contents` never produces renames
+                    (_, Rename) => stdx::never!(),
                 }
                 set_data(&o.get().change);
             }
@@ -264,6 +284,38 @@ impl Vfs {
         true
     }
 
+    /// Moves the file at `from` to `to`, keeping its [`FileId`] and content.
+    ///
+    /// Because the id and the content hash are preserved, consumers only need
+    /// to rebuild their file sets; data derived from the file's text stays
+    /// valid. Records a [`Change::Rename`] for the file.
+    ///
+    /// Returns `false`, without doing anything, if `from` does not exist, if
+    /// `to` already holds a file, or if there already is a pending change
+    /// recorded for `from`.
+    pub fn rename(&mut self, from: &VfsPath, to: VfsPath) -> bool {
+        let Some(file_id) = self.file_id(from) else { return false };
+        if self.file_id(&to).is_some() || self.changes.contains_key(&file_id) {
+            return false;
+        }
+        self.interner.rename(file_id, to);
+        self.changes.insert(file_id, ChangedFile { file_id, change: Change::Rename });
+        true
+    }
+
+    /// Content hash of the file, if it exists.
+    pub fn file_content_hash(&self, file_id: FileId) -> Option<u64> {
+        match self.get(file_id) {
+            FileState::Exists(hash) => Some(hash),
+            FileState::Deleted => None,
+        }
+    }
+
+    /// Returns `true` if there are changes that have not been [taken](Vfs::take_changes) yet.
+    pub fn has_changes(&self) -> bool {
+        !self.changes.is_empty()
+    }
+
     /// Drain and returns all the changes in the `Vfs`.
     pub fn take_changes(&mut self) -> IndexMap<FileId, ChangedFile, BuildHasherDefault<FxHasher>> {
         mem::take(&mut self.changes)
@@ -304,3 +356,57 @@ impl fmt::Debug for Vfs {
         f.debug_struct("Vfs").field("n_files", &self.data.len()).finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(s: &str) -> VfsPath {
+        VfsPath::new_virtual_path(s.to_owned())
+    }
+
+    #[test]
+    fn rename_preserves_file_id_and_content() {
+        let mut vfs = Vfs::default();
+        vfs.set_file_contents(path("/foo/a.rs"), Some(b"fn f() {}".to_vec()));
+        let file_id = vfs.file_id(&path("/foo/a.rs")).unwrap();
+        let hash = vfs.file_content_hash(file_id).unwrap();
+        vfs.take_changes();
+
+        assert!(vfs.rename(&path("/foo/a.rs"), path("/bar/a.rs")));
+        assert_eq!(vfs.file_id(&path("/bar/a.rs")), Some(file_id));
+        assert_eq!(vfs.file_id(&path("/foo/a.rs")), None);
+        assert_eq!(vfs.file_path(file_id), &path("/bar/a.rs"));
+        assert_eq!(vfs.file_content_hash(file_id), Some(hash));
+
+        let changes = vfs.take_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[&file_id].change, Change::Rename);
+    }
+
+    #[test]
+    fn rename_refuses_missing_source_and_occupied_target() {
+        let mut vfs = Vfs::default();
+        vfs.set_file_contents(path("/a.rs"), Some(b"a".to_vec()));
+        vfs.set_file_contents(path("/b.rs"), Some(b"b".to_vec()));
+        vfs.take_changes();
+
+        assert!(!vfs.rename(&path("/missing.rs"), path("/c.rs")));
+        assert!(!vfs.rename(&path("/a.rs"), path("/b.rs")));
+        assert!(vfs.take_changes().is_empty());
+    }
+
+    #[test]
+    fn recreating_the_old_path_allocates_a_fresh_id() {
+        let mut vfs = Vfs::default();
+        vfs.set_file_contents(path("/foo/a.rs"), Some(b"fn f() {}".to_vec()));
+        let file_id = vfs.file_id(&path("/foo/a.rs")).unwrap();
+        vfs.take_changes();
+
+        assert!(vfs.rename(&path("/foo/a.rs"), path("/bar/a.rs")));
+        vfs.set_file_contents(path("/foo/a.rs"), Some(b"fn g() {}".to_vec()));
+        let new_id = vfs.file_id(&path("/foo/a.rs")).unwrap();
+        assert_ne!(new_id, file_id);
+        assert_eq!(vfs.file_id(&path("/bar/a.rs")), Some(file_id));
+    }
+}
@@ -43,6 +43,20 @@ pub struct Config {
     pub watch: Vec<usize>,
 }
 
+/// Counters describing the work a [`Handle`] has performed so far.
+///
+/// These exist purely for debugging: if `events_received` stays at zero after
+/// a file was known to change, the file watcher is likely broken.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Stats {
+    /// Number of change events received from the file watcher.
+    pub events_received: u64,
+    /// Number of files read during initial scans.
+    pub files_scanned: u64,
+    /// Number of paths successfully registered with the file watcher.
+    pub watch_registrations: u64,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum LoadingProgress {
     Started,
@@ -90,6 +104,11 @@ pub trait Handle: fmt::Debug {
     /// Load the content of the given file, returning [`None`] if it does not
     /// exists.
     fn load_sync(&mut self, path: &AbsPath) -> Option<Vec<u8>>;
+
+    /// Returns counters describing the work done so far, for debugging.
+    fn stats(&self) -> Stats {
+        Stats::default()
+    }
 }
 
 impl Entry {
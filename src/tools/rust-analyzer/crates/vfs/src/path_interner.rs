@@ -1,17 +1,19 @@
 //! Maps paths to compact integer ids. We don't care about clearings paths which
 //! no longer exist -- the assumption is total size of paths we ever look at is
 //! not too big.
-use std::hash::BuildHasherDefault;
-
-use indexmap::IndexSet;
-use rustc_hash::FxHasher;
+use rustc_hash::FxHashMap;
 
 use crate::{FileId, VfsPath};
 
 /// Structure to map between [`VfsPath`] and [`FileId`].
+///
+/// Ids are stable, but the path an id refers to can be updated via
+/// [`rename`](PathInterner::rename), which is what keeps `FileId`s alive
+/// across directory moves.
 #[derive(Default)]
 pub(crate) struct PathInterner {
-    map: IndexSet<VfsPath, BuildHasherDefault<FxHasher>>,
+    map: FxHashMap<VfsPath, FileId>,
+    vec: Vec<VfsPath>,
 }
 
 impl PathInterner {
@@ -19,7 +21,7 @@ impl PathInterner {
     ///
     /// If `path` does not exists in `self`, returns [`None`].
     pub(crate) fn get(&self, path: &VfsPath) -> Option<FileId> {
-        self.map.get_index_of(path).map(|i| FileId(i as u32))
+        self.map.get(path).copied()
     }
 
     /// Insert `path` in `self`.
@@ -27,9 +29,15 @@ impl PathInterner {
     /// - If `path` already exists in `self`, returns its associated id;
     /// - Else, returns a newly allocated id.
     pub(crate) fn intern(&mut self, path: VfsPath) -> FileId {
-        let (id, _added) = self.map.insert_full(path);
+        if let Some(id) = self.get(&path) {
+            return id;
+        }
+        let id = self.vec.len();
         assert!(id < u32::MAX as usize);
-        FileId(id as u32)
+        let id = FileId(id as u32);
+        self.map.insert(path.clone(), id);
+        self.vec.push(path);
+        id
     }
 
     /// Returns the path corresponding to `id`.
@@ -38,6 +46,16 @@ impl PathInterner {
     ///
     /// Panics if `id` does not exists in `self`.
     pub(crate) fn lookup(&self, id: FileId) -> &VfsPath {
-        self.map.get_index(id.0 as usize).unwrap()
+        &self.vec[id.0 as usize]
+    }
+
+    /// Re-points `id` from its current path to `to`, so lookups of `to`
+    /// resolve to `id`.
+    ///
+    /// The old path is forgotten; interning it again allocates a fresh id.
+    pub(crate) fn rename(&mut self, id: FileId, to: VfsPath) {
+        let old = std::mem::replace(&mut self.vec[id.0 as usize], to.clone());
+        self.map.remove(&old);
+        self.map.insert(to, id);
     }
 }
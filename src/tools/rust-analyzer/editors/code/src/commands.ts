@@ -49,7 +49,7 @@ export function analyzerStatus(ctx: CtxInit): Cmd {
             if (doc != null) {
                 params.textDocument = client.code2ProtocolConverter.asTextDocumentIdentifier(doc);
             }
-            return await client.sendRequest(ra.analyzerStatus, params);
+            return (await client.sendRequest(ra.analyzerStatus, params)).status;
         }
 
         get onDidChange(): vscode.Event<vscode.Uri> {
@@ -26,7 +26,7 @@ export type CommandLinkGroup = {
 
 // rust-analyzer extensions
 
-export const analyzerStatus = new lc.RequestType<AnalyzerStatusParams, string, void>(
+export const analyzerStatus = new lc.RequestType<AnalyzerStatusParams, AnalyzerStatusResult, void>(
     "rust-analyzer/analyzerStatus",
 );
 export const cancelFlycheck = new lc.NotificationType0("rust-analyzer/cancelFlycheck");
@@ -111,6 +111,12 @@ export const changeTestState = new lc.NotificationType<ChangeTestStateParams>(
 );
 
 export type AnalyzerStatusParams = { textDocument?: lc.TextDocumentIdentifier };
+export type AnalyzerStatusHealth = "healthy" | "degraded" | "loading" | "error";
+export type AnalyzerStatusResult = {
+    health: AnalyzerStatusHealth;
+    reasons: string[];
+    status: string;
+};
 
 export interface FetchDependencyListParams {}
 
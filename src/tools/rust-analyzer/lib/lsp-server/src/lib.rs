@@ -58,6 +58,15 @@ impl Connection {
     /// Use this to create a real language server.
     pub fn listen<A: ToSocketAddrs>(addr: A) -> io::Result<(Connection, IoThreads)> {
         let listener = TcpListener::bind(addr)?;
+        Self::accept(&listener)
+    }
+
+    /// Accept a connection on an already bound tcp listener.
+    /// This call blocks until a connection is established.
+    ///
+    /// Use this to create a real language server that serves clients one after
+    /// another over the same address.
+    pub fn accept(listener: &TcpListener) -> io::Result<(Connection, IoThreads)> {
         let (stream, _) = listener.accept()?;
         let (sender, receiver, io_threads) = socket::socket_transport(stream);
         Ok((Connection { sender, receiver }, io_threads))
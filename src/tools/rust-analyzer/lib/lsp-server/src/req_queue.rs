@@ -53,6 +53,11 @@ impl<I> Incoming<I> {
     pub fn is_completed(&self, id: &RequestId) -> bool {
         !self.pending.contains_key(id)
     }
+
+    /// Returns an iterator over all pending requests and their associated data.
+    pub fn iter(&self) -> impl Iterator<Item = (&RequestId, &I)> {
+        self.pending.iter()
+    }
 }
 
 impl<O> Outgoing<O> {
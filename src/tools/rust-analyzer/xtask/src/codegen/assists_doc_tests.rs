@@ -53,6 +53,23 @@ r#####"
         );
     }
 
+    {
+        // Generate the list of assist ids, used to answer `rust-analyzer/listAssists`.
+
+        let buf = assists
+            .iter()
+            .fold("pub const ASSIST_IDS: &[&str] = &[\n".to_owned(), |mut acc, it| {
+                format_to_acc!(acc, "    \"{}\",\n", it.id)
+            });
+        let buf = add_preamble(crate::flags::CodegenType::AssistsDocTests, format!("{buf}];\n"));
+        ensure_file_contents(
+            crate::flags::CodegenType::AssistsDocTests,
+            &project_root().join("crates/ide-assists/src/ids.rs"),
+            &buf,
+            check,
+        );
+    }
+
     {
         // Generate assists manual. Note that we do _not_ commit manual to the
         // git repo. Instead, `cargo xtask release` runs this test before making